@@ -6,7 +6,7 @@ use rusty_gql_axum::*;
 use std::{net::SocketAddr, path::Path};
 
 use axum::{
-    extract::Extension,
+    extract::{Extension, WebSocketUpgrade},
     response::{self, IntoResponse},
     routing::get,
     AddExtensionLayer, Router,
@@ -20,6 +20,19 @@ async fn gql_handler(container: Extension<ContainerType>, req: GqlRequest) -> Gq
     GqlResponse::from(result)
 }
 
+async fn gql_sse_handler(container: Extension<ContainerType>, req: GqlRequest) -> GqlSseResponse {
+    GqlSseResponse::from(execute_stream(&container, req.0))
+}
+
+async fn gql_ws_handler(
+    container: Extension<ContainerType>,
+    GqlWebSocketProtocol(protocol): GqlWebSocketProtocol,
+    ws: WebSocketUpgrade,
+) -> impl IntoResponse {
+    ws.protocols([protocol.sec_websocket_protocol()])
+        .on_upgrade(move |socket| GqlWebSocket::new(socket, &container, protocol).serve())
+}
+
 async fn gql_playground() -> impl IntoResponse {
     response::Html(playground_html("/", None))
 }
@@ -40,6 +53,8 @@ async fn main() {
     let app = Router::new()
         .route("/graphiql", get(gql_playground))
         .route("/", get(gql_handler).post(gql_handler))
+        .route("/ws", get(gql_ws_handler))
+        .route("/sse", get(gql_sse_handler).post(gql_sse_handler))
         .layer(AddExtensionLayer::new(container));
     let addr = SocketAddr::from(([127, 0, 0, 1], 3000));
     axum::Server::bind(&addr)
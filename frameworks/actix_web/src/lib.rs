@@ -1,14 +1,47 @@
 mod request;
 mod response;
+mod subscription;
 
 pub use request::GqlRequest;
 pub use response::GqlResponse;
+pub use subscription::{GqlWebSocket, GqlWebSocketProtocol};
 
 #[cfg(test)]
 mod tests {
-    #[test]
-    fn it_works() {
-        let result = 2 + 2;
-        assert_eq!(result, 4);
+    use actix_web::{http::header, test::TestRequest, FromRequest};
+    use rusty_gql::WebSocketProtocol;
+
+    use crate::{GqlRequest, GqlWebSocketProtocol};
+
+    #[actix_rt::test]
+    async fn test_gql_request() {
+        let (req, mut payload) = TestRequest::get()
+            .uri("/?query=%7B%20value%20%7D")
+            .to_http_parts();
+        let request = GqlRequest::from_request(&req, &mut payload).await.unwrap();
+        assert_eq!(request.0.query, "{ value }");
+
+        let (req, mut payload) = TestRequest::post()
+            .set_payload(r#"{"query":"{ value }","operationName":"Value"}"#)
+            .to_http_parts();
+        let request = GqlRequest::from_request(&req, &mut payload).await.unwrap();
+        assert_eq!(request.0.query, "{ value }");
+        assert_eq!(request.0.operation_name, Some("Value".to_string()));
+    }
+
+    #[actix_rt::test]
+    async fn test_gql_websocket_protocol() {
+        let (req, mut payload) = TestRequest::get()
+            .insert_header((header::SEC_WEBSOCKET_PROTOCOL, "graphql-ws"))
+            .to_http_parts();
+        let protocol = GqlWebSocketProtocol::from_request(&req, &mut payload)
+            .await
+            .unwrap();
+        assert_eq!(protocol.0, WebSocketProtocol::SubscriptionsTransportWs);
+
+        let (req, mut payload) = TestRequest::get().to_http_parts();
+        assert!(GqlWebSocketProtocol::from_request(&req, &mut payload)
+            .await
+            .is_err());
     }
 }
@@ -1,25 +1,34 @@
-use std::pin::Pin;
-
 use actix_web::{
-    http::{Error, Method},
-    Error, FromRequest, Result,
+    dev::Payload, error::ErrorBadRequest, http::Method, web::Bytes, Error, FromRequest, HttpRequest,
 };
-use futures::Future;
+use futures_util::future::LocalBoxFuture;
+use rusty_gql::receive_http_request;
 
 pub struct GqlRequest(pub rusty_gql::Request);
 
 impl FromRequest for GqlRequest {
     type Error = Error;
 
-    type Future;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
 
-    fn from_request(
-        req: &actix_web::HttpRequest,
-        payload: &mut actix_web::dev::Payload,
-    ) -> Self::Future {
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
         if req.method() == Method::GET {
-            let body = serde_urlencoded::from_str(req.query_string());
-            Box::pin(async move { Ok(Self(rusty_gql::Request(body?))) })
+            let res = serde_urlencoded::from_str(req.query_string()).map_err(|err| {
+                ErrorBadRequest(format!(
+                    "failed to parse graphql requst from query params: {}",
+                    err
+                ))
+            });
+            Box::pin(async move { Ok(Self(res?)) })
+        } else {
+            let body = Bytes::from_request(req, payload);
+            Box::pin(async move {
+                let body = body.await?;
+                let request = receive_http_request(&body[..])
+                    .await
+                    .map_err(|err| ErrorBadRequest(format!("{:?}", err)))?;
+                Ok(Self(request))
+            })
         }
     }
 }
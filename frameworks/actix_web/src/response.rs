@@ -1,8 +1,4 @@
-use actix_web::{
-    http::{Error, StatusCode},
-    HttpResponse, Responder,
-};
-use futures_util::future::{ready, Ready};
+use actix_web::{body::BoxBody, HttpRequest, HttpResponse, Responder};
 
 pub struct GqlResponse(pub rusty_gql::Response);
 
@@ -13,9 +9,9 @@ impl From<rusty_gql::Response> for GqlResponse {
 }
 
 impl Responder for GqlResponse {
-    type Body = HttpResponse;
+    type Body = BoxBody;
 
-    fn respond_to(self, _: &actix_web::HttpRequest) -> HttpResponse {
+    fn respond_to(self, _: &HttpRequest) -> HttpResponse {
         let body = serde_json::to_string(&self.0).unwrap();
         HttpResponse::Ok()
             .content_type("application/json")
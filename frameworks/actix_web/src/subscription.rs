@@ -0,0 +1,135 @@
+use std::time::Duration;
+
+use actix_web::{
+    dev::Payload,
+    error::ErrorBadRequest,
+    http::header::{self, HeaderValue},
+    web, Error, FromRequest, HttpRequest, HttpResponse,
+};
+use actix_ws::{CloseCode, CloseReason, Message};
+use futures_channel::mpsc;
+use futures_util::{
+    future::{self, Ready},
+    Future, StreamExt,
+};
+use rusty_gql::{
+    Container, Data, GqlError, GqlValue, SelectionSetResolver, SubscriptionResolver,
+    WebSocketConnection, WebSocketProtocol, WsMessage,
+};
+
+pub struct GqlWebSocketProtocol(pub WebSocketProtocol);
+
+impl FromRequest for GqlWebSocketProtocol {
+    type Error = Error;
+
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let protocol = req
+            .headers()
+            .get(header::SEC_WEBSOCKET_PROTOCOL)
+            .and_then(|value| value.to_str().ok())
+            .and_then(WebSocketProtocol::from_sec_websocket_protocol)
+            .map(GqlWebSocketProtocol)
+            .ok_or_else(|| {
+                ErrorBadRequest("Sec-WebSocket-Protocol must be graphql-transport-ws or graphql-ws")
+            });
+        future::ready(protocol)
+    }
+}
+
+pub struct GqlWebSocket<Query, Mutation, Subscription>
+where
+    Query: SelectionSetResolver + 'static,
+    Mutation: SelectionSetResolver + 'static,
+    Subscription: SubscriptionResolver + 'static,
+{
+    protocol: WebSocketProtocol,
+    connection: WebSocketConnection<Query, Mutation, Subscription>,
+}
+
+impl<Query, Mutation, Subscription> GqlWebSocket<Query, Mutation, Subscription>
+where
+    Query: SelectionSetResolver + 'static,
+    Mutation: SelectionSetResolver + 'static,
+    Subscription: SubscriptionResolver + 'static,
+{
+    pub fn new(
+        container: &Container<Query, Mutation, Subscription>,
+        protocol: WebSocketProtocol,
+    ) -> Self {
+        GqlWebSocket {
+            protocol,
+            connection: WebSocketConnection::new(container, protocol),
+        }
+    }
+
+    pub fn on_connection_init<F, Fut>(mut self, callback: F) -> Self
+    where
+        F: FnOnce(GqlValue) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<Data, GqlError>> + Send + 'static,
+    {
+        self.connection = self.connection.on_connection_init(callback);
+        self
+    }
+
+    pub fn connection_init_timeout(mut self, timeout: Duration) -> Self {
+        self.connection = self.connection.connection_init_timeout(timeout);
+        self
+    }
+
+    pub fn start(self, req: &HttpRequest, body: web::Payload) -> Result<HttpResponse, Error> {
+        let (mut response, session, mut stream) = actix_ws::handle(req, body)?;
+        response.headers_mut().insert(
+            header::SEC_WEBSOCKET_PROTOCOL,
+            HeaderValue::from_static(self.protocol.sec_websocket_protocol()),
+        );
+
+        // The actix message stream is not `Send`, so incoming messages are forwarded through a channel.
+        let (tx, rx) = mpsc::unbounded();
+        let mut pong_session = session.clone();
+        actix_web::rt::spawn(async move {
+            while let Some(Ok(message)) = stream.next().await {
+                let message = match message {
+                    Message::Text(text) => text.into_bytes(),
+                    Message::Binary(binary) => binary,
+                    Message::Ping(bytes) => {
+                        let _ = pong_session.pong(&bytes).await;
+                        continue;
+                    }
+                    Message::Close(_) => break,
+                    _ => continue,
+                };
+                if tx.unbounded_send(message).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut outgoing = self.connection.serve(rx);
+        let mut session = session;
+        actix_web::rt::spawn(async move {
+            while let Some(message) = outgoing.next().await {
+                match message {
+                    WsMessage::Text(text) => {
+                        if session.text(text).await.is_err() {
+                            return;
+                        }
+                    }
+                    WsMessage::Close(code, reason) => {
+                        let _ = session
+                            .close(Some(CloseReason {
+                                code: CloseCode::from(code),
+                                description: Some(reason),
+                            }))
+                            .await;
+                        return;
+                    }
+                }
+            }
+            let _ = session.close(None).await;
+        });
+
+        Ok(response)
+    }
+}
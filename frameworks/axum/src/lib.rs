@@ -1,5 +1,9 @@
 mod request;
 mod response;
+mod sse;
+mod subscription;
 
-pub use request::GqlRequest;
-pub use response::GqlResponse;
+pub use request::{GqlBatchRequest, GqlRequest};
+pub use response::{GqlBatchResponse, GqlIncrementalResponse, GqlResponse};
+pub use sse::{GqlSseConnections, GqlSseResponse, GqlSseToken, SSE_TOKEN_HEADER};
+pub use subscription::{GqlWebSocket, GqlWebSocketProtocol};
@@ -1,23 +1,34 @@
 use axum::extract::{BodyStream, FromRequest};
-use axum::http::{Method, StatusCode};
+use axum::http::{header, Method, StatusCode};
 use axum::response::{IntoResponse, Response};
 use axum::{body, BoxError};
 use bytes::Bytes;
 use futures_util::TryStreamExt;
-use rusty_gql::{receive_http_request, HttpRequestError};
+use rusty_gql::{
+    receive_batch_http_request, receive_multipart_request, BatchRequest, HttpRequestError,
+    MultipartOptions,
+};
 use tokio_util::compat::TokioAsyncReadCompatExt;
 
 pub struct GqlRequest(pub rusty_gql::Request);
 
+/// Extracts a single request or a batch of requests.
+///
+/// A `multipart/form-data` body is read as a GraphQL multipart request. Its limits are read from the
+/// `MultipartOptions` extension, e.g. `.layer(AddExtensionLayer::new(MultipartOptions { .. }))`, and
+/// default to `MultipartOptions::default()`.
+pub struct GqlBatchRequest(pub BatchRequest);
+
 pub struct GqlRejection(pub HttpRequestError);
 
 impl IntoResponse for GqlRejection {
     fn into_response(self) -> Response {
+        let status = match self.0 {
+            HttpRequestError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            _ => StatusCode::BAD_REQUEST,
+        };
         let body = body::boxed(body::Full::from(format!("{:?}", self.0)));
-        Response::builder()
-            .status(StatusCode::BAD_REQUEST)
-            .body(body)
-            .unwrap()
+        Response::builder().status(status).body(body).unwrap()
     }
 }
 
@@ -29,6 +40,22 @@ impl From<HttpRequestError> for GqlRejection {
 
 #[async_trait::async_trait]
 impl<B> FromRequest<B> for GqlRequest
+where
+    B: http_body::Body + Unpin + Send + Sync + 'static,
+    B::Data: Into<Bytes>,
+    B::Error: Into<BoxError>,
+{
+    type Rejection = GqlRejection;
+    async fn from_request(
+        req: &mut axum::extract::RequestParts<B>,
+    ) -> Result<Self, Self::Rejection> {
+        let GqlBatchRequest(batch_request) = GqlBatchRequest::from_request(req).await?;
+        Ok(Self(batch_request.into_single()?))
+    }
+}
+
+#[async_trait::async_trait]
+impl<B> FromRequest<B> for GqlBatchRequest
 where
     B: http_body::Body + Unpin + Send + Sync + 'static,
     B::Data: Into<Bytes>,
@@ -45,8 +72,19 @@ where
                     format!("failed to parse graphql requst from query params: {}", err),
                 ))
             });
-            Ok(Self(res?))
+            Ok(Self(BatchRequest::Single(res?)))
         } else {
+            let content_type = req
+                .headers()
+                .and_then(|headers| headers.get(header::CONTENT_TYPE))
+                .and_then(|value| value.to_str().ok())
+                .map(ToString::to_string);
+            let options = req
+                .extensions()
+                .and_then(|extensions| extensions.get::<MultipartOptions>())
+                .copied()
+                .unwrap_or_default();
+
             let body_stream = BodyStream::from_request(req)
                 .await
                 .map_err(|err| {
@@ -57,7 +95,101 @@ where
                 })?
                 .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err.to_string()));
             let body_reader = tokio_util::io::StreamReader::new(body_stream).compat();
-            Ok(Self(receive_http_request(body_reader).await?))
+            match content_type {
+                Some(content_type) if content_type.starts_with("multipart/form-data") => Ok(Self(
+                    receive_multipart_request(body_reader, &content_type, options).await?,
+                )),
+                _ => Ok(Self(receive_batch_http_request(body_reader).await?)),
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::extract::{FromRequest, RequestParts};
+    use axum::http::Request;
+    use axum::response::IntoResponse;
+    use rusty_gql::{BatchRequest, MultipartOptions};
+
+    use super::{GqlBatchRequest, GqlRequest};
+
+    fn build_request(body: &'static str) -> RequestParts<Body> {
+        RequestParts::new(
+            Request::post("/graphql")
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_batch_request() {
+        let mut req = build_request(r#"[{"query": "{ a }"}, {"query": "{ b }"}]"#);
+        let GqlBatchRequest(batch_request) =
+            GqlBatchRequest::from_request(&mut req).await.ok().unwrap();
+        match batch_request {
+            BatchRequest::Batch(requests) => assert_eq!(requests.len(), 2),
+            BatchRequest::Single(_) => panic!("expected a batch"),
+        }
+
+        let mut req = build_request(r#"{"query": "{ a }"}"#);
+        let GqlBatchRequest(batch_request) =
+            GqlBatchRequest::from_request(&mut req).await.ok().unwrap();
+        assert!(matches!(batch_request, BatchRequest::Single(_)));
+    }
+
+    #[tokio::test]
+    async fn test_single_request() {
+        let mut req = build_request(r#"{"query": "{ a }"}"#);
+        let GqlRequest(request) = GqlRequest::from_request(&mut req).await.ok().unwrap();
+        assert_eq!(request.query, "{ a }");
+
+        // A batch is rejected, even if it has a single request.
+        let mut req = build_request(r#"[{"query": "{ a }"}]"#);
+        assert!(GqlRequest::from_request(&mut req).await.is_err());
+    }
+
+    fn build_multipart_request(options: Option<MultipartOptions>) -> RequestParts<Body> {
+        let body = "--boundary\r\n\
+            Content-Disposition: form-data; name=\"operations\"\r\n\r\n\
+            {\"query\": \"mutation ($file: Upload!) { upload(file: $file) }\", \"variables\": {\"file\": null}}\r\n\
+            --boundary\r\n\
+            Content-Disposition: form-data; name=\"map\"\r\n\r\n\
+            {\"0\": [\"variables.file\"]}\r\n\
+            --boundary\r\n\
+            Content-Disposition: form-data; name=\"0\"; filename=\"a.txt\"\r\n\r\n\
+            file a\r\n\
+            --boundary--\r\n";
+        let mut req = Request::post("/graphql")
+            .header("content-type", "multipart/form-data; boundary=boundary")
+            .body(Body::from(body))
+            .unwrap();
+        if let Some(options) = options {
+            req.extensions_mut().insert(options);
+        }
+        RequestParts::new(req)
+    }
+
+    #[tokio::test]
+    async fn test_multipart_request() {
+        let mut req = build_multipart_request(None);
+        let GqlRequest(request) = GqlRequest::from_request(&mut req).await.ok().unwrap();
+        assert!(matches!(
+            request.variables.0.get("file"),
+            Some(rusty_gql::GqlValue::String(upload)) if upload.starts_with("#upload:")
+        ));
+
+        let options = MultipartOptions {
+            max_file_size: Some(1),
+            ..Default::default()
+        };
+        let mut req = build_multipart_request(Some(options));
+        let rejection = GqlRequest::from_request(&mut req).await.err().unwrap();
+        assert_eq!(
+            rejection.into_response().status(),
+            axum::http::StatusCode::PAYLOAD_TOO_LARGE
+        );
+    }
+}
@@ -1,4 +1,12 @@
+use std::convert::Infallible;
+
+use axum::body::{Bytes, StreamBody};
+use axum::http::{header::CONTENT_TYPE, HeaderValue};
 use axum::response::IntoResponse;
+use futures_util::{
+    stream::{self, BoxStream},
+    StreamExt,
+};
 
 pub struct GqlResponse(pub rusty_gql::Response);
 
@@ -13,3 +21,90 @@ impl IntoResponse for GqlResponse {
         axum::Json(&self.0).into_response()
     }
 }
+
+/// Responds with a JSON object, or a JSON array for a batch.
+pub struct GqlBatchResponse(pub rusty_gql::BatchResponse);
+
+impl From<rusty_gql::BatchResponse> for GqlBatchResponse {
+    fn from(response: rusty_gql::BatchResponse) -> Self {
+        GqlBatchResponse(response)
+    }
+}
+
+impl IntoResponse for GqlBatchResponse {
+    fn into_response(self) -> axum::response::Response {
+        axum::Json(&self.0).into_response()
+    }
+}
+
+const INCREMENTAL_BOUNDARY: &str = "-";
+const INCREMENTAL_CONTENT_TYPE: &str = "multipart/mixed; boundary=\"-\"";
+
+/// Streams the initial payload and the incremental payloads of `@defer` and `@stream` as `multipart/mixed`.
+pub struct GqlIncrementalResponse(pub BoxStream<'static, rusty_gql::IncrementalResponse>);
+
+impl From<BoxStream<'static, rusty_gql::IncrementalResponse>> for GqlIncrementalResponse {
+    fn from(stream: BoxStream<'static, rusty_gql::IncrementalResponse>) -> Self {
+        GqlIncrementalResponse(stream)
+    }
+}
+
+impl IntoResponse for GqlIncrementalResponse {
+    fn into_response(self) -> axum::response::Response {
+        let parts = self.0.map(|payload| {
+            let json = serde_json::to_string(&payload).unwrap_or_default();
+            format!(
+                "\r\nContent-Type: application/json; charset=utf-8\r\n\r\n{}\r\n--{}",
+                json, INCREMENTAL_BOUNDARY
+            )
+        });
+        let body = stream::once(async { format!("\r\n--{}", INCREMENTAL_BOUNDARY) })
+            .chain(parts)
+            .chain(stream::once(async { "--\r\n".to_string() }))
+            .map(|part| Ok::<_, Infallible>(Bytes::from(part)));
+
+        let mut response = StreamBody::new(body).into_response();
+        response.headers_mut().insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static(INCREMENTAL_CONTENT_TYPE),
+        );
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::response::IntoResponse;
+    use futures_util::{stream, StreamExt};
+    use http_body::Body;
+    use rusty_gql::IncrementalResponse;
+
+    use super::GqlIncrementalResponse;
+
+    #[tokio::test]
+    async fn test_incremental_response() {
+        let payloads = vec![
+            IncrementalResponse {
+                data: Some(rusty_gql::GqlValue::Null),
+                has_next: true,
+                ..Default::default()
+            },
+            IncrementalResponse::default(),
+        ];
+        let response = GqlIncrementalResponse(stream::iter(payloads).boxed()).into_response();
+        assert_eq!(
+            response.headers()["content-type"],
+            "multipart/mixed; boundary=\"-\""
+        );
+
+        let mut body = response.into_body();
+        let mut content = Vec::new();
+        while let Some(chunk) = body.data().await {
+            content.extend_from_slice(&chunk.unwrap());
+        }
+        assert_eq!(
+            String::from_utf8(content).unwrap(),
+            "\r\n---\r\nContent-Type: application/json; charset=utf-8\r\n\r\n{\"data\":null,\"hasNext\":true}\r\n---\r\nContent-Type: application/json; charset=utf-8\r\n\r\n{\"hasNext\":false}\r\n-----\r\n"
+        );
+    }
+}
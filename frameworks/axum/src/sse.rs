@@ -0,0 +1,473 @@
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use axum::extract::{FromRequest, Query, RequestParts};
+use axum::http::StatusCode;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use futures_util::{
+    stream::{self, AbortHandle, BoxStream},
+    StreamExt,
+};
+use rusty_gql::{execute_stream, Container, GqlValue, SelectionSetResolver, SubscriptionResolver};
+use serde::Serialize;
+use tokio::sync::mpsc;
+
+pub const SSE_TOKEN_HEADER: &str = "x-graphql-event-stream-token";
+
+const DEFAULT_RESERVATION_TTL: Duration = Duration::from_secs(60);
+const DEFAULT_MAX_PENDING_RESERVATIONS: usize = 1024;
+
+/// Streams the responses of an operation over a distinct SSE connection.
+pub struct GqlSseResponse(pub BoxStream<'static, rusty_gql::Response>);
+
+impl From<BoxStream<'static, rusty_gql::Response>> for GqlSseResponse {
+    fn from(stream: BoxStream<'static, rusty_gql::Response>) -> Self {
+        GqlSseResponse(stream)
+    }
+}
+
+impl IntoResponse for GqlSseResponse {
+    fn into_response(self) -> Response {
+        let events = self
+            .0
+            .map(|response| next_event(&response))
+            .chain(stream::once(async { complete_event(None) }))
+            .map(Ok::<_, Infallible>);
+        Sse::new(events)
+            .keep_alive(KeepAlive::new())
+            .into_response()
+    }
+}
+
+#[derive(Serialize)]
+struct OperationPayload<'a> {
+    id: &'a str,
+    payload: &'a rusty_gql::Response,
+}
+
+#[derive(Serialize)]
+struct OperationId<'a> {
+    id: &'a str,
+}
+
+fn next_event<T: Serialize>(payload: &T) -> Event {
+    Event::default().event("next").json_data(payload).unwrap()
+}
+
+fn complete_event(id: Option<&str>) -> Event {
+    let event = Event::default().event("complete");
+    match id {
+        Some(id) => event.json_data(OperationId { id }).unwrap(),
+        None => event.data(""),
+    }
+}
+
+/// The reservation token of the single connection mode, read from the
+/// `X-GraphQL-Event-Stream-Token` header or the `token` query parameter.
+pub struct GqlSseToken(pub Option<String>);
+
+#[async_trait::async_trait]
+impl<B: Send> FromRequest<B> for GqlSseToken {
+    type Rejection = Infallible;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let header = req
+            .headers()
+            .and_then(|headers| headers.get(SSE_TOKEN_HEADER))
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string());
+        if header.is_some() {
+            return Ok(GqlSseToken(header));
+        }
+
+        let query = Query::<HashMap<String, String>>::from_request(req)
+            .await
+            .map(|Query(mut params)| params.remove("token"))
+            .unwrap_or_default();
+        Ok(GqlSseToken(query))
+    }
+}
+
+struct Reservation {
+    created_at: Instant,
+    sender: Option<mpsc::UnboundedSender<Event>>,
+    operations: HashMap<String, AbortHandle>,
+}
+
+impl Reservation {
+    /// A reservation is pending until its stream is opened.
+    fn is_pending(&self) -> bool {
+        self.sender.is_none()
+    }
+}
+
+type Reservations = Arc<Mutex<HashMap<String, Reservation>>>;
+
+/// Removes the reservation once its event stream is dropped.
+struct ReservationGuard {
+    token: String,
+    reservations: Reservations,
+}
+
+impl Drop for ReservationGuard {
+    fn drop(&mut self) {
+        if let Some(reservation) = self.reservations.lock().unwrap().remove(&self.token) {
+            for (_, handle) in reservation.operations {
+                handle.abort();
+            }
+        }
+    }
+}
+
+/// Serves operations in the single connection mode of the GraphQL over SSE protocol.
+///
+/// A client reserves a stream with `PUT`, opens it with `GET`, executes operations with `POST`
+/// and stops them with `DELETE`. Every request except the reservation carries the token.
+///
+/// A reservation expires unless its stream is opened within the reservation TTL, and new reservations
+/// are rejected while there are too many pending ones.
+pub struct GqlSseConnections<Query, Mutation, Subscription>
+where
+    Query: SelectionSetResolver + 'static,
+    Mutation: SelectionSetResolver + 'static,
+    Subscription: SubscriptionResolver + 'static,
+{
+    container: Container<Query, Mutation, Subscription>,
+    reservations: Reservations,
+    reservation_ttl: Duration,
+    max_pending_reservations: usize,
+}
+
+impl<Query, Mutation, Subscription> Clone for GqlSseConnections<Query, Mutation, Subscription>
+where
+    Query: SelectionSetResolver + 'static,
+    Mutation: SelectionSetResolver + 'static,
+    Subscription: SubscriptionResolver + 'static,
+{
+    fn clone(&self) -> Self {
+        GqlSseConnections {
+            container: self.container.clone(),
+            reservations: self.reservations.clone(),
+            reservation_ttl: self.reservation_ttl,
+            max_pending_reservations: self.max_pending_reservations,
+        }
+    }
+}
+
+impl<Query, Mutation, Subscription> GqlSseConnections<Query, Mutation, Subscription>
+where
+    Query: SelectionSetResolver + 'static,
+    Mutation: SelectionSetResolver + 'static,
+    Subscription: SubscriptionResolver + 'static,
+{
+    pub fn new(container: &Container<Query, Mutation, Subscription>) -> Self {
+        GqlSseConnections {
+            container: container.clone(),
+            reservations: Default::default(),
+            reservation_ttl: DEFAULT_RESERVATION_TTL,
+            max_pending_reservations: DEFAULT_MAX_PENDING_RESERVATIONS,
+        }
+    }
+
+    /// Sets how long a reservation waits for its stream to be opened. Defaults to 60 seconds.
+    pub fn reservation_ttl(mut self, ttl: Duration) -> Self {
+        self.reservation_ttl = ttl;
+        self
+    }
+
+    /// Sets the maximum number of reservations whose stream is not opened yet. Defaults to 1024.
+    pub fn max_pending_reservations(mut self, max: usize) -> Self {
+        self.max_pending_reservations = max;
+        self
+    }
+
+    pub fn reserve(&self) -> Response {
+        let mut reservations = self.reservations.lock().unwrap();
+        let ttl = self.reservation_ttl;
+        reservations.retain(|_, reservation| {
+            !reservation.is_pending() || reservation.created_at.elapsed() < ttl
+        });
+        let pending = reservations
+            .values()
+            .filter(|reservation| reservation.is_pending())
+            .count();
+        if pending >= self.max_pending_reservations {
+            return (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "Too many pending reservations",
+            )
+                .into_response();
+        }
+
+        let token = format!("{:032x}", rand::random::<u128>());
+        reservations.insert(
+            token.clone(),
+            Reservation {
+                created_at: Instant::now(),
+                sender: None,
+                operations: HashMap::new(),
+            },
+        );
+        (StatusCode::CREATED, token).into_response()
+    }
+
+    pub fn stream(&self, token: GqlSseToken) -> Response {
+        let token = match token.0 {
+            Some(token) => token,
+            None => return (StatusCode::UNAUTHORIZED, "Missing token").into_response(),
+        };
+
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let mut reservations = self.reservations.lock().unwrap();
+        match reservations.get_mut(&token) {
+            Some(reservation) if !reservation.is_pending() => {
+                return (StatusCode::CONFLICT, "Stream already open").into_response();
+            }
+            Some(reservation) if reservation.created_at.elapsed() >= self.reservation_ttl => {
+                reservations.remove(&token);
+                return (StatusCode::NOT_FOUND, "Stream not found").into_response();
+            }
+            Some(reservation) => reservation.sender = Some(sender),
+            None => return (StatusCode::NOT_FOUND, "Stream not found").into_response(),
+        }
+        drop(reservations);
+
+        let guard = ReservationGuard {
+            token,
+            reservations: self.reservations.clone(),
+        };
+        let events = stream::unfold((receiver, guard), |(mut receiver, guard)| async move {
+            receiver
+                .recv()
+                .await
+                .map(|event| (Ok::<_, Infallible>(event), (receiver, guard)))
+        });
+        Sse::new(events)
+            .keep_alive(KeepAlive::new())
+            .into_response()
+    }
+
+    pub fn execute(&self, token: GqlSseToken, request: rusty_gql::Request) -> Response {
+        let token = match token.0 {
+            Some(token) => token,
+            None => return (StatusCode::UNAUTHORIZED, "Missing token").into_response(),
+        };
+        let id = match request.extensions.get("operationId") {
+            Some(GqlValue::String(id)) => id.clone(),
+            _ => return (StatusCode::BAD_REQUEST, "Missing operationId").into_response(),
+        };
+
+        let mut reservations = self.reservations.lock().unwrap();
+        let reservation = match reservations.get_mut(&token) {
+            Some(reservation) => reservation,
+            None => return (StatusCode::NOT_FOUND, "Stream not found").into_response(),
+        };
+        let sender = match &reservation.sender {
+            Some(sender) => sender.clone(),
+            None => return (StatusCode::NOT_FOUND, "Stream not open").into_response(),
+        };
+        if reservation.operations.contains_key(&id) {
+            return (StatusCode::CONFLICT, "Operation with ID already exists").into_response();
+        }
+
+        let (mut responses, handle) = stream::abortable(execute_stream(&self.container, request));
+        reservation.operations.insert(id.clone(), handle);
+
+        let shared_reservations = self.reservations.clone();
+        tokio::spawn(async move {
+            while let Some(response) = responses.next().await {
+                let event = next_event(&OperationPayload {
+                    id: &id,
+                    payload: &response,
+                });
+                if sender.send(event).is_err() {
+                    return;
+                }
+            }
+            let completed = shared_reservations
+                .lock()
+                .unwrap()
+                .get_mut(&token)
+                .and_then(|reservation| reservation.operations.remove(&id))
+                .is_some();
+            if completed {
+                let _ = sender.send(complete_event(Some(&id)));
+            }
+        });
+
+        StatusCode::ACCEPTED.into_response()
+    }
+
+    pub fn stop(&self, token: GqlSseToken, operation_id: Option<&str>) -> Response {
+        let token = match token.0 {
+            Some(token) => token,
+            None => return (StatusCode::UNAUTHORIZED, "Missing token").into_response(),
+        };
+        let operation_id = match operation_id {
+            Some(operation_id) => operation_id,
+            None => return (StatusCode::BAD_REQUEST, "Missing operationId").into_response(),
+        };
+
+        match self.reservations.lock().unwrap().get_mut(&token) {
+            Some(reservation) => {
+                if let Some(handle) = reservation.operations.remove(operation_id) {
+                    handle.abort();
+                }
+                StatusCode::OK.into_response()
+            }
+            None => (StatusCode::NOT_FOUND, "Stream not found").into_response(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::BoxBody;
+    use axum::http::StatusCode;
+    use axum::response::{IntoResponse, Response};
+    use futures_util::{stream, Stream};
+    use http_body::Body;
+    use rusty_gql::*;
+
+    use std::time::Duration;
+
+    use super::{GqlSseConnections, GqlSseResponse, GqlSseToken};
+
+    struct Query;
+
+    #[GqlType]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct Subscription;
+
+    #[GqlType]
+    impl Subscription {
+        async fn values(&self, count: i32) -> impl Stream<Item = i32> {
+            stream::iter(0..count)
+        }
+    }
+
+    fn build_container() -> Container<Query, EmptyMutation, Subscription> {
+        let schema = "type Query { value: Int } type Subscription { values(count: Int!): Int! }";
+        Container::new(
+            &[schema],
+            Query,
+            EmptyMutation,
+            Subscription,
+            Default::default(),
+        )
+        .unwrap()
+    }
+
+    fn build_request(query: &str, operation_id: Option<&str>) -> Request {
+        let mut extensions = std::collections::HashMap::new();
+        if let Some(id) = operation_id {
+            extensions.insert("operationId".to_string(), GqlValue::String(id.to_string()));
+        }
+        Request {
+            query: query.to_string(),
+            operation_name: None,
+            variables: Default::default(),
+            extensions,
+            document_id: None,
+            data: Default::default(),
+        }
+    }
+
+    async fn read_chunk(body: &mut BoxBody) -> String {
+        let chunk = body.data().await.unwrap().unwrap();
+        String::from_utf8(chunk.to_vec()).unwrap()
+    }
+
+    async fn read_text(response: Response) -> String {
+        let mut body = response.into_body();
+        let mut text = String::new();
+        while let Some(chunk) = body.data().await {
+            text.push_str(std::str::from_utf8(&chunk.unwrap()).unwrap());
+        }
+        text
+    }
+
+    #[tokio::test]
+    async fn test_distinct_connections() {
+        let container = build_container();
+        let request = build_request("subscription { values(count: 2) }", None);
+        let response = GqlSseResponse::from(execute_stream(&container, request)).into_response();
+
+        assert_eq!(
+            read_text(response).await,
+            concat!(
+                "event: next\ndata:{\"data\":{\"values\":0}}\n\n",
+                "event: next\ndata:{\"data\":{\"values\":1}}\n\n",
+                "event: complete\ndata: \n\n",
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_single_connection() {
+        let connections = GqlSseConnections::new(&build_container());
+
+        let response = connections.reserve();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let token = read_text(response).await;
+
+        let stream = connections.stream(GqlSseToken(Some(token.clone())));
+        assert_eq!(stream.status(), StatusCode::OK);
+        let duplicated = connections.stream(GqlSseToken(Some(token.clone())));
+        assert_eq!(duplicated.status(), StatusCode::CONFLICT);
+
+        let request = build_request("subscription { values(count: 1) }", Some("1"));
+        let response = connections.execute(GqlSseToken(Some(token.clone())), request);
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+        let mut body = stream.into_body();
+        assert_eq!(
+            read_chunk(&mut body).await,
+            "event: next\ndata:{\"id\":\"1\",\"payload\":{\"data\":{\"values\":0}}}\n\n"
+        );
+        assert_eq!(
+            read_chunk(&mut body).await,
+            "event: complete\ndata:{\"id\":\"1\"}\n\n"
+        );
+
+        let response = connections.stop(GqlSseToken(Some(token.clone())), Some("1"));
+        assert_eq!(response.status(), StatusCode::OK);
+
+        drop(body);
+        let request = build_request("{ value }", Some("2"));
+        let response = connections.execute(GqlSseToken(Some(token)), request);
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = connections.stream(GqlSseToken(None));
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_reservation_limits() {
+        let connections = GqlSseConnections::new(&build_container()).max_pending_reservations(1);
+        assert_eq!(connections.reserve().status(), StatusCode::CREATED);
+        assert_eq!(
+            connections.reserve().status(),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+
+        let connections = GqlSseConnections::new(&build_container())
+            .reservation_ttl(Duration::ZERO)
+            .max_pending_reservations(1);
+        let token = read_text(connections.reserve()).await;
+        let response = connections.stream(GqlSseToken(Some(token)));
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        // Expired reservations do not count as pending.
+        assert_eq!(connections.reserve().status(), StatusCode::CREATED);
+    }
+}
@@ -0,0 +1,105 @@
+use std::{borrow::Cow, time::Duration};
+
+use axum::extract::ws::{CloseFrame, Message, WebSocket};
+use axum::extract::{FromRequest, RequestParts};
+use axum::http::{header, StatusCode};
+use futures_util::{future, Future, StreamExt};
+use rusty_gql::{
+    Container, Data, GqlError, GqlValue, SelectionSetResolver, SubscriptionResolver,
+    WebSocketConnection, WebSocketProtocol, WsMessage,
+};
+
+pub struct GqlWebSocketProtocol(pub WebSocketProtocol);
+
+#[async_trait::async_trait]
+impl<B: Send> FromRequest<B> for GqlWebSocketProtocol {
+    type Rejection = (StatusCode, &'static str);
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        req.headers()
+            .and_then(|headers| headers.get(header::SEC_WEBSOCKET_PROTOCOL))
+            .and_then(|value| value.to_str().ok())
+            .and_then(WebSocketProtocol::from_sec_websocket_protocol)
+            .map(GqlWebSocketProtocol)
+            .ok_or((
+                StatusCode::BAD_REQUEST,
+                "Sec-WebSocket-Protocol must be graphql-transport-ws or graphql-ws",
+            ))
+    }
+}
+
+pub struct GqlWebSocket<Query, Mutation, Subscription>
+where
+    Query: SelectionSetResolver + 'static,
+    Mutation: SelectionSetResolver + 'static,
+    Subscription: SubscriptionResolver + 'static,
+{
+    socket: WebSocket,
+    connection: WebSocketConnection<Query, Mutation, Subscription>,
+}
+
+impl<Query, Mutation, Subscription> GqlWebSocket<Query, Mutation, Subscription>
+where
+    Query: SelectionSetResolver + 'static,
+    Mutation: SelectionSetResolver + 'static,
+    Subscription: SubscriptionResolver + 'static,
+{
+    pub fn new(
+        socket: WebSocket,
+        container: &Container<Query, Mutation, Subscription>,
+        protocol: WebSocketProtocol,
+    ) -> Self {
+        GqlWebSocket {
+            socket,
+            connection: WebSocketConnection::new(container, protocol),
+        }
+    }
+
+    pub fn on_connection_init<F, Fut>(mut self, callback: F) -> Self
+    where
+        F: FnOnce(GqlValue) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<Data, GqlError>> + Send + 'static,
+    {
+        self.connection = self.connection.on_connection_init(callback);
+        self
+    }
+
+    pub fn connection_init_timeout(mut self, timeout: Duration) -> Self {
+        self.connection = self.connection.connection_init_timeout(timeout);
+        self
+    }
+
+    pub async fn serve(self) {
+        let (sink, stream) = self.socket.split();
+
+        let incoming = stream
+            .take_while(|message| {
+                future::ready(
+                    matches!(message, Ok(message) if !matches!(message, Message::Close(_))),
+                )
+            })
+            .filter_map(|message| {
+                future::ready(match message {
+                    Ok(Message::Text(text)) => Some(text.into_bytes()),
+                    Ok(Message::Binary(binary)) => Some(binary),
+                    _ => None,
+                })
+            })
+            .boxed();
+
+        let _ = self
+            .connection
+            .serve(incoming)
+            .map(|message| {
+                Ok(match message {
+                    WsMessage::Text(text) => Message::Text(text),
+                    WsMessage::Close(code, reason) => Message::Close(Some(CloseFrame {
+                        code,
+                        reason: Cow::from(reason),
+                    })),
+                })
+            })
+            .forward(sink)
+            .await;
+    }
+}
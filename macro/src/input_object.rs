@@ -50,7 +50,7 @@ pub fn generate_input_object(derive_input: &DeriveInput) -> Result<TokenStream,
             }
 
             fn to_gql_value(&self) -> GqlValue {
-                let mut obj = std::collections::BTreeMap::new();
+                let mut obj = #crate_name::IndexMap::new();
                 #(#set_fields)*
                 #crate_name::GqlValue::Object(obj)
             }
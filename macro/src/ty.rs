@@ -4,6 +4,7 @@ use syn::{ext::IdentExt, Block, FnArg, ImplItem, ItemImpl, NestedMeta, ReturnTyp
 
 use crate::utils::{
     get_method_args_without_context, is_context_type, is_interface, is_internal, is_result_type,
+    is_stream_type,
 };
 
 pub fn generate_type(
@@ -27,6 +28,7 @@ pub fn generate_type(
     let (impl_generics, _, where_clause) = &item_impl.generics.split_for_impl();
 
     let mut resolvers = Vec::new();
+    let mut subscription_resolvers = Vec::new();
     for item in &mut item_impl.items {
         if let ImplItem::Method(method) = item {
             if method.sig.asyncness.is_none() {
@@ -47,6 +49,7 @@ pub fn generate_type(
             };
 
             let is_result = is_result_type(return_type);
+            let is_stream = is_stream_type(return_type);
             let block = &method.block;
 
             if !is_result {
@@ -89,6 +92,27 @@ pub fn generate_type(
                 });
             }
 
+            if is_stream {
+                subscription_resolvers.push(quote! {
+                    if ctx.item.name == #field_name {
+                        let resolve_fn = async move {
+                            #(#gql_arg_values)*
+                            let res = self.#method_name(ctx, #(#args),*).await;
+                            res.map_err(|err| #crate_name::Error::from(err).into_gql_error(ctx.item.position))
+                        };
+
+                        let stream = resolve_fn.await?;
+                        let ctx_selection_set = ctx.with_selection_set(&ctx.item.selection_set);
+                        let stream = #crate_name::futures_util::StreamExt::then(stream, move |obj| {
+                            let ctx_selection_set = ctx_selection_set.clone();
+                            async move { obj.resolve_selection_set(&ctx_selection_set).await }
+                        });
+                        return Ok(::std::option::Option::Some(::std::boxed::Box::pin(stream)));
+                    }
+                });
+                continue;
+            }
+
             resolvers.push(quote! {
                 if ctx.item.name == #field_name {
                     let resolve_fn = async move {
@@ -113,6 +137,20 @@ pub fn generate_type(
         })
     };
 
+    let subscription_resolver = if subscription_resolvers.is_empty() {
+        None
+    } else {
+        Some(quote! {
+            #[#crate_name::async_trait::async_trait]
+            impl #impl_generics #crate_name::SubscriptionResolver for #self_ty #where_clause {
+                async fn resolve_subscription_field<'a>(&'a self, ctx: &'a #crate_name::Context<'_>) -> #crate_name::ResolverResult<::std::option::Option<#crate_name::SubscriptionStream<'a>>> {
+                    #(#subscription_resolvers)*
+                    Ok(::std::option::Option::None)
+                }
+            }
+        })
+    };
+
     let expanded = quote! {
         #item_impl
 
@@ -135,6 +173,8 @@ pub fn generate_type(
                 #crate_name::resolve_selection_parallelly(ctx, self).await
             }
         }
+
+        #subscription_resolver
     };
 
     Ok(expanded.into())
@@ -1,4 +1,7 @@
-use syn::{FnArg, ImplItemMethod, Meta, NestedMeta, Pat, PatIdent, Type, TypeReference};
+use syn::{
+    FnArg, GenericArgument, ImplItemMethod, Meta, NestedMeta, Pat, PatIdent, PathArguments, Type,
+    TypeParamBound, TypeReference,
+};
 
 pub fn is_internal(args: &[NestedMeta]) -> bool {
     for arg in args {
@@ -89,3 +92,28 @@ pub fn is_result_type(return_type: &Type) -> bool {
     }
     false
 }
+
+pub fn is_stream_type(return_type: &Type) -> bool {
+    match return_type {
+        Type::ImplTrait(impl_trait) => impl_trait.bounds.iter().any(|bound| {
+            if let TypeParamBound::Trait(trait_bound) = bound {
+                trait_bound.path.segments.last().unwrap().ident == "Stream"
+            } else {
+                false
+            }
+        }),
+        Type::Path(ty_path) => {
+            let segment = ty_path.path.segments.last().unwrap();
+            if segment.ident != "Result" {
+                return false;
+            }
+            if let PathArguments::AngleBracketed(args) = &segment.arguments {
+                if let Some(GenericArgument::Type(ty)) = args.args.first() {
+                    return is_stream_type(ty);
+                }
+            }
+            false
+        }
+        _ => false,
+    }
+}
@@ -1,9 +1,13 @@
 use std::{collections::HashMap, ops::Deref, sync::Arc};
 
 use crate::{
+    data::Data,
+    document_cache::DocumentCache,
     error::GqlError,
     types::schema::{build_schema, Schema},
-    CustomDirective, QueryRoot, SelectionSetResolver,
+    validation::ValidationLimits,
+    CustomDirective, ExtensionFactory, PersistedQueryStore, QueryRoot, SelectionSetResolver,
+    TrustedDocuments,
 };
 
 pub struct ContainerInner<
@@ -15,15 +19,32 @@ pub struct ContainerInner<
     pub mutation_resolvers: Mutation,
     pub subscription_resolvers: Subscription,
     pub schema: Schema,
+    pub data: Data,
+    pub extensions: Vec<Box<dyn ExtensionFactory>>,
+    pub limits: ValidationLimits,
+    pub persisted_query_store: Option<Box<dyn PersistedQueryStore>>,
+    pub trusted_documents: Option<TrustedDocuments>,
+    pub document_cache: Option<DocumentCache>,
+    pub max_batch_size: Option<usize>,
 }
 
-#[derive(Clone)]
 pub struct Container<
     Query: SelectionSetResolver,
     Mutation: SelectionSetResolver,
     Subscription: SelectionSetResolver,
 >(Arc<ContainerInner<Query, Mutation, Subscription>>);
 
+impl<Query, Mutation, Subscription> Clone for Container<Query, Mutation, Subscription>
+where
+    Query: SelectionSetResolver,
+    Mutation: SelectionSetResolver,
+    Subscription: SelectionSetResolver,
+{
+    fn clone(&self) -> Self {
+        Container(self.0.clone())
+    }
+}
+
 impl<Query, Mutation, Subscription> Deref for Container<Query, Mutation, Subscription>
 where
     Query: SelectionSetResolver + 'static,
@@ -49,6 +70,25 @@ where
         mutation: Mutation,
         subscription: Subscription,
         custom_directives: HashMap<&'static str, Box<dyn CustomDirective>>,
+    ) -> Result<Self, GqlError> {
+        Self::new_with_data(
+            schema_doc,
+            query,
+            mutation,
+            subscription,
+            custom_directives,
+            Data::default(),
+        )
+    }
+
+    /// Creates a container whose `data` is shared by every request.
+    pub fn new_with_data(
+        schema_doc: &[&str],
+        query: Query,
+        mutation: Mutation,
+        subscription: Subscription,
+        custom_directives: HashMap<&'static str, Box<dyn CustomDirective>>,
+        data: Data,
     ) -> Result<Self, GqlError> {
         let schema = build_schema(schema_doc, custom_directives)?;
         Ok(Container(Arc::new(ContainerInner {
@@ -56,6 +96,139 @@ where
             mutation_resolvers: mutation,
             subscription_resolvers: subscription,
             schema,
+            data,
+            extensions: Vec::new(),
+            limits: ValidationLimits::default(),
+            persisted_query_store: None,
+            trusted_documents: None,
+            document_cache: None,
+            max_batch_size: None,
         })))
     }
+
+    /// Registers an extension. Extensions are called in the order in which they are registered.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the container has been cloned.
+    pub fn extension(mut self, factory: impl ExtensionFactory + 'static) -> Self {
+        self.inner_mut().extensions.push(Box::new(factory));
+        self
+    }
+
+    /// Rejects operations whose cost is greater than `max_complexity`, and reports the cost in `extensions.complexity`.
+    ///
+    /// A field costs 1 unless it has a `@cost(weight: Int, multipliers: [String])` directive in the schema.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the container has been cloned.
+    pub fn max_complexity(mut self, max_complexity: usize) -> Self {
+        self.inner_mut().limits.max_complexity = Some(max_complexity);
+        self
+    }
+
+    /// Rejects operations whose fields are nested more deeply than `max_depth`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the container has been cloned.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.inner_mut().limits.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Rejects operations with more than `max_aliases` aliases.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the container has been cloned.
+    pub fn max_aliases(mut self, max_aliases: usize) -> Self {
+        self.inner_mut().limits.max_aliases = Some(max_aliases);
+        self
+    }
+
+    /// Rejects operations with more than `max_fields` fields. A fragment counts every time it is spread.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the container has been cloned.
+    pub fn max_fields(mut self, max_fields: usize) -> Self {
+        self.inner_mut().limits.max_fields = Some(max_fields);
+        self
+    }
+
+    /// Rejects operations with more than `max_root_fields` fields at the root.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the container has been cloned.
+    pub fn max_root_fields(mut self, max_root_fields: usize) -> Self {
+        self.inner_mut().limits.max_root_fields = Some(max_root_fields);
+        self
+    }
+
+    /// Rejects operations with more than `max_directives` directives.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the container has been cloned.
+    pub fn max_directives(mut self, max_directives: usize) -> Self {
+        self.inner_mut().limits.max_directives = Some(max_directives);
+        self
+    }
+
+    /// Rejects batches of more than `max_batch_size` requests.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the container has been cloned.
+    pub fn max_batch_size(mut self, max_batch_size: usize) -> Self {
+        self.inner_mut().max_batch_size = Some(max_batch_size);
+        self
+    }
+
+    /// Enables Automatic Persisted Queries. Queries sent with `extensions.persistedQuery` are stored in `store`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the container has been cloned.
+    pub fn persisted_query_store(mut self, store: impl PersistedQueryStore) -> Self {
+        self.inner_mut().persisted_query_store = Some(Box::new(store));
+        self
+    }
+
+    /// Executes only the trusted documents. The documents are validated against the schema.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a document is invalid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the container has been cloned.
+    pub fn trusted_documents(
+        mut self,
+        trusted_documents: TrustedDocuments,
+    ) -> Result<Self, GqlError> {
+        trusted_documents.validate(&self.schema)?;
+        self.inner_mut().trusted_documents = Some(trusted_documents);
+        Ok(self)
+    }
+
+    /// Caches the parsed documents of the `capacity` most recently used queries, and the outcome of their validation.
+    /// Requests with a cached query skip the parsing and validation, except for the complexity, which depends on
+    /// the variables. The `parse_query` and `validation` hooks of extensions are not called for them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the container has been cloned.
+    pub fn document_cache(mut self, capacity: usize) -> Self {
+        self.inner_mut().document_cache = Some(DocumentCache::new(capacity));
+        self
+    }
+
+    fn inner_mut(&mut self) -> &mut ContainerInner<Query, Mutation, Subscription> {
+        Arc::get_mut(&mut self.0).expect("the container must be configured before it is cloned")
+    }
 }
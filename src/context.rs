@@ -1,20 +1,40 @@
+use std::any::Any;
+
 use crate::{
-    error::GqlError, input::GqlInputType, operation::Operation, types::schema::Schema, GqlValue,
-    ResolverResult,
+    error::{GqlError, PathSegment},
+    extension::Extensions,
+    incremental::{DeferDirective, IncrementalScope, StreamDirective},
+    input::{coerce_input_value, GqlInputType},
+    operation::Operation,
+    telemetry,
+    types::schema::Schema,
+    FieldType, GqlValue, GqlValueType, ResolverResult,
 };
 use graphql_parser::{
     query::{Field, SelectionSet},
     schema::{Directive, Value},
+    Pos,
 };
 
 #[derive(Clone)]
 pub struct ExecutionContext<'a, T> {
     pub schema: &'a Schema,
-    pub operation: &'a Operation<'a>,
+    pub operation: &'a Operation,
     pub item: T,
+    /// The response path of the item being resolved.
+    pub path: Vec<PathSegment>,
+    /// The schema definition of the field being resolved, if it is known.
+    pub field_definition: Option<&'a FieldType>,
+    /// The position of the field being resolved in the query.
+    pub(crate) field_position: Option<Pos>,
+    pub(crate) extensions: &'a Extensions,
+    /// Where the value is delivered when the operation is executed by `execute_incremental`.
+    pub(crate) incremental: Option<IncrementalScope>,
+    /// The `@stream` of the list being resolved.
+    pub(crate) stream: Option<StreamDirective>,
 }
 
-pub type Context<'a> = ExecutionContext<'a, &'a Field<'a, String>>;
+pub type Context<'a> = ExecutionContext<'a, &'a Field<'static, String>>;
 
 impl<'a> Context<'a> {
     pub fn get_arg_value<T: GqlInputType>(&self, arg_name: &str) -> ResolverResult<T> {
@@ -24,84 +44,232 @@ impl<'a> Context<'a> {
             .iter()
             .find(|(name, _)| name == arg_name)
             .map(|(_, v)| v);
+        let arg_def = self
+            .field_definition
+            .and_then(|field_def| field_def.arguments.iter().find(|arg| arg.name == arg_name));
         let gql_value = match value {
-            Some(v) => {
-                if let Value::Variable(var_name) = v {
-                    self.resolve_variable_value(var_name)?
-                } else {
-                    GqlValue::from(v.clone())
-                }
+            // A variable without a value is treated as if the argument was omitted.
+            Some(Value::Variable(var_name))
+                if !self.operation.variables.0.contains_key(var_name) =>
+            {
+                None
             }
-            None => GqlValue::Null,
+            Some(Value::Variable(var_name)) => Some(self.resolve_variable_value(var_name)?),
+            Some(v) => Some(GqlValue::from(v.clone())),
+            None => None,
         };
-        match T::from_gql_value(Some(gql_value)) {
-            Ok(v) => Ok(v),
-            Err(err) => Err(GqlError::new(err, None)),
-        }
+        let invalid_value = |reason: String| {
+            GqlError::new(
+                format!("Argument \"{}\" got invalid value; {}", arg_name, reason),
+                Some(self.item.position),
+            )
+        };
+        let gql_value = match arg_def {
+            Some(arg_def) => match gql_value.or_else(|| arg_def.default_value.clone()) {
+                Some(value) => coerce_input_value(self.schema, &arg_def.meta_type, value)
+                    .map_err(invalid_value)?,
+                None => GqlValue::Null,
+            },
+            None => gql_value.unwrap_or(GqlValue::Null),
+        };
+        T::from_gql_value(Some(gql_value)).map_err(invalid_value)
     }
 }
 
-pub type SelectionSetContext<'a> = ExecutionContext<'a, &'a SelectionSet<'a, String>>;
+pub type SelectionSetContext<'a> = ExecutionContext<'a, &'a SelectionSet<'static, String>>;
 
 impl<'a, T> ExecutionContext<'a, T> {
     pub fn with_field(
         &self,
-        field: &'a Field<'a, String>,
-    ) -> ExecutionContext<'a, &'a Field<'a, String>> {
+        field: &'a Field<'static, String>,
+    ) -> ExecutionContext<'a, &'a Field<'static, String>> {
+        let mut path = self.path.clone();
+        path.push(PathSegment::Field(
+            field.alias.clone().unwrap_or_else(|| field.name.clone()),
+        ));
         ExecutionContext {
             schema: self.schema,
             operation: self.operation,
             item: field,
+            path,
+            field_definition: None,
+            field_position: Some(field.position),
+            extensions: self.extensions,
+            incremental: self.incremental.clone(),
+            stream: None,
         }
     }
 
     pub fn with_selection_set(
         &self,
-        selection_set: &'a SelectionSet<'a, String>,
-    ) -> ExecutionContext<'a, &'a SelectionSet<'a, String>> {
+        selection_set: &'a SelectionSet<'static, String>,
+    ) -> ExecutionContext<'a, &'a SelectionSet<'static, String>> {
         ExecutionContext {
             schema: self.schema,
             operation: self.operation,
             item: selection_set,
+            path: self.path.clone(),
+            field_definition: self.field_definition,
+            field_position: self.field_position,
+            extensions: self.extensions,
+            incremental: self.incremental.clone(),
+            stream: self.stream.clone(),
         }
     }
 
-    pub fn is_skip(&self, directives: &'a [Directive<'a, String>]) -> bool {
-        for dir in directives {
-            let skip = match dir.name.as_str() {
-                "skip" => true,
-                "include" => false,
-                _ => continue,
-            };
-
-            for (key, value) in &dir.arguments {
-                if key != "if" {
-                    continue;
-                } else if let Value::Boolean(cond) = value {
-                    if skip && *cond {
-                        return true;
-                    }
-                }
+    /// Returns the schema type of the value being resolved, if it is known.
+    /// Inside a list this is the type of the list item rather than the type of the field.
+    pub fn field_type(&self) -> Option<&'a GqlValueType> {
+        let list_depth = self
+            .path
+            .iter()
+            .rev()
+            .take_while(|segment| matches!(segment, PathSegment::Index(_)))
+            .count();
+        let field_type = self.field_definition.map(|field_def| &field_def.meta_type);
+        (0..list_depth).fold(field_type, |ty, _| ty.and_then(|ty| ty.list_item_type()))
+    }
+
+    /// Returns true when the selection is excluded by `@skip` or `@include`.
+    /// The selection is skipped if `@skip(if:)` is true or `@include(if:)` is false.
+    pub fn is_skip(&self, directives: &'a [Directive<'static, String>]) -> bool {
+        directives.iter().any(|dir| {
+            let cond = dir
+                .arguments
+                .iter()
+                .any(|(key, value)| key == "if" && self.directive_condition(value));
+            match dir.name.as_str() {
+                "skip" => cond,
+                "include" => !cond,
+                _ => false,
             }
+        })
+    }
 
-            return skip;
+    fn directive_condition(&self, value: &Value<'static, String>) -> bool {
+        match value {
+            Value::Boolean(cond) => *cond,
+            Value::Variable(var_name) => matches!(
+                self.resolve_variable_value(var_name),
+                Ok(GqlValue::Boolean(true))
+            ),
+            _ => false,
         }
+    }
+
+    /// Returns the `@defer` of a fragment unless its `if` is false.
+    pub(crate) fn defer_directive(
+        &self,
+        directives: &'a [Directive<'static, String>],
+    ) -> Option<DeferDirective> {
+        let directive = self.incremental_directive("defer", directives)?;
+        Some(DeferDirective {
+            label: self.directive_label(directive),
+        })
+    }
+
+    /// Returns the `@stream` of a field unless its `if` is false.
+    pub(crate) fn stream_directive(
+        &self,
+        directives: &'a [Directive<'static, String>],
+    ) -> Option<StreamDirective> {
+        let directive = self.incremental_directive("stream", directives)?;
+        let initial_count = directive
+            .arguments
+            .iter()
+            .find(|(key, _)| key == "initialCount")
+            .and_then(|(_, value)| match value {
+                Value::Int(n) => n.as_i64(),
+                Value::Variable(var_name) => match self.resolve_variable_value(var_name) {
+                    Ok(GqlValue::Number(n)) => n.as_i64(),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .unwrap_or_default();
+        Some(StreamDirective {
+            label: self.directive_label(directive),
+            initial_count: usize::try_from(initial_count).unwrap_or_default(),
+        })
+    }
+
+    fn incremental_directive(
+        &self,
+        name: &str,
+        directives: &'a [Directive<'static, String>],
+    ) -> Option<&'a Directive<'static, String>> {
+        directives.iter().find(|dir| {
+            dir.name == name
+                && dir
+                    .arguments
+                    .iter()
+                    .find(|(key, _)| key == "if")
+                    .map(|(_, value)| self.directive_condition(value))
+                    .unwrap_or(true)
+        })
+    }
+
+    fn directive_label(&self, directive: &Directive<'static, String>) -> Option<String> {
+        directive
+            .arguments
+            .iter()
+            .find(|(key, _)| key == "label")
+            .and_then(|(_, value)| match value {
+                Value::String(label) => Some(label.clone()),
+                Value::Variable(var_name) => match self.resolve_variable_value(var_name) {
+                    Ok(GqlValue::String(label)) => Some(label),
+                    _ => None,
+                },
+                _ => None,
+            })
+    }
 
-        false
+    pub fn data<D: Any + Send + Sync>(&self) -> ResolverResult<&'a D> {
+        self.data_opt::<D>().ok_or_else(|| {
+            GqlError::new(
+                format!("Data of type {} does not exist", std::any::type_name::<D>()),
+                None,
+            )
+        })
+    }
+
+    pub fn data_opt<D: Any + Send + Sync>(&self) -> Option<&'a D> {
+        self.operation.data.get::<D>()
     }
+
+    /// Adds an error to the response. The error gets the current path unless it already has one.
     pub fn add_error(&self, error: &GqlError) {
-        self.operation.errors.lock().unwrap().push(error.clone());
+        let mut error = error.clone();
+        if error.path.is_empty() {
+            error.path = self.path.clone();
+        }
+        telemetry::record_error(&error);
+        self.push_error(error);
+    }
+
+    /// Adds an error to the payload of the value being resolved.
+    pub(crate) fn push_error(&self, error: GqlError) {
+        match &self.incremental {
+            Some(scope) => scope.payload.errors.lock().unwrap().push(error),
+            None => self.operation.errors.lock().unwrap().push(error),
+        }
     }
 
     pub fn resolve_variable_value(&self, name: &str) -> ResolverResult<GqlValue> {
-        let v = self
+        let var_def = self
             .operation
             .variable_definitions
             .iter()
-            .find(|var_def| var_def.name == name)
-            .and_then(|var_def| self.operation.variables.0.get(&var_def.name));
-        match v {
-            Some(value) => Ok(value.clone()),
+            .find(|var_def| var_def.name == name);
+        match var_def {
+            // Variables are coerced before execution, so a missing value is a nullable variable without a value.
+            Some(var_def) => Ok(self
+                .operation
+                .variables
+                .0
+                .get(&var_def.name)
+                .cloned()
+                .unwrap_or(GqlValue::Null)),
             None => Err(GqlError::new(
                 format!("Variable {} is not defined", name),
                 None,
@@ -112,11 +280,18 @@ impl<'a, T> ExecutionContext<'a, T> {
 
 pub(crate) fn build_context<'a>(
     schema: &'a Schema,
-    operation: &'a Operation<'a>,
-) -> ExecutionContext<'a, &'a SelectionSet<'a, String>> {
+    operation: &'a Operation,
+    extensions: &'a Extensions,
+) -> ExecutionContext<'a, &'a SelectionSet<'static, String>> {
     ExecutionContext {
         schema,
         operation,
         item: &operation.selection_set,
+        path: Vec::new(),
+        field_definition: None,
+        field_position: None,
+        extensions,
+        incremental: operation.incremental.root_scope(),
+        stream: None,
     }
 }
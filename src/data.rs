@@ -0,0 +1,61 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    fmt::{self, Debug, Formatter},
+    sync::Arc,
+};
+
+#[derive(Default, Clone)]
+pub struct Data(HashMap<TypeId, Arc<dyn Any + Send + Sync>>);
+
+impl Data {
+    pub fn insert<T: Any + Send + Sync>(&mut self, data: T) {
+        self.0.insert(TypeId::of::<T>(), Arc::new(data));
+    }
+
+    pub fn get<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.0
+            .get(&TypeId::of::<T>())
+            .and_then(|data| data.downcast_ref::<T>())
+    }
+
+    pub fn merge(&mut self, other: Data) {
+        self.0.extend(other.0);
+    }
+}
+
+impl Debug for Data {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Data").finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Data;
+
+    #[test]
+    fn test_insert_and_get() {
+        let mut data = Data::default();
+        data.insert(10_i32);
+        data.insert("token".to_string());
+
+        assert_eq!(data.get::<i32>(), Some(&10));
+        assert_eq!(data.get::<String>(), Some(&"token".to_string()));
+        assert!(data.get::<i64>().is_none());
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut data = Data::default();
+        data.insert(10_i32);
+
+        let mut other = Data::default();
+        other.insert(20_i32);
+        other.insert(true);
+        data.merge(other);
+
+        assert_eq!(data.get::<i32>(), Some(&20));
+        assert_eq!(data.get::<bool>(), Some(&true));
+    }
+}
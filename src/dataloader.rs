@@ -0,0 +1,354 @@
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use futures_util::future::join_all;
+use tokio::sync::oneshot;
+
+/// Loads the values of a batch of keys, typically with a single query to a database.
+/// Keys without a value are omitted from the result. If `load` panics, the keys of its batch have no value.
+#[async_trait::async_trait]
+pub trait Loader<K, V>: Send + Sync + 'static {
+    async fn load(&self, keys: &[K]) -> HashMap<K, V>;
+}
+
+type Waiters<V> = Vec<oneshot::Sender<Option<V>>>;
+
+struct DataLoaderState<K, V> {
+    cache: HashMap<K, V>,
+    /// Keys waiting for the next batch.
+    pending: HashMap<K, Waiters<V>>,
+    /// Keys whose batch is being loaded.
+    loading: HashMap<K, Waiters<V>>,
+}
+
+struct DataLoaderInner<K, V> {
+    loader: Box<dyn Loader<K, V>>,
+    state: Mutex<DataLoaderState<K, V>>,
+    max_batch_size: usize,
+    delay: Duration,
+    cache_enabled: bool,
+}
+
+/// Batches and caches the keys requested by resolvers.
+///
+/// Keys requested during the same execution tick are deduplicated and passed to the `Loader` together.
+/// The cache lives as long as the `DataLoader`, so attach a new one to each `Request` to cache per request.
+pub struct DataLoader<K, V>(Arc<DataLoaderInner<K, V>>);
+
+impl<K, V> DataLoader<K, V>
+where
+    K: Clone + Eq + Hash + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    pub fn new(loader: impl Loader<K, V>) -> Self {
+        DataLoader(Arc::new(DataLoaderInner {
+            loader: Box::new(loader),
+            state: Mutex::new(DataLoaderState {
+                cache: HashMap::new(),
+                pending: HashMap::new(),
+                loading: HashMap::new(),
+            }),
+            max_batch_size: 100,
+            delay: Duration::from_millis(1),
+            cache_enabled: true,
+        }))
+    }
+
+    /// Sets the maximum number of keys passed to `Loader::load` at once. Defaults to 100.
+    ///
+    /// # Panics
+    ///
+    /// Panics while a batch is being loaded.
+    pub fn max_batch_size(mut self, max_batch_size: usize) -> Self {
+        self.inner_mut().max_batch_size = max_batch_size.max(1);
+        self
+    }
+
+    /// Sets how long keys are collected before a batch is loaded. Defaults to 1ms.
+    ///
+    /// # Panics
+    ///
+    /// Panics while a batch is being loaded.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.inner_mut().delay = delay;
+        self
+    }
+
+    /// Loads every key from the `Loader`, even if it has been loaded before.
+    ///
+    /// # Panics
+    ///
+    /// Panics while a batch is being loaded.
+    pub fn disable_cache(mut self) -> Self {
+        self.inner_mut().cache_enabled = false;
+        self
+    }
+
+    fn inner_mut(&mut self) -> &mut DataLoaderInner<K, V> {
+        Arc::get_mut(&mut self.0).expect("the data loader must be configured before it loads keys")
+    }
+
+    pub async fn load_one(&self, key: K) -> Option<V> {
+        self.load_many(Some(key)).await.into_values().next()
+    }
+
+    pub async fn load_many(&self, keys: impl IntoIterator<Item = K>) -> HashMap<K, V> {
+        let mut values = HashMap::new();
+        let mut receivers = Vec::new();
+
+        {
+            let mut state = self.0.state.lock().unwrap();
+            let starts_batch = state.pending.is_empty();
+
+            for key in keys {
+                if self.0.cache_enabled {
+                    if let Some(value) = state.cache.get(&key) {
+                        values.insert(key, value.clone());
+                        continue;
+                    }
+                }
+                let (sender, receiver) = oneshot::channel();
+                // A key whose batch is already being loaded waits for that batch instead of loading again.
+                match state.loading.get_mut(&key) {
+                    Some(waiters) => waiters.push(sender),
+                    None => state.pending.entry(key.clone()).or_default().push(sender),
+                }
+                receivers.push((key, receiver));
+            }
+
+            if starts_batch && !state.pending.is_empty() {
+                tokio::spawn(dispatch(self.0.clone()));
+            }
+        }
+
+        for (key, receiver) in receivers {
+            if let Ok(Some(value)) = receiver.await {
+                values.insert(key, value);
+            }
+        }
+        values
+    }
+
+    /// Removes every loaded value from the cache.
+    pub fn clear(&self) {
+        self.0.state.lock().unwrap().cache.clear();
+    }
+}
+
+async fn dispatch<K, V>(inner: Arc<DataLoaderInner<K, V>>)
+where
+    K: Clone + Eq + Hash + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    tokio::time::sleep(inner.delay).await;
+
+    let pending = {
+        let mut state = inner.state.lock().unwrap();
+        let pending = state.pending.drain().collect::<Vec<_>>();
+        state
+            .loading
+            .extend(pending.iter().map(|(key, _)| (key.clone(), Vec::new())));
+        pending
+    };
+    let _loading = LoadingGuard {
+        inner: &inner,
+        keys: pending.iter().map(|(key, _)| key.clone()).collect(),
+    };
+
+    let mut batches = Vec::new();
+    let mut pending = pending.into_iter().peekable();
+    while pending.peek().is_some() {
+        batches.push(
+            pending
+                .by_ref()
+                .take(inner.max_batch_size)
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    join_all(batches.into_iter().map(|batch| {
+        let inner = inner.clone();
+        async move {
+            let keys = batch.iter().map(|(key, _)| key.clone()).collect::<Vec<_>>();
+            let mut values = inner.loader.load(&keys).await;
+
+            let mut state = inner.state.lock().unwrap();
+            for (key, mut senders) in batch {
+                senders.extend(state.loading.remove(&key).unwrap_or_default());
+                let value = values.remove(&key);
+                if inner.cache_enabled {
+                    if let Some(value) = &value {
+                        state.cache.insert(key, value.clone());
+                    }
+                }
+                for sender in senders {
+                    let _ = sender.send(value.clone());
+                }
+            }
+        }
+    }))
+    .await;
+}
+
+/// Removes the keys of a dispatch from `loading` when it ends, even if `Loader::load` panics.
+/// The waiters left are dropped, so they get no value rather than waiting forever.
+struct LoadingGuard<'a, K: Eq + Hash, V> {
+    inner: &'a DataLoaderInner<K, V>,
+    keys: Vec<K>,
+}
+
+impl<K: Eq + Hash, V> Drop for LoadingGuard<'_, K, V> {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.inner.state.lock() {
+            for key in &self.keys {
+                state.loading.remove(key);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    };
+
+    use futures_util::future::join_all;
+    use tokio::time::Duration;
+
+    use super::{DataLoader, Loader};
+
+    struct TestLoader(Arc<Mutex<Vec<Vec<i32>>>>);
+
+    #[async_trait::async_trait]
+    impl Loader<i32, String> for TestLoader {
+        async fn load(&self, keys: &[i32]) -> HashMap<i32, String> {
+            let mut batch = keys.to_vec();
+            batch.sort_unstable();
+            self.0.lock().unwrap().push(batch);
+            keys.iter()
+                .filter(|key| **key >= 0)
+                .map(|key| (*key, key.to_string()))
+                .collect()
+        }
+    }
+
+    struct SlowLoader(Arc<Mutex<Vec<Vec<i32>>>>);
+
+    #[async_trait::async_trait]
+    impl Loader<i32, String> for SlowLoader {
+        async fn load(&self, keys: &[i32]) -> HashMap<i32, String> {
+            self.0.lock().unwrap().push(keys.to_vec());
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            keys.iter().map(|key| (*key, key.to_string())).collect()
+        }
+    }
+
+    struct PanicLoader;
+
+    #[async_trait::async_trait]
+    impl Loader<i32, String> for PanicLoader {
+        async fn load(&self, _keys: &[i32]) -> HashMap<i32, String> {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            panic!("failed to load");
+        }
+    }
+
+    fn build_loader() -> (DataLoader<i32, String>, Arc<Mutex<Vec<Vec<i32>>>>) {
+        let batches = Arc::new(Mutex::new(Vec::new()));
+        (DataLoader::new(TestLoader(batches.clone())), batches)
+    }
+
+    #[tokio::test]
+    async fn test_batch_and_deduplicate() {
+        let (loader, batches) = build_loader();
+
+        let values = join_all([1, 2, 1, -1, 3].map(|key| loader.load_one(key))).await;
+        assert_eq!(
+            values,
+            vec![
+                Some("1".to_string()),
+                Some("2".to_string()),
+                Some("1".to_string()),
+                None,
+                Some("3".to_string())
+            ]
+        );
+        assert_eq!(*batches.lock().unwrap(), vec![vec![-1, 1, 2, 3]]);
+    }
+
+    #[tokio::test]
+    async fn test_cache() {
+        let (loader, batches) = build_loader();
+
+        loader.load_many(vec![1, 2]).await;
+        let values = loader.load_many(vec![2, 3]).await;
+        assert_eq!(values.len(), 2);
+        assert_eq!(*batches.lock().unwrap(), vec![vec![1, 2], vec![3]]);
+
+        loader.clear();
+        loader.load_one(1).await;
+        assert_eq!(batches.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_disable_cache() {
+        let batches = Arc::new(Mutex::new(Vec::new()));
+        let loader = DataLoader::new(TestLoader(batches.clone())).disable_cache();
+
+        loader.load_one(1).await;
+        loader.load_one(1).await;
+        assert_eq!(*batches.lock().unwrap(), vec![vec![1], vec![1]]);
+    }
+
+    #[tokio::test]
+    async fn test_max_batch_size() {
+        let batches = Arc::new(Mutex::new(Vec::new()));
+        let loader = DataLoader::new(TestLoader(batches.clone())).max_batch_size(2);
+
+        let values = loader.load_many(vec![1, 2, 3, 4, 5]).await;
+        assert_eq!(values.len(), 5);
+        let batches = batches.lock().unwrap();
+        assert_eq!(batches.len(), 3);
+        assert!(batches.iter().all(|batch| batch.len() <= 2));
+    }
+
+    #[tokio::test]
+    async fn test_deduplicate_in_flight_keys() {
+        let batches = Arc::new(Mutex::new(Vec::new()));
+        let loader = Arc::new(DataLoader::new(SlowLoader(batches.clone())).disable_cache());
+
+        let first = tokio::spawn({
+            let loader = loader.clone();
+            async move { loader.load_one(1).await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let second = loader.load_one(1).await;
+
+        assert_eq!(first.await.unwrap(), Some("1".to_string()));
+        assert_eq!(second, Some("1".to_string()));
+        assert_eq!(*batches.lock().unwrap(), vec![vec![1]]);
+    }
+
+    #[tokio::test]
+    async fn test_panicking_loader() {
+        let loader = Arc::new(DataLoader::new(PanicLoader));
+
+        let first = tokio::spawn({
+            let loader = loader.clone();
+            async move { loader.load_one(1).await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let second = tokio::time::timeout(Duration::from_secs(1), loader.load_one(1)).await;
+
+        assert_eq!(first.await.unwrap(), None);
+        assert_eq!(second, Ok(None));
+        let third = tokio::time::timeout(Duration::from_secs(1), loader.load_one(1)).await;
+        assert_eq!(third, Ok(None));
+    }
+}
@@ -0,0 +1,115 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use graphql_parser::query::Document;
+use lru::LruCache;
+
+use crate::GqlError;
+
+/// Keeps the parsed documents of the most recently used queries, and the outcome of the validation rules for each
+/// of their operations.
+///
+/// A container has its own cache, so the outcome is always the outcome for the schema of the container.
+pub struct DocumentCache {
+    documents: Mutex<LruCache<String, CacheEntry>>,
+}
+
+struct CacheEntry {
+    document: Arc<Document<'static, String>>,
+    validations: HashMap<Option<String>, Result<(), Vec<GqlError>>>,
+}
+
+/// A parsed query, and the outcome of the validation rules for the requested operation if it has been validated.
+pub(crate) struct CachedDocument {
+    pub(crate) document: Arc<Document<'static, String>>,
+    pub(crate) validation: Option<Result<(), Vec<GqlError>>>,
+}
+
+impl DocumentCache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        DocumentCache {
+            documents: Mutex::new(LruCache::new(capacity.max(1))),
+        }
+    }
+
+    pub(crate) fn get(&self, query: &str, operation_name: Option<&str>) -> Option<CachedDocument> {
+        let mut documents = self.documents.lock().unwrap();
+        let entry = documents.get(query)?;
+        Some(CachedDocument {
+            document: entry.document.clone(),
+            validation: entry
+                .validations
+                .get(&operation_name.map(ToString::to_string))
+                .cloned(),
+        })
+    }
+
+    pub(crate) fn insert(
+        &self,
+        query: &str,
+        operation_name: Option<&str>,
+        document: Arc<Document<'static, String>>,
+        validation: Result<(), Vec<GqlError>>,
+    ) {
+        let mut documents = self.documents.lock().unwrap();
+        if !documents.contains(query) {
+            documents.put(
+                query.to_string(),
+                CacheEntry {
+                    document,
+                    validations: HashMap::new(),
+                },
+            );
+        }
+        if let Some(entry) = documents.get_mut(query) {
+            entry
+                .validations
+                .insert(operation_name.map(ToString::to_string), validation);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::DocumentCache;
+
+    fn insert(cache: &DocumentCache, query: &str) {
+        let document = graphql_parser::parse_query::<String>(query).unwrap();
+        cache.insert(query, None, Arc::new(document.into_static()), Ok(()));
+    }
+
+    #[test]
+    fn test_lru() {
+        let cache = DocumentCache::new(2);
+        insert(&cache, "{ a }");
+        insert(&cache, "{ b }");
+        assert!(cache.get("{ a }", None).is_some());
+
+        // "{ b }" is the least recently used document.
+        insert(&cache, "{ c }");
+        assert!(cache.get("{ b }", None).is_none());
+        assert!(cache.get("{ a }", None).is_some());
+        assert!(cache.get("{ c }", None).is_some());
+    }
+
+    #[test]
+    fn test_validation_per_operation() {
+        let cache = DocumentCache::new(2);
+        let query = "query A { a } query B { b }";
+        let document = Arc::new(
+            graphql_parser::parse_query::<String>(query)
+                .unwrap()
+                .into_static(),
+        );
+        cache.insert(query, Some("A"), document, Ok(()));
+
+        let cached = cache.get(query, Some("A")).unwrap();
+        assert!(matches!(cached.validation, Some(Ok(()))));
+        let cached = cache.get(query, Some("B")).unwrap();
+        assert!(cached.validation.is_none());
+    }
+}
@@ -12,6 +12,23 @@ pub struct Location {
     pub column: usize,
 }
 
+/// A segment of the response path, either a response key of a field or an index of a list.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum PathSegment {
+    Field(String),
+    Index(usize),
+}
+
+impl Display for PathSegment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PathSegment::Field(name) => write!(f, "{}", name),
+            PathSegment::Index(index) => write!(f, "{}", index),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub enum GqlErrorType {
     BadRequest,
@@ -38,7 +55,7 @@ pub struct GqlTypedError {
 pub struct GqlError {
     pub message: String,
     pub locations: Vec<Location>,
-    pub path: Vec<String>,
+    pub path: Vec<PathSegment>,
     pub extensions: Option<GqlTypedError>,
 }
 
@@ -60,7 +77,7 @@ impl GqlError {
     }
 
     pub fn set_path(&mut self, path: &str) -> Self {
-        self.path.push(path.to_string());
+        self.path.push(PathSegment::Field(path.to_string()));
         self.clone()
     }
 
@@ -1,7 +1,33 @@
+use std::{pin::Pin, sync::Arc, task::Poll};
+
+use futures_util::{
+    future::{self, join_all, poll_fn},
+    stream::BoxStream,
+    Future, FutureExt, StreamExt,
+};
+use graphql_parser::query::{Field, Selection, SelectionSet};
+use tokio::sync::mpsc;
+
 use crate::{
-    container::Container, context::build_context, error::GqlError, operation::build_operation,
-    request::Request, resolve_selection_parallelly, resolve_selection_serially, response::Response,
-    validation::apply_validation, OperationType, SelectionSetResolver,
+    container::Container,
+    context::build_context,
+    data::Data,
+    document_cache::CachedDocument,
+    error::GqlError,
+    extension::{ExtensionContext, Extensions},
+    incremental::Incremental,
+    operation::build_operation,
+    operation::Operation,
+    persisted_query::resolve_persisted_query,
+    request::{BatchRequest, Request},
+    resolve_selection_parallelly, resolve_selection_serially,
+    response::{BatchResponse, IncrementalResponse, Response},
+    telemetry,
+    trusted_documents::resolve_trusted_document,
+    validation::{apply_validation_rules, check_complexity, ValidationResult},
+    variables::coerce_variables,
+    GqlValue, OperationType, ResolverResult, SelectionSetContext, SelectionSetResolver,
+    SubscriptionResolver,
 };
 
 pub async fn execute<
@@ -12,61 +38,633 @@ pub async fn execute<
     container: &Container<Query, Mutation, Subscription>,
     request: Request,
 ) -> Response {
-    let query_doc = match graphql_parser::parse_query::<String>(&request.query) {
-        Ok(doc) => doc,
-        Err(_) => {
-            let err = GqlError::new("failed to parse query", None);
-            return Response::from_errors(vec![err]);
+    let request = match resolve_request_query(container, request).await {
+        Ok(request) => request,
+        Err(error) => return Response::from_errors(vec![error]),
+    };
+    execute_resolved_request(container, request, Default::default()).await
+}
+
+/// Executes a query with `@defer` fragments and `@stream` lists, and returns the initial payload followed by the
+/// incremental payloads. Mutations and queries without the directives have only the initial payload.
+///
+/// `execute` resolves the deferred fragments and the streamed lists in the single response.
+pub fn execute_incremental<
+    Query: SelectionSetResolver + 'static,
+    Mutation: SelectionSetResolver + 'static,
+    Subscription: SelectionSetResolver + 'static,
+>(
+    container: &Container<Query, Mutation, Subscription>,
+    request: Request,
+) -> BoxStream<'static, IncrementalResponse> {
+    let container = container.clone();
+
+    Box::pin(async_stream::stream! {
+        let request = match resolve_request_query(&container, request).await {
+            Ok(request) => request,
+            Err(error) => {
+                yield IncrementalResponse::initial(Response::from_errors(vec![error]), false);
+                return;
+            }
+        };
+
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let execution = execute_resolved_request(&container, request, Arc::new(Incremental::new(sender)));
+        futures_util::pin_mut!(execution);
+        let mut response = None;
+        let mut has_sent = false;
+        loop {
+            // The execution resolves the deferred parts while their payloads are sent.
+            let received = poll_fn(|cx| {
+                if response.is_none() {
+                    if let Poll::Ready(result) = execution.as_mut().poll(cx) {
+                        response = Some(result);
+                    }
+                }
+                match receiver.poll_recv(cx) {
+                    Poll::Pending if response.is_none() => Poll::Pending,
+                    Poll::Pending => Poll::Ready(None),
+                    Poll::Ready(payload) => Poll::Ready(payload),
+                }
+            })
+            .await;
+            let mut payloads = match received {
+                Some(payload) => vec![payload],
+                None => break,
+            };
+            if response.is_none() {
+                response = execution.as_mut().now_or_never();
+            }
+            while let Some(Some(payload)) = receiver.recv().now_or_never() {
+                payloads.push(payload);
+            }
+
+            // The last payload has the extensions of the response once the execution is done.
+            let is_done = response.is_some();
+            let last = payloads.len() - 1;
+            for (index, (mut payload, state)) in payloads.into_iter().enumerate() {
+                payload.has_next = !is_done || index < last;
+                if !payload.has_next {
+                    if let Some(response) = &mut response {
+                        payload.extensions.extend(std::mem::take(&mut response.extensions));
+                    }
+                }
+                yield payload;
+                has_sent = true;
+                state.mark_sent();
+            }
+            if is_done {
+                return;
+            }
+        }
+
+        if let Some(response) = response {
+            if has_sent {
+                yield IncrementalResponse {
+                    extensions: response.extensions,
+                    has_next: false,
+                    ..Default::default()
+                };
+            } else {
+                yield IncrementalResponse::initial(response, false);
+            }
         }
+    })
+}
+
+async fn execute_resolved_request<
+    Query: SelectionSetResolver + 'static,
+    Mutation: SelectionSetResolver + 'static,
+    Subscription: SelectionSetResolver + 'static,
+>(
+    container: &Container<Query, Mutation, Subscription>,
+    request: Request,
+    incremental: Arc<Incremental>,
+) -> Response {
+    let extensions = Extensions::new(&container.extensions);
+    let data = request_data(&container.data, &request.data);
+    let ctx = ExtensionContext {
+        schema: &container.schema,
+        data: &data,
+        query: &request.query,
+        operation_name: request.operation_name.as_deref(),
+        variables: &request.variables,
     };
+
+    let span = telemetry::request_span(request.operation_name.as_deref());
+    telemetry::in_span(span, async {
+        let response = extensions
+            .request(
+                &ctx,
+                Box::pin(execute_request(
+                    container,
+                    &request,
+                    &extensions,
+                    &ctx,
+                    incremental,
+                )),
+            )
+            .await;
+        // Errors of fields are recorded in the span of the resolver.
+        for error in response.errors.iter().filter(|error| error.path.is_empty()) {
+            telemetry::record_error(error);
+        }
+        response
+    })
+    .await
+}
+
+async fn execute_request<
+    'a,
+    Query: SelectionSetResolver + 'static,
+    Mutation: SelectionSetResolver + 'static,
+    Subscription: SelectionSetResolver + 'static,
+>(
+    container: &'a Container<Query, Mutation, Subscription>,
+    request: &'a Request,
+    extensions: &'a Extensions,
+    ctx: &'a ExtensionContext<'a>,
+    incremental: Arc<Incremental>,
+) -> Response {
+    let query_doc = match parse_document(container, request, extensions, ctx).await {
+        Ok(doc) => doc,
+        Err(error) => return Response::from_errors(vec![error]),
+    };
+
     let operation = build_operation(
-        &query_doc,
+        &query_doc.document,
         request.operation_name.clone(),
         request.variables.clone(),
+        ctx.data.clone(),
     );
 
     let operation = match operation {
         Ok(op) => op,
         Err(error) => return Response::from_errors(vec![error]),
     };
+    telemetry::record_operation_type(&operation.operation_type);
 
-    if let Err(errors) = apply_validation(
+    let validation_result = match validate_document(
+        container, request, extensions, ctx, &query_doc, &operation,
+    )
+    .await
+    {
+        Ok(result) => result,
+        Err(errors) => return Response::from_errors(errors),
+    };
+
+    // The fields of a mutation are resolved serially, so a mutation is sent in a single payload.
+    let incremental = match operation.operation_type {
+        OperationType::Query => incremental,
+        _ => Default::default(),
+    };
+    let operation = match coerce_variables(
         &container.schema,
-        &query_doc,
-        Some(&request.variables),
-        &operation,
-        request.operation_name.as_deref(),
+        &operation.variable_definitions,
+        &operation.variables,
     ) {
-        return Response::from_errors(errors);
+        Ok(variables) => operation.with_variables(variables, incremental),
+        Err(errors) => return Response::from_errors(errors),
+    };
+
+    let execution = extensions.execute(
+        ctx,
+        Box::pin(execute_operation(container, &operation, extensions)),
+    );
+    let mut response = telemetry::in_span(telemetry::execute_span(), execution).await;
+    if let Some(complexity) = validation_result.complexity {
+        response
+            .extensions
+            .insert("complexity".to_string(), GqlValue::from(complexity));
+    }
+    response
+}
+
+/// Executes the requests of a batch concurrently. The responses are in the order of the requests.
+pub async fn execute_batch<
+    Query: SelectionSetResolver + 'static,
+    Mutation: SelectionSetResolver + 'static,
+    Subscription: SelectionSetResolver + 'static,
+>(
+    container: &Container<Query, Mutation, Subscription>,
+    batch_request: BatchRequest,
+) -> BatchResponse {
+    match batch_request {
+        BatchRequest::Single(request) => BatchResponse::Single(execute(container, request).await),
+        BatchRequest::Batch(requests) => {
+            if let Some(max_batch_size) = container.max_batch_size {
+                if requests.len() > max_batch_size {
+                    let error = GqlError::new(
+                        format!(
+                            "Batch has {} requests, which exceeds the maximum batch size of {}.",
+                            requests.len(),
+                            max_batch_size
+                        ),
+                        None,
+                    );
+                    return BatchResponse::Single(Response::from_errors(vec![error]));
+                }
+            }
+            let responses = join_all(
+                requests
+                    .into_iter()
+                    .map(|request| execute(container, request)),
+            )
+            .await;
+            BatchResponse::Batch(responses)
+        }
     }
+}
 
-    let ctx = build_context(&container.schema, &operation);
+/// Executes a subscription, and returns a response for each of its events.
+/// Queries and mutations have a single response.
+///
+/// The `request` and `execute` extension hooks run for each response of the stream.
+pub fn execute_stream<
+    Query: SelectionSetResolver + 'static,
+    Mutation: SelectionSetResolver + 'static,
+    Subscription: SubscriptionResolver + 'static,
+>(
+    container: &Container<Query, Mutation, Subscription>,
+    request: Request,
+) -> BoxStream<'static, Response> {
+    execute_subscription(container, request)
+        .map(SubscriptionResponse::into_response)
+        .boxed()
+}
+
+/// A response of `execute_subscription`.
+pub(crate) enum SubscriptionResponse {
+    /// The errors of a request which fails before its execution, e.g. on parsing, validation or variables.
+    RequestError(Response),
+    /// A result of the execution, which may have errors of the fields.
+    Executed(Response),
+}
+
+impl SubscriptionResponse {
+    pub(crate) fn into_response(self) -> Response {
+        match self {
+            SubscriptionResponse::RequestError(response) => response,
+            SubscriptionResponse::Executed(response) => response,
+        }
+    }
+}
+
+/// Executes a request like `execute_stream`, and tells the errors of the request from the results of its execution.
+pub(crate) fn execute_subscription<
+    Query: SelectionSetResolver + 'static,
+    Mutation: SelectionSetResolver + 'static,
+    Subscription: SubscriptionResolver + 'static,
+>(
+    container: &Container<Query, Mutation, Subscription>,
+    request: Request,
+) -> BoxStream<'static, SubscriptionResponse> {
+    let container = container.clone();
+
+    Box::pin(async_stream::stream! {
+        let request = match resolve_request_query(&container, request).await {
+            Ok(request) => request,
+            Err(error) => {
+                yield SubscriptionResponse::RequestError(Response::from_errors(vec![error]));
+                return;
+            }
+        };
+        let extensions = Extensions::new(&container.extensions);
+        let data = request_data(&container.data, &request.data);
+        let ctx = ExtensionContext {
+            schema: &container.schema,
+            data: &data,
+            query: &request.query,
+            operation_name: request.operation_name.as_deref(),
+            variables: &request.variables,
+        };
+
+        let query_doc = match parse_document(&container, &request, &extensions, &ctx).await {
+            Ok(doc) => doc,
+            Err(error) => {
+                yield SubscriptionResponse::RequestError(failed_request(&extensions, &ctx, vec![error]).await);
+                return;
+            }
+        };
+
+        let operation = build_operation(
+            &query_doc.document,
+            request.operation_name.clone(),
+            request.variables.clone(),
+            data.clone(),
+        );
+
+        let operation = match operation {
+            Ok(op) => op,
+            Err(error) => {
+                yield SubscriptionResponse::RequestError(failed_request(&extensions, &ctx, vec![error]).await);
+                return;
+            }
+        };
+
+        if let Err(errors) =
+            validate_document(&container, &request, &extensions, &ctx, &query_doc, &operation).await
+        {
+            yield SubscriptionResponse::RequestError(failed_request(&extensions, &ctx, errors).await);
+            return;
+        }
+
+        let operation = match coerce_variables(
+            &container.schema,
+            &operation.variable_definitions,
+            &operation.variables,
+        ) {
+            Ok(variables) => operation.with_variables(variables, Default::default()),
+            Err(errors) => {
+                yield SubscriptionResponse::RequestError(failed_request(&extensions, &ctx, errors).await);
+                return;
+            }
+        };
+
+        if !matches!(operation.operation_type, OperationType::Subscription) {
+            let execution = extensions.execute(
+                &ctx,
+                Box::pin(execute_operation(&container, &operation, &extensions)),
+            );
+            let execution = telemetry::in_span(telemetry::execute_span(), execution);
+            yield SubscriptionResponse::Executed(extensions.request(&ctx, Box::pin(execution)).await);
+            return;
+        }
+
+        let root_ctx = build_context(&container.schema, &operation, &extensions);
+        let mut root_fields = Vec::new();
+        collect_root_fields(&root_ctx, root_ctx.item, &mut root_fields);
+        let field = match root_fields.as_slice() {
+            [field] => *field,
+            _ => {
+                let error = GqlError::new(
+                    "Subscription operations must select exactly one root field",
+                    Some(root_ctx.item.span.0),
+                );
+                yield SubscriptionResponse::RequestError(failed_request(&extensions, &ctx, vec![error]).await);
+                return;
+            }
+        };
+        let mut ctx_field = root_ctx.with_field(field);
+        ctx_field.field_definition = container.schema.subscriptions.get(&field.name);
+
+        let stream = match container
+            .subscription_resolvers
+            .resolve_subscription_field(&ctx_field)
+            .await
+        {
+            Ok(Some(stream)) => stream,
+            Ok(None) => {
+                let error = GqlError::new(
+                    format!("{} is not defined in Subscription", field.name),
+                    Some(field.position),
+                );
+                yield SubscriptionResponse::Executed(failed_request(&extensions, &ctx, vec![error]).await);
+                return;
+            }
+            Err(mut error) => {
+                if error.path.is_empty() {
+                    error.path = ctx_field.path.clone();
+                }
+                yield SubscriptionResponse::Executed(failed_request(&extensions, &ctx, vec![error]).await);
+                return;
+            }
+        };
+
+        let mut stream = stream.peekable();
+        while Pin::new(&mut stream).peek().await.is_some() {
+            let next = async {
+                let result = stream.next().await;
+                let mut errors = std::mem::take(&mut *operation.errors.lock().unwrap());
+                match result {
+                    Some(Ok(value)) => {
+                        let response_name =
+                            field.alias.clone().unwrap_or_else(|| field.name.clone());
+                        let data = GqlValue::Object([(response_name, value)].into());
+                        if errors.is_empty() {
+                            Response::new(data)
+                        } else {
+                            Response::from_data_and_errors(data, errors)
+                        }
+                    }
+                    Some(Err(mut error)) => {
+                        if error.path.is_empty() {
+                            error.path = ctx_field.path.clone();
+                        }
+                        errors.insert(0, error);
+                        Response::from_errors(errors)
+                    }
+                    None => Response::from_errors(errors),
+                }
+            };
+            let execution = extensions.execute(&ctx, Box::pin(next));
+            let execution = telemetry::in_span(telemetry::execute_span(), execution);
+            yield SubscriptionResponse::Executed(extensions.request(&ctx, Box::pin(execution)).await);
+        }
+    })
+}
+
+/// Passes the errors of a request that fails before its execution through the `request` extension hook.
+async fn failed_request(
+    extensions: &Extensions,
+    ctx: &ExtensionContext<'_>,
+    errors: Vec<GqlError>,
+) -> Response {
+    extensions
+        .request(ctx, Box::pin(future::ready(Response::from_errors(errors))))
+        .await
+}
+
+async fn execute_operation<
+    'a,
+    Query: SelectionSetResolver + 'static,
+    Mutation: SelectionSetResolver + 'static,
+    Subscription: SelectionSetResolver + 'static,
+>(
+    container: &'a Container<Query, Mutation, Subscription>,
+    operation: &'a Operation,
+    extensions: &'a Extensions,
+) -> Response {
+    let ctx = build_context(&container.schema, operation, extensions);
 
     let result = match operation.operation_type {
         OperationType::Query => {
-            resolve_selection_parallelly(&ctx, &container.query_resolvers).await
+            let resolve = resolve_selection_parallelly(&ctx, &container.query_resolvers);
+            match &ctx.incremental {
+                Some(scope) => {
+                    let (result, deferred) = scope.resolve(resolve, |value| value).await;
+                    // The data is sent in the initial payload before its deferred parts are resolved.
+                    if let (Ok(value), Some(deferred)) = (&result, deferred) {
+                        let response = operation_response(&ctx, Ok(value.clone()));
+                        scope.send(IncrementalResponse::initial(response, true));
+                        deferred.await;
+                    }
+                    result
+                }
+                None => resolve.await,
+            }
         }
         OperationType::Mutation => {
             resolve_selection_serially(&ctx, &container.mutation_resolvers).await
         }
         OperationType::Subscription => {
-            let error = GqlError::new("Subscription cannot execute from this path", None);
+            let error = GqlError::new("Subscription must be executed with execute_stream", None);
             return Response::from_errors(vec![error]);
         }
     };
+    operation_response(&ctx, result)
+}
 
+fn operation_response(ctx: &SelectionSetContext<'_>, result: ResolverResult<GqlValue>) -> Response {
+    let mut errors = ctx.operation.errors.lock().unwrap().clone();
+    if let Some(scope) = &ctx.incremental {
+        errors.extend(scope.payload.take_errors());
+    }
     match result {
         Ok(value) => {
-            if !ctx.operation.errors.lock().unwrap().is_empty() {
-                Response::from_data_and_errors(value, ctx.operation.errors.lock().unwrap().clone())
+            if !errors.is_empty() {
+                Response::from_data_and_errors(value, errors)
             } else {
                 Response::new(value)
             }
         }
         Err(error) => {
-            let mut errors = vec![error];
-            errors.extend(ctx.operation.errors.lock().unwrap().clone());
+            errors.insert(0, error);
             Response::from_errors(errors)
         }
     }
 }
+
+/// Parses the query of the request, or takes its document from the cache.
+async fn parse_document<
+    Query: SelectionSetResolver + 'static,
+    Mutation: SelectionSetResolver + 'static,
+    Subscription: SelectionSetResolver + 'static,
+>(
+    container: &Container<Query, Mutation, Subscription>,
+    request: &Request,
+    extensions: &Extensions,
+    ctx: &ExtensionContext<'_>,
+) -> Result<CachedDocument, GqlError> {
+    let cached = container
+        .document_cache
+        .as_ref()
+        .and_then(|cache| cache.get(&request.query, request.operation_name.as_deref()));
+    if let Some(cached) = cached {
+        return Ok(cached);
+    }
+
+    let parse = extensions.parse_query(
+        ctx,
+        Box::pin(async {
+            graphql_parser::parse_query::<String>(&request.query)
+                .map_err(|_| GqlError::new("failed to parse query", None))
+        }),
+    );
+    let document = telemetry::in_span(telemetry::parse_span(), parse).await?;
+    Ok(CachedDocument {
+        document: Arc::new(document.into_static()),
+        validation: None,
+    })
+}
+
+/// Validates the operation. The outcome of the validation rules is taken from the cache, or stored in it.
+async fn validate_document<
+    'a,
+    Query: SelectionSetResolver + 'static,
+    Mutation: SelectionSetResolver + 'static,
+    Subscription: SelectionSetResolver + 'static,
+>(
+    container: &'a Container<Query, Mutation, Subscription>,
+    request: &'a Request,
+    extensions: &'a Extensions,
+    ctx: &'a ExtensionContext<'a>,
+    query_doc: &'a CachedDocument,
+    operation: &'a Operation,
+) -> Result<ValidationResult, Vec<GqlError>> {
+    if let Some(validation) = &query_doc.validation {
+        validation.clone()?;
+        return check_complexity(&container.schema, operation, &container.limits);
+    }
+
+    let validation = extensions.validation(
+        ctx,
+        Box::pin(async {
+            let validation = apply_validation_rules(
+                &container.schema,
+                &query_doc.document,
+                Some(&request.variables),
+                operation,
+                request.operation_name.as_deref(),
+                &container.limits,
+            );
+            if let Some(cache) = &container.document_cache {
+                cache.insert(
+                    &request.query,
+                    request.operation_name.as_deref(),
+                    query_doc.document.clone(),
+                    validation.clone(),
+                );
+            }
+            validation?;
+            check_complexity(&container.schema, operation, &container.limits)
+        }),
+    );
+    telemetry::in_span(telemetry::validate_span(), validation).await
+}
+
+/// Resolves the query of a persisted query or a trusted document.
+async fn resolve_request_query<
+    Query: SelectionSetResolver + 'static,
+    Mutation: SelectionSetResolver + 'static,
+    Subscription: SelectionSetResolver + 'static,
+>(
+    container: &Container<Query, Mutation, Subscription>,
+    request: Request,
+) -> Result<Request, GqlError> {
+    let store = container.persisted_query_store.as_deref();
+    resolve_persisted_query(store, request, |request| {
+        resolve_trusted_document(container.trusted_documents.as_ref(), request)
+    })
+    .await
+}
+
+// Request data takes precedence over the data of the container.
+fn request_data(container_data: &Data, request_data: &Data) -> Data {
+    let mut data = container_data.clone();
+    data.merge(request_data.clone());
+    data
+}
+
+fn collect_root_fields<'a>(
+    ctx: &SelectionSetContext<'a>,
+    selection_set: &'a SelectionSet<'static, String>,
+    fields: &mut Vec<&'a Field<'static, String>>,
+) {
+    for item in &selection_set.items {
+        match item {
+            Selection::Field(field) => {
+                if !ctx.is_skip(&field.directives) {
+                    fields.push(field);
+                }
+            }
+            Selection::FragmentSpread(fragment_spread) => {
+                if ctx.is_skip(&fragment_spread.directives) {
+                    continue;
+                }
+                if let Some(fragment_def) = ctx
+                    .operation
+                    .fragment_definitions
+                    .get(&fragment_spread.fragment_name)
+                {
+                    collect_root_fields(ctx, &fragment_def.selection_set, fields);
+                }
+            }
+            Selection::InlineFragment(inline_fragment) => {
+                if !ctx.is_skip(&inline_fragment.directives) {
+                    collect_root_fields(ctx, &inline_fragment.selection_set, fields);
+                }
+            }
+        }
+    }
+}
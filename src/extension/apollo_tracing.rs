@@ -0,0 +1,182 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use graphql_parser::query::Document;
+use serde::Serialize;
+
+use crate::{
+    types::value::serialize_into_gql_value, validation::ValidationResult, Context, GqlError,
+    GqlValue, PathSegment, ResolveFut, ResolverResult, Response,
+};
+
+use super::{Extension, ExtensionContext, ExtensionFactory, NextFut};
+
+/// Adds the timings of a request to `extensions.tracing` in the Apollo tracing format.
+///
+/// https://github.com/apollographql/apollo-tracing
+pub struct ApolloTracing;
+
+impl ExtensionFactory for ApolloTracing {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(ApolloTracingExtension {
+            state: Mutex::new(TracingState::new()),
+        })
+    }
+}
+
+struct ApolloTracingExtension {
+    state: Mutex<TracingState>,
+}
+
+/// The trace of a response. A subscription starts a new trace after each of its responses.
+struct TracingState {
+    start_time: DateTime<Utc>,
+    start: Instant,
+    parsing: Option<Timing>,
+    validation: Option<Timing>,
+    resolvers: Vec<ResolverTiming>,
+}
+
+#[derive(Serialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+struct Timing {
+    start_offset: u64,
+    duration: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ResolverTiming {
+    path: Vec<PathSegment>,
+    parent_type: String,
+    field_name: String,
+    return_type: String,
+    start_offset: u64,
+    duration: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TracingResult<'a> {
+    version: u8,
+    start_time: String,
+    end_time: String,
+    duration: u64,
+    parsing: Option<Timing>,
+    validation: Option<Timing>,
+    execution: ExecutionResult<'a>,
+}
+
+#[derive(Serialize)]
+struct ExecutionResult<'a> {
+    resolvers: &'a [ResolverTiming],
+}
+
+impl TracingState {
+    fn new() -> Self {
+        TracingState {
+            start_time: Utc::now(),
+            start: Instant::now(),
+            parsing: None,
+            validation: None,
+            resolvers: Vec::new(),
+        }
+    }
+
+    /// The nanoseconds elapsed since the start of the trace.
+    fn offset(&self, instant: Instant) -> u64 {
+        instant.saturating_duration_since(self.start).as_nanos() as u64
+    }
+
+    fn timing(&self, start: Instant) -> Timing {
+        Timing {
+            start_offset: self.offset(start),
+            duration: start.elapsed().as_nanos() as u64,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for ApolloTracingExtension {
+    async fn request(&self, _ctx: &ExtensionContext<'_>, next: NextFut<'_, Response>) -> Response {
+        let mut response = next.await;
+        let end_time = Utc::now();
+
+        let mut state = self.state.lock().unwrap();
+        state
+            .resolvers
+            .sort_by_key(|resolver| resolver.start_offset);
+        let result = TracingResult {
+            version: 1,
+            start_time: state
+                .start_time
+                .to_rfc3339_opts(SecondsFormat::Millis, true),
+            end_time: end_time.to_rfc3339_opts(SecondsFormat::Millis, true),
+            duration: state.offset(Instant::now()),
+            parsing: state.parsing,
+            validation: state.validation,
+            execution: ExecutionResult {
+                resolvers: &state.resolvers,
+            },
+        };
+        if let Ok(value) = serialize_into_gql_value(result) {
+            response.extensions.insert("tracing".to_string(), value);
+        }
+        // The next event of a subscription is traced on its own.
+        *state = TracingState::new();
+        response
+    }
+
+    async fn parse_query<'a>(
+        &self,
+        _ctx: &ExtensionContext<'_>,
+        next: NextFut<'_, Result<Document<'a, String>, GqlError>>,
+    ) -> Result<Document<'a, String>, GqlError> {
+        let start = Instant::now();
+        let result = next.await;
+        let mut state = self.state.lock().unwrap();
+        state.parsing = Some(state.timing(start));
+        result
+    }
+
+    async fn validation(
+        &self,
+        _ctx: &ExtensionContext<'_>,
+        next: NextFut<'_, Result<ValidationResult, Vec<GqlError>>>,
+    ) -> Result<ValidationResult, Vec<GqlError>> {
+        let start = Instant::now();
+        let result = next.await;
+        let mut state = self.state.lock().unwrap();
+        state.validation = Some(state.timing(start));
+        result
+    }
+
+    async fn resolve_field(
+        &self,
+        ctx: &Context<'_>,
+        parent_type: &str,
+        next: ResolveFut<'_>,
+    ) -> ResolverResult<Option<GqlValue>> {
+        let start = Instant::now();
+        let result = next.await;
+        let return_type = ctx
+            .field_type()
+            .map(|ty| ty.to_parser_type().to_string())
+            .unwrap_or_default();
+
+        let mut state = self.state.lock().unwrap();
+        let timing = state.timing(start);
+        state.resolvers.push(ResolverTiming {
+            path: ctx.path.clone(),
+            parent_type: parent_type.to_string(),
+            field_name: ctx.item.name.clone(),
+            return_type,
+            start_offset: timing.start_offset,
+            duration: timing.duration,
+        });
+        result
+    }
+}
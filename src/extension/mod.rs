@@ -0,0 +1,155 @@
+mod apollo_tracing;
+
+use std::{future::Future, sync::Arc};
+
+use futures_util::future::BoxFuture;
+use graphql_parser::query::Document;
+
+use crate::{
+    data::Data, types::schema::Schema, validation::ValidationResult, Context, GqlError, GqlValue,
+    ResolveFut, ResolverResult, Response, Variables,
+};
+
+pub use apollo_tracing::ApolloTracing;
+
+/// The future of the next step in the chain of extensions.
+pub type NextFut<'a, T> = &'a mut (dyn Future<Output = T> + Send + Unpin);
+
+/// The request which is being executed.
+pub struct ExtensionContext<'a> {
+    pub schema: &'a Schema,
+    /// The data of the container merged with the data of the request.
+    pub data: &'a Data,
+    pub query: &'a str,
+    pub operation_name: Option<&'a str>,
+    pub variables: &'a Variables,
+}
+
+/// Hooks around the steps of a request.
+///
+/// Each hook gets the next step as a future, so it can run code before and after the step or replace its result.
+/// Every hook calls the next step by default.
+#[async_trait::async_trait]
+pub trait Extension: Send + Sync {
+    /// Wraps the whole request. Entries can be added to `Response::extensions` here.
+    async fn request(&self, _ctx: &ExtensionContext<'_>, next: NextFut<'_, Response>) -> Response {
+        next.await
+    }
+
+    async fn parse_query<'a>(
+        &self,
+        _ctx: &ExtensionContext<'_>,
+        next: NextFut<'_, Result<Document<'a, String>, GqlError>>,
+    ) -> Result<Document<'a, String>, GqlError> {
+        next.await
+    }
+
+    async fn validation(
+        &self,
+        _ctx: &ExtensionContext<'_>,
+        next: NextFut<'_, Result<ValidationResult, Vec<GqlError>>>,
+    ) -> Result<ValidationResult, Vec<GqlError>> {
+        next.await
+    }
+
+    async fn execute(&self, _ctx: &ExtensionContext<'_>, next: NextFut<'_, Response>) -> Response {
+        next.await
+    }
+
+    /// Wraps the resolver of each field of an object of type `parent_type`.
+    async fn resolve_field(
+        &self,
+        _ctx: &Context<'_>,
+        _parent_type: &str,
+        next: ResolveFut<'_>,
+    ) -> ResolverResult<Option<GqlValue>> {
+        next.await
+    }
+}
+
+/// Creates the extension for each request, so that an extension can keep the state of a single request.
+pub trait ExtensionFactory: Send + Sync {
+    fn create(&self) -> Arc<dyn Extension>;
+}
+
+/// The extensions of a single request.
+#[derive(Default)]
+pub(crate) struct Extensions(Vec<Arc<dyn Extension>>);
+
+impl Extensions {
+    pub(crate) fn new(factories: &[Box<dyn ExtensionFactory>]) -> Self {
+        Extensions(factories.iter().map(|factory| factory.create()).collect())
+    }
+
+    /// Wraps `fut` with the hook of every extension. The first extension is the outermost.
+    fn chain<'a, T: 'a>(
+        &'a self,
+        fut: BoxFuture<'a, T>,
+        hook: impl Fn(&'a dyn Extension, BoxFuture<'a, T>) -> BoxFuture<'a, T>,
+    ) -> BoxFuture<'a, T> {
+        self.0
+            .iter()
+            .rev()
+            .fold(fut, |next, extension| hook(extension.as_ref(), next))
+    }
+
+    pub(crate) async fn request<'a>(
+        &'a self,
+        ctx: &'a ExtensionContext<'a>,
+        fut: BoxFuture<'a, Response>,
+    ) -> Response {
+        self.chain(fut, |extension, mut next| {
+            Box::pin(async move { extension.request(ctx, &mut next).await })
+        })
+        .await
+    }
+
+    pub(crate) async fn parse_query<'a, 'doc: 'a>(
+        &'a self,
+        ctx: &'a ExtensionContext<'a>,
+        fut: BoxFuture<'a, Result<Document<'doc, String>, GqlError>>,
+    ) -> Result<Document<'doc, String>, GqlError> {
+        self.chain(fut, |extension, mut next| {
+            Box::pin(async move { extension.parse_query(ctx, &mut next).await })
+        })
+        .await
+    }
+
+    pub(crate) async fn validation<'a>(
+        &'a self,
+        ctx: &'a ExtensionContext<'a>,
+        fut: BoxFuture<'a, Result<ValidationResult, Vec<GqlError>>>,
+    ) -> Result<ValidationResult, Vec<GqlError>> {
+        self.chain(fut, |extension, mut next| {
+            Box::pin(async move { extension.validation(ctx, &mut next).await })
+        })
+        .await
+    }
+
+    pub(crate) async fn execute<'a>(
+        &'a self,
+        ctx: &'a ExtensionContext<'a>,
+        fut: BoxFuture<'a, Response>,
+    ) -> Response {
+        self.chain(fut, |extension, mut next| {
+            Box::pin(async move { extension.execute(ctx, &mut next).await })
+        })
+        .await
+    }
+
+    pub(crate) async fn resolve_field<'a>(
+        &'a self,
+        ctx: &'a Context<'_>,
+        parent_type: &'a str,
+        fut: impl Future<Output = ResolverResult<Option<GqlValue>>> + Send + 'a,
+    ) -> ResolverResult<Option<GqlValue>> {
+        // Fields are resolved often, so the future is boxed only if there is an extension.
+        if self.0.is_empty() {
+            return fut.await;
+        }
+        self.chain(Box::pin(fut), |extension, mut next| {
+            Box::pin(async move { extension.resolve_field(ctx, parent_type, &mut next).await })
+        })
+        .await
+    }
+}
@@ -0,0 +1,181 @@
+//! Incremental delivery of `@defer` fragments and `@stream` lists.
+//!
+//! A query is executed once. A value which has deferred parts, i.e. deferred fragments, streamed items or children
+//! with deferred parts, publishes itself to the slot of its scope as soon as the rest of it is resolved, and keeps
+//! resolving the deferred parts. Its parent takes the published value, and drives the rest of the future of the
+//! child along with its own deferred parts. The deferred parts wait until the payload of their parent is sent, and
+//! then send their own payloads through a channel.
+
+use std::{
+    sync::{Arc, Mutex},
+    task::Poll,
+};
+
+use futures_util::{
+    future::{poll_fn, BoxFuture, Either},
+    stream::FuturesUnordered,
+    Future, FutureExt, StreamExt,
+};
+use tokio::sync::{mpsc, watch};
+
+use crate::{GqlError, GqlValue, IncrementalResponse, ResolverResult};
+
+/// The future which resolves the deferred parts of a value after the value is published.
+pub(crate) type DeferredFuture<'a> = BoxFuture<'a, ()>;
+
+/// A payload after the initial payload, and the payload state which is marked as sent once it is delivered.
+pub(crate) type SentPayload = (IncrementalResponse, Arc<PayloadState>);
+
+/// The incremental delivery of an operation. Mutations and `execute` do not have a channel, and they resolve
+/// `@defer` and `@stream` in the single response.
+#[derive(Debug, Default)]
+pub(crate) struct Incremental {
+    scope: Option<IncrementalScope>,
+}
+
+impl Incremental {
+    pub(crate) fn new(payloads: mpsc::UnboundedSender<SentPayload>) -> Self {
+        Incremental {
+            scope: Some(IncrementalScope {
+                slot: Default::default(),
+                payload: Default::default(),
+                payloads,
+            }),
+        }
+    }
+
+    /// The scope of the root selection set, whose value is the data of the initial payload.
+    pub(crate) fn root_scope(&self) -> Option<IncrementalScope> {
+        self.scope.clone()
+    }
+}
+
+/// The state of a payload which is shared by the values in it.
+#[derive(Debug)]
+pub(crate) struct PayloadState {
+    sent: watch::Sender<bool>,
+    is_sent: watch::Receiver<bool>,
+    /// The errors of the nullable values of the payload.
+    pub(crate) errors: Mutex<Vec<GqlError>>,
+}
+
+impl Default for PayloadState {
+    fn default() -> Self {
+        let (sent, is_sent) = watch::channel(false);
+        PayloadState {
+            sent,
+            is_sent,
+            errors: Default::default(),
+        }
+    }
+}
+
+impl PayloadState {
+    /// Marks the payload as delivered, which starts the deferred parts of its values.
+    pub(crate) fn mark_sent(&self) {
+        let _ = self.sent.send(true);
+    }
+
+    /// Waits until the payload is delivered.
+    pub(crate) async fn wait_sent(&self) {
+        let mut is_sent = self.is_sent.clone();
+        while !*is_sent.borrow() {
+            if is_sent.changed().await.is_err() {
+                return;
+            }
+        }
+    }
+
+    pub(crate) fn take_errors(&self) -> Vec<GqlError> {
+        std::mem::take(&mut *self.errors.lock().unwrap())
+    }
+}
+
+/// Where the value being resolved by `execute_incremental` is delivered.
+#[derive(Debug, Clone)]
+pub(crate) struct IncrementalScope {
+    /// Receives the value once it is resolved except for its deferred parts.
+    slot: Arc<Mutex<Option<GqlValue>>>,
+    /// The payload which the value is sent in.
+    pub(crate) payload: Arc<PayloadState>,
+    payloads: mpsc::UnboundedSender<SentPayload>,
+}
+
+impl IncrementalScope {
+    /// The scope of a field or a list item of the value, which has its own slot.
+    pub(crate) fn child(&self) -> Self {
+        IncrementalScope {
+            slot: Default::default(),
+            payload: self.payload.clone(),
+            payloads: self.payloads.clone(),
+        }
+    }
+
+    /// The scope of a deferred fragment or a streamed item, which is sent in a new payload.
+    pub(crate) fn deferred(&self) -> Self {
+        IncrementalScope {
+            slot: Default::default(),
+            payload: Default::default(),
+            payloads: self.payloads.clone(),
+        }
+    }
+
+    /// Publishes the value before its deferred parts are resolved.
+    pub(crate) fn publish(&self, value: GqlValue) {
+        *self.slot.lock().unwrap() = Some(value);
+    }
+
+    /// Sends the payload of the scope. The payloads are delivered in the order in which they are sent.
+    pub(crate) fn send(&self, mut response: IncrementalResponse) {
+        response.errors.extend(self.payload.take_errors());
+        let _ = self.payloads.send((response, self.payload.clone()));
+    }
+
+    /// Polls the future of the value of the scope until it completes or publishes the value.
+    /// A published value is returned with the rest of the future, which resolves the deferred parts of the value.
+    pub(crate) async fn resolve<'a, T: Send + 'a>(
+        &self,
+        future: impl Future<Output = ResolverResult<T>> + Send + 'a,
+        published: impl FnOnce(GqlValue) -> T,
+    ) -> (ResolverResult<T>, Option<DeferredFuture<'a>>) {
+        let mut future = future.boxed();
+        let polled = poll_fn(|cx| match future.as_mut().poll(cx) {
+            Poll::Ready(result) => Poll::Ready(Either::Left(result)),
+            Poll::Pending => match self.slot.lock().unwrap().take() {
+                Some(value) => Poll::Ready(Either::Right(value)),
+                None => Poll::Pending,
+            },
+        })
+        .await;
+        match polled {
+            Either::Left(result) => (result, None),
+            Either::Right(value) => (Ok(published(value)), Some(future.map(|_| ()).boxed())),
+        }
+    }
+}
+
+/// Polls `future` to completion while driving the deferred parts of the values which are already sent.
+pub(crate) async fn drive_with<F: Future>(
+    future: F,
+    deferred: &mut FuturesUnordered<DeferredFuture<'_>>,
+) -> F::Output {
+    futures_util::pin_mut!(future);
+    poll_fn(|cx| {
+        while let Poll::Ready(Some(())) = deferred.poll_next_unpin(cx) {}
+        future.as_mut().poll(cx)
+    })
+    .await
+}
+
+/// The `@defer` of a fragment.
+#[derive(Debug, Clone)]
+pub(crate) struct DeferDirective {
+    pub(crate) label: Option<String>,
+}
+
+/// The `@stream` of a list field.
+#[derive(Debug, Clone)]
+pub(crate) struct StreamDirective {
+    pub(crate) label: Option<String>,
+    pub(crate) initial_count: usize,
+}
@@ -0,0 +1,116 @@
+use indexmap::IndexMap;
+use serde_json::Number;
+
+use crate::{types::schema::Schema, GqlInputType, GqlValue, GqlValueType, TypeDefinition};
+
+/// Coerces an input value to the given schema type.
+/// Input object fields get their default values, and numbers are checked against the built-in scalars.
+pub(crate) fn coerce_input_value(
+    schema: &Schema,
+    value_type: &GqlValueType,
+    value: GqlValue,
+) -> Result<GqlValue, String> {
+    match (value_type, value) {
+        (GqlValueType::NonNullType(_), GqlValue::Null) => Err(format!(
+            "Expected non-nullable type \"{}\" not to be null.",
+            value_type.to_parser_type()
+        )),
+        (GqlValueType::NonNullType(inner_type), value) => {
+            coerce_input_value(schema, inner_type, value)
+        }
+        (_, GqlValue::Null) => Ok(GqlValue::Null),
+        (GqlValueType::ListType(item_type), GqlValue::List(items)) => items
+            .into_iter()
+            .map(|item| coerce_input_value(schema, item_type, item))
+            .collect::<Result<Vec<_>, _>>()
+            .map(GqlValue::List),
+        // A single value is coerced to a list of one item.
+        (GqlValueType::ListType(item_type), value) => Ok(GqlValue::List(vec![coerce_input_value(
+            schema, item_type, value,
+        )?])),
+        (GqlValueType::NamedType(type_name), value) => coerce_named_value(schema, type_name, value),
+    }
+}
+
+fn coerce_named_value(
+    schema: &Schema,
+    type_name: &str,
+    value: GqlValue,
+) -> Result<GqlValue, String> {
+    let invalid_value = |value: &GqlValue| {
+        format!(
+            "Expected type \"{}\", found {}.",
+            type_name,
+            serde_json::to_string(value).unwrap_or_else(|_| value.to_string())
+        )
+    };
+
+    match schema.type_definitions.get(type_name) {
+        Some(TypeDefinition::Scalar(_)) => match (type_name, value) {
+            // Int is a signed 32-bit integer. Floats without a fractional part are integers as well.
+            ("Int", GqlValue::Number(n)) => {
+                match i32::from_gql_value(Some(GqlValue::Number(n.clone()))) {
+                    Ok(n) => Ok(GqlValue::Number(Number::from(n))),
+                    Err(_) => Err(invalid_value(&GqlValue::Number(n))),
+                }
+            }
+            ("Float", GqlValue::Number(n)) => match n.as_f64().and_then(Number::from_f64) {
+                Some(n) => Ok(GqlValue::Number(n)),
+                None => Err(invalid_value(&GqlValue::Number(n))),
+            },
+            ("String", GqlValue::String(s)) => Ok(GqlValue::String(s)),
+            ("Boolean", GqlValue::Boolean(b)) => Ok(GqlValue::Boolean(b)),
+            ("ID", GqlValue::String(s)) => Ok(GqlValue::String(s)),
+            ("ID", GqlValue::Number(n)) if n.is_i64() || n.is_u64() => {
+                Ok(GqlValue::String(n.to_string()))
+            }
+            ("Int" | "Float" | "String" | "Boolean" | "ID", value) => Err(invalid_value(&value)),
+            // Custom scalars are parsed by their resolvers.
+            (_, value) => Ok(value),
+        },
+        Some(TypeDefinition::Enum(enum_type)) => match value {
+            GqlValue::String(name) | GqlValue::Enum(name) if enum_type.contains(&name) => {
+                Ok(GqlValue::Enum(name))
+            }
+            value => Err(invalid_value(&value)),
+        },
+        Some(TypeDefinition::InputObject(input_object)) => match value {
+            GqlValue::Object(mut fields) => {
+                if let Some(unknown_field) = fields
+                    .keys()
+                    .find(|name| !input_object.fields.iter().any(|field| &field.name == *name))
+                {
+                    return Err(format!(
+                        "Field \"{}\" is not defined by type \"{}\".",
+                        unknown_field, type_name
+                    ));
+                }
+
+                let mut coerced_fields = IndexMap::new();
+                for field in &input_object.fields {
+                    let field_value = match fields.remove(&field.name) {
+                        Some(field_value) => field_value,
+                        None => match &field.default_value {
+                            Some(default_value) => default_value.clone(),
+                            None if field.meta_type.is_non_null() => {
+                                return Err(format!(
+                                    "Field \"{}\" of required type \"{}\" was not provided.",
+                                    field.name,
+                                    field.meta_type.to_parser_type()
+                                ))
+                            }
+                            None => continue,
+                        },
+                    };
+                    coerced_fields.insert(
+                        field.name.clone(),
+                        coerce_input_value(schema, &field.meta_type, field_value)?,
+                    );
+                }
+                Ok(GqlValue::Object(coerced_fields))
+            }
+            value => Err(invalid_value(&value)),
+        },
+        _ => Err(format!("\"{}\" is not an input type.", type_name)),
+    }
+}
@@ -1,15 +1,19 @@
 mod boolean;
+mod coercion;
 mod id;
 mod list;
 mod number;
 mod object;
 mod optional;
 mod string;
+mod upload;
 
 use std::sync::Arc;
 
 use crate::GqlValue;
 
+pub(crate) use coercion::coerce_input_value;
+
 pub trait GqlInputType: Send + Sync + Sized {
     fn from_gql_value(value: Option<GqlValue>) -> Result<Self, String>;
 
@@ -4,241 +4,56 @@ use crate::GqlValue;
 
 use super::GqlInputType;
 
-impl GqlInputType for i8 {
-    fn from_gql_value(value: Option<GqlValue>) -> Result<Self, String> {
-        match value {
-            Some(value) => match value {
-                GqlValue::Number(v) => {
-                    let n = v.as_i64().ok_or_else(|| "Invalid number".to_string())?;
-                    Ok(n as Self)
+/// Reads an integer without losing precision. Floats are accepted only if they have no fractional part.
+fn integer_value(value: Option<GqlValue>) -> Result<i128, String> {
+    match value {
+        Some(GqlValue::Number(v)) => {
+            if let Some(n) = v.as_i64() {
+                Ok(n as i128)
+            } else if let Some(n) = v.as_u64() {
+                Ok(n as i128)
+            } else {
+                match v.as_f64() {
+                    Some(n) if n.fract() == 0.0 && n.abs() < i128::MAX as f64 => Ok(n as i128),
+                    _ => Err(format!("Expected type: integer, but found {}", v)),
                 }
-                invalid_value => Err(format!(
-                    "Expected type: number, but found {}",
-                    invalid_value.to_string()
-                )),
-            },
-            None => Err("Expected type: number, but not found".to_string()),
-        }
-    }
-
-    fn to_gql_value(&self) -> GqlValue {
-        GqlValue::Number(Number::from(*self))
-    }
-}
-
-impl GqlInputType for i16 {
-    fn from_gql_value(value: Option<GqlValue>) -> Result<Self, String> {
-        match value {
-            Some(value) => match value {
-                GqlValue::Number(v) => {
-                    let n = v.as_i64().ok_or_else(|| "Invalid number".to_string())?;
-                    Ok(n as Self)
-                }
-                invalid_value => Err(format!(
-                    "Expected type: number, but found {}",
-                    invalid_value.to_string()
-                )),
-            },
-            None => Err("Expected type: number, but not found".to_string()),
+            }
         }
-    }
-
-    fn to_gql_value(&self) -> GqlValue {
-        GqlValue::Number(Number::from(*self))
+        Some(invalid_value) => Err(format!(
+            "Expected type: number, but found {}",
+            invalid_value.to_string()
+        )),
+        None => Err("Expected type: number, but not found".to_string()),
     }
 }
 
-impl GqlInputType for i32 {
-    fn from_gql_value(value: Option<GqlValue>) -> Result<Self, String> {
-        match value {
-            Some(value) => match value {
-                GqlValue::Number(v) => {
-                    let n = v.as_i64().ok_or_else(|| "Invalid number".to_string())?;
-                    Ok(n as Self)
+macro_rules! integer_input_type {
+    ($($ty:ident),*) => {
+        $(
+            impl GqlInputType for $ty {
+                fn from_gql_value(value: Option<GqlValue>) -> Result<Self, String> {
+                    let n = integer_value(value)?;
+                    $ty::try_from(n)
+                        .map_err(|_| format!("{} is out of range for {}", n, stringify!($ty)))
                 }
-                invalid_value => Err(format!(
-                    "Expected type: number, but found {}",
-                    invalid_value.to_string()
-                )),
-            },
-            None => Err("Expected type: number, but not found".to_string()),
-        }
-    }
-
-    fn to_gql_value(&self) -> GqlValue {
-        GqlValue::Number(Number::from(*self))
-    }
-}
 
-impl GqlInputType for i64 {
-    fn from_gql_value(value: Option<GqlValue>) -> Result<Self, String> {
-        match value {
-            Some(value) => match value {
-                GqlValue::Number(v) => {
-                    let n = v.as_i64().ok_or_else(|| "Invalid number".to_string())?;
-                    Ok(n as Self)
+                fn to_gql_value(&self) -> GqlValue {
+                    GqlValue::Number(Number::from(*self))
                 }
-                invalid_value => Err(format!(
-                    "Expected type: number, but found {}",
-                    invalid_value.to_string()
-                )),
-            },
-            None => Err("Expected type: number, but not found".to_string()),
-        }
-    }
-
-    fn to_gql_value(&self) -> GqlValue {
-        GqlValue::Number(Number::from(*self))
-    }
+            }
+        )*
+    };
 }
 
-impl GqlInputType for u8 {
-    fn from_gql_value(value: Option<GqlValue>) -> Result<Self, String> {
-        match value {
-            Some(value) => match value {
-                GqlValue::Number(v) => {
-                    let n = v.as_i64().ok_or_else(|| "Invalid number".to_string())?;
-                    Ok(n as Self)
-                }
-                invalid_value => Err(format!(
-                    "Expected type: number, but found {}",
-                    invalid_value.to_string()
-                )),
-            },
-            None => Err("Expected type: number, but not found".to_string()),
-        }
-    }
-
-    fn to_gql_value(&self) -> GqlValue {
-        GqlValue::Number(Number::from(*self))
-    }
-}
-
-impl GqlInputType for u16 {
-    fn from_gql_value(value: Option<GqlValue>) -> Result<Self, String> {
-        match value {
-            Some(value) => match value {
-                GqlValue::Number(v) => {
-                    let n = v.as_i64().ok_or_else(|| "Invalid number".to_string())?;
-                    Ok(n as Self)
-                }
-                invalid_value => Err(format!(
-                    "Expected type: number, but found {}",
-                    invalid_value.to_string()
-                )),
-            },
-            None => Err("Expected type: number, but not found".to_string()),
-        }
-    }
-
-    fn to_gql_value(&self) -> GqlValue {
-        GqlValue::Number(Number::from(*self))
-    }
-}
-
-impl GqlInputType for u32 {
-    fn from_gql_value(value: Option<GqlValue>) -> Result<Self, String> {
-        match value {
-            Some(value) => match value {
-                GqlValue::Number(v) => {
-                    let n = v.as_i64().ok_or_else(|| "Invalid number".to_string())?;
-                    Ok(n as Self)
-                }
-                invalid_value => Err(format!(
-                    "Expected type: number, but found {}",
-                    invalid_value.to_string()
-                )),
-            },
-            None => Err("Expected type: number, but not found".to_string()),
-        }
-    }
-
-    fn to_gql_value(&self) -> GqlValue {
-        GqlValue::Number(Number::from(*self))
-    }
-}
-
-impl GqlInputType for u64 {
-    fn from_gql_value(value: Option<GqlValue>) -> Result<Self, String> {
-        match value {
-            Some(value) => match value {
-                GqlValue::Number(v) => {
-                    let n = v.as_i64().ok_or_else(|| "Invalid number".to_string())?;
-                    Ok(n as Self)
-                }
-                invalid_value => Err(format!(
-                    "Expected type: number, but found {}",
-                    invalid_value.to_string()
-                )),
-            },
-            None => Err("Expected type: number, but not found".to_string()),
-        }
-    }
-
-    fn to_gql_value(&self) -> GqlValue {
-        GqlValue::Number(Number::from(*self))
-    }
-}
-
-impl GqlInputType for usize {
-    fn from_gql_value(value: Option<GqlValue>) -> Result<Self, String> {
-        match value {
-            Some(value) => match value {
-                GqlValue::Number(v) => {
-                    let n = v.as_i64().ok_or_else(|| "Invalid number".to_string())?;
-                    Ok(n as Self)
-                }
-                invalid_value => Err(format!(
-                    "Expected type: number, but found {}",
-                    invalid_value.to_string()
-                )),
-            },
-            None => Err("Expected type: number, but not found".to_string()),
-        }
-    }
-
-    fn to_gql_value(&self) -> GqlValue {
-        GqlValue::Number(Number::from(*self))
-    }
-}
-
-impl GqlInputType for isize {
-    fn from_gql_value(value: Option<GqlValue>) -> Result<Self, String> {
-        match value {
-            Some(value) => match value {
-                GqlValue::Number(v) => {
-                    let n = v.as_i64().ok_or_else(|| "Invalid number".to_string())?;
-                    Ok(n as Self)
-                }
-                invalid_value => Err(format!(
-                    "Expected type: number, but found {}",
-                    invalid_value.to_string()
-                )),
-            },
-            None => Err("Expected type: number, but not found".to_string()),
-        }
-    }
-
-    fn to_gql_value(&self) -> GqlValue {
-        GqlValue::Number(Number::from(*self))
-    }
-}
+integer_input_type!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
 
 impl GqlInputType for f32 {
     fn from_gql_value(value: Option<GqlValue>) -> Result<Self, String> {
-        match value {
-            Some(value) => match value {
-                GqlValue::Number(v) => {
-                    let n = v.as_f64().ok_or_else(|| "Invalid number".to_string())?;
-                    Ok(n as Self)
-                }
-                invalid_value => Err(format!(
-                    "Expected type: number, but found {}",
-                    invalid_value.to_string()
-                )),
-            },
-            None => Err("Expected type: number, but not found".to_string()),
+        let n = f64::from_gql_value(value)?;
+        if n.is_finite() && n.abs() > f32::MAX as f64 {
+            return Err(format!("{} is out of range for f32", n));
         }
+        Ok(n as Self)
     }
 
     fn to_gql_value(&self) -> GqlValue {
@@ -273,3 +88,47 @@ impl GqlInputType for f64 {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Number;
+
+    use crate::{GqlInputType, GqlValue};
+
+    fn number(n: f64) -> Option<GqlValue> {
+        Number::from_f64(n).map(GqlValue::Number)
+    }
+
+    #[test]
+    fn test_integer_range() {
+        assert_eq!(i8::from_gql_value(Some(GqlValue::from(100))), Ok(100));
+        assert_eq!(
+            i8::from_gql_value(Some(GqlValue::from(300))),
+            Err("300 is out of range for i8".to_string())
+        );
+        assert_eq!(
+            u32::from_gql_value(Some(GqlValue::from(-1))),
+            Err("-1 is out of range for u32".to_string())
+        );
+        assert_eq!(
+            u64::from_gql_value(Some(GqlValue::from(u64::MAX))),
+            Ok(u64::MAX)
+        );
+    }
+
+    #[test]
+    fn test_integral_float() {
+        assert_eq!(i32::from_gql_value(number(2.0)), Ok(2));
+        assert_eq!(
+            i32::from_gql_value(number(2.5)),
+            Err("Expected type: integer, but found 2.5".to_string())
+        );
+    }
+
+    #[test]
+    fn test_float() {
+        assert_eq!(f64::from_gql_value(Some(GqlValue::from(3))), Ok(3.0));
+        assert_eq!(f32::from_gql_value(number(1.5)), Ok(1.5));
+        assert!(f32::from_gql_value(number(1e300)).is_err());
+    }
+}
@@ -1,5 +1,7 @@
 use std::collections::{BTreeMap, HashMap};
 
+use indexmap::IndexMap;
+
 use crate::GqlValue;
 
 use super::GqlInputType;
@@ -25,7 +27,7 @@ impl<T: GqlInputType> GqlInputType for BTreeMap<String, T> {
     }
 
     fn to_gql_value(&self) -> GqlValue {
-        let mut result = BTreeMap::new();
+        let mut result = IndexMap::new();
         for (key, value) in self {
             result.insert(key.clone(), T::to_gql_value(value));
         }
@@ -54,7 +56,7 @@ impl<T: GqlInputType> GqlInputType for HashMap<String, T> {
     }
 
     fn to_gql_value(&self) -> GqlValue {
-        let mut result = BTreeMap::new();
+        let mut result = IndexMap::new();
         for (key, value) in self {
             result.insert(key.clone(), T::to_gql_value(value));
         }
@@ -0,0 +1,23 @@
+use crate::{
+    types::{Upload, UPLOAD_PREFIX},
+    GqlValue,
+};
+
+use super::GqlInputType;
+
+impl GqlInputType for Upload {
+    fn from_gql_value(value: Option<GqlValue>) -> Result<Self, String> {
+        match value {
+            Some(GqlValue::String(v)) if v.starts_with(UPLOAD_PREFIX) => Ok(Upload(v)),
+            Some(invalid_value) => Err(format!(
+                "{}: invalid gql value for upload",
+                invalid_value.to_string()
+            )),
+            None => Err("Expected type: upload, but not found".to_string()),
+        }
+    }
+
+    fn to_gql_value(&self) -> GqlValue {
+        GqlValue::String(self.0.clone())
+    }
+}
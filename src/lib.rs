@@ -1,49 +1,71 @@
 mod container;
 mod context;
 mod custom_directive;
+mod data;
+mod dataloader;
+mod document_cache;
 mod error;
 mod executor;
+mod extension;
+mod incremental;
 mod input;
 mod operation;
+mod persisted_query;
 mod playground_html;
 mod query_root;
 mod request;
 mod resolver;
 mod response;
+mod telemetry;
 mod test_utils;
+mod trusted_documents;
 mod types;
 mod validation;
 mod variables;
+mod websocket;
 
 use std::path::Path;
 
 #[doc(hidden)]
 pub use async_trait;
+#[doc(hidden)]
+pub use futures_util;
+pub use indexmap::IndexMap;
 
 pub use container::Container;
 pub use context::{Context, ExecutionContext, SelectionSetContext};
 pub use custom_directive::CustomDirective;
-pub use error::{Error, GqlError, GqlErrorType, GqlTypedError};
-pub use executor::execute;
+pub use data::Data;
+pub use dataloader::{DataLoader, Loader};
+pub use error::{Error, GqlError, GqlErrorType, GqlTypedError, PathSegment};
+pub use executor::{execute, execute_batch, execute_incremental, execute_stream};
+pub use extension::{ApolloTracing, Extension, ExtensionContext, ExtensionFactory, NextFut};
 use futures_util::Future;
 pub use input::GqlInputType;
 pub use operation::OperationType;
+pub use persisted_query::{LruPersistedQueryStore, PersistedQueryStore};
 pub use playground_html::playground_html;
 pub use query_root::QueryRoot;
-pub use request::{receive_http_request, HttpRequestError, Request};
+pub use request::{
+    receive_batch_http_request, receive_http_request, receive_multipart_request, BatchRequest,
+    HttpRequestError, MultipartOptions, Request,
+};
 pub use resolver::{
     resolve_selection_parallelly, resolve_selection_serially, CollectFields, FieldResolver, Fields,
-    SelectionSetResolver,
+    SelectionSetResolver, SubscriptionResolver, SubscriptionStream,
 };
-pub use response::Response;
+pub use response::{BatchResponse, IncrementalResponse, Response};
 pub use test_utils::{build_test_request, check_gql_response, schema_content};
+pub use trusted_documents::TrustedDocuments;
 pub use types::schema::build_schema;
 pub use types::{
     DirectiveDefinition, EnumType, FieldType, GqlConstValue as Value, GqlDirective, GqlValue,
     GqlValueType, InputObjectType, InputValueType, InterfaceType, ObjectType, ScalarType, Schema,
-    TypeDefinition, UnionType, ID,
+    TypeDefinition, UnionType, Upload, UploadValue, ID,
 };
+pub use validation::{ValidationLimits, ValidationResult};
 pub use variables::Variables;
+pub use websocket::{WebSocketConnection, WebSocketProtocol, WsMessage};
 
 pub type ResolverResult<T> = ::std::result::Result<T, GqlError>;
 
@@ -99,6 +121,16 @@ impl SelectionSetResolver for EmptySubscription {
     }
 }
 
+#[async_trait::async_trait]
+impl SubscriptionResolver for EmptySubscription {
+    async fn resolve_subscription_field<'a>(
+        &'a self,
+        _ctx: &'a Context<'_>,
+    ) -> ResolverResult<Option<SubscriptionStream<'a>>> {
+        Ok(None)
+    }
+}
+
 pub type ResolveFut<'a> =
     &'a mut (dyn Future<Output = ResolverResult<Option<GqlValue>>> + Send + Unpin);
 
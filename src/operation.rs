@@ -7,32 +7,58 @@ use std::{
 use graphql_parser::{
     query::{Definition, Document, FragmentDefinition, SelectionSet, VariableDefinition},
     schema::Directive,
+    Pos,
 };
 
-use crate::{error::GqlError, Variables};
+use crate::{data::Data, error::GqlError, incremental::Incremental, Variables};
 
 #[derive(Debug)]
-pub struct OperationInner<'a> {
+pub struct OperationInner {
     pub operation_type: OperationType,
-    pub directives: Vec<Directive<'a, String>>,
-    pub variable_definitions: Vec<VariableDefinition<'a, String>>,
-    pub selection_set: SelectionSet<'a, String>,
-    pub fragment_definitions: HashMap<String, FragmentDefinition<'a, String>>,
+    /// The position of the operation definition in the document.
+    pub position: Pos,
+    pub directives: Arc<Vec<Directive<'static, String>>>,
+    pub variable_definitions: Arc<Vec<VariableDefinition<'static, String>>>,
+    pub selection_set: Arc<SelectionSet<'static, String>>,
+    pub fragment_definitions: Arc<HashMap<String, FragmentDefinition<'static, String>>>,
     pub errors: Mutex<Vec<GqlError>>,
     pub variables: Variables,
+    pub data: Data,
+    pub(crate) incremental: Arc<Incremental>,
 }
 
 #[derive(Debug)]
-pub struct Operation<'a>(Arc<OperationInner<'a>>);
+pub struct Operation(Arc<OperationInner>);
 
-impl<'a> Operation<'a> {
-    pub fn new(operation: OperationInner<'a>) -> Operation<'a> {
+impl Operation {
+    pub fn new(operation: OperationInner) -> Operation {
         Operation(Arc::new(operation))
     }
+
+    /// Returns a copy of the operation which uses the given variables, and delivers its deferred fragments and
+    /// streamed lists with `incremental`. The parts taken from the document are shared rather than copied.
+    pub(crate) fn with_variables(
+        &self,
+        variables: Variables,
+        incremental: Arc<Incremental>,
+    ) -> Operation {
+        Operation::new(OperationInner {
+            operation_type: self.operation_type.clone(),
+            position: self.position,
+            directives: Arc::clone(&self.directives),
+            variable_definitions: Arc::clone(&self.variable_definitions),
+            selection_set: Arc::clone(&self.selection_set),
+            fragment_definitions: Arc::clone(&self.fragment_definitions),
+            errors: Default::default(),
+            variables,
+            data: self.data.clone(),
+            incremental,
+        })
+    }
 }
 
-impl<'a> Deref for Operation<'a> {
-    type Target = OperationInner<'a>;
+impl Deref for Operation {
+    type Target = OperationInner;
 
     fn deref(&self) -> &Self::Target {
         &self.0
@@ -40,11 +66,12 @@ impl<'a> Deref for Operation<'a> {
 }
 
 #[derive(Clone, Debug)]
-struct OperationDefinition<'a> {
+struct OperationDefinition {
     operation_type: OperationType,
-    directives: Vec<Directive<'a, String>>,
-    variable_definitions: Vec<VariableDefinition<'a, String>>,
-    selection_set: SelectionSet<'a, String>,
+    position: Pos,
+    directives: Vec<Directive<'static, String>>,
+    variable_definitions: Vec<VariableDefinition<'static, String>>,
+    selection_set: SelectionSet<'static, String>,
 }
 
 #[derive(Clone, Debug)]
@@ -64,20 +91,22 @@ impl ToString for OperationType {
     }
 }
 
-pub fn get_operation_definitions<'a>(
-    doc: &'a Document<'a, String>,
-) -> Vec<&'a graphql_parser::query::Definition<'a, String>> {
+pub fn get_operation_definitions<'a, 'd>(
+    doc: &'a Document<'d, String>,
+) -> Vec<&'a graphql_parser::query::Definition<'d, String>> {
     doc.definitions
         .iter()
         .filter(|def| matches!(def, Definition::Operation(_)))
         .collect::<Vec<_>>()
 }
 
-pub fn build_operation<'a>(
-    doc: &'a Document<'a, String>,
+/// Builds the requested operation of a document. Documents are `'static` so that they can be shared by the document cache.
+pub fn build_operation(
+    doc: &Document<'static, String>,
     operation_name: Option<String>,
     variables: Variables,
-) -> Result<Operation<'a>, GqlError> {
+    data: Data,
+) -> Result<Operation, GqlError> {
     let mut fragment_definitions = HashMap::new();
 
     for def in &doc.definitions {
@@ -94,116 +123,92 @@ pub fn build_operation<'a>(
         ));
     };
 
-    let mut operation_definitions: HashMap<String, OperationDefinition> = HashMap::new();
+    let mut operation_definitions = HashMap::new();
     let no_name_key = "no_operation_name";
 
-    for definition in doc.clone().definitions {
+    for definition in &doc.definitions {
         if let Definition::Operation(operation) = definition {
-            match operation {
-                graphql_parser::query::OperationDefinition::SelectionSet(selection_set) => {
-                    operation_definitions.insert(
-                        no_name_key.to_string(),
-                        OperationDefinition {
-                            operation_type: OperationType::Query,
-                            selection_set,
-                            directives: vec![],
-                            variable_definitions: vec![],
-                        },
-                    );
-                }
-                graphql_parser::query::OperationDefinition::Query(query) => {
-                    let query_name = query.name.unwrap_or_else(|| no_name_key.to_string());
-                    operation_definitions.insert(
-                        query_name,
-                        OperationDefinition {
-                            operation_type: OperationType::Query,
-                            selection_set: query.selection_set,
-                            directives: query.directives,
-                            variable_definitions: query.variable_definitions,
-                        },
-                    );
-                }
+            let name = match operation {
+                graphql_parser::query::OperationDefinition::SelectionSet(_) => None,
+                graphql_parser::query::OperationDefinition::Query(query) => query.name.as_ref(),
                 graphql_parser::query::OperationDefinition::Mutation(mutation) => {
-                    let mutation_name = mutation.name.unwrap_or_else(|| no_name_key.to_string());
-                    operation_definitions.insert(
-                        mutation_name,
-                        OperationDefinition {
-                            operation_type: OperationType::Mutation,
-                            selection_set: mutation.selection_set,
-                            directives: mutation.directives,
-                            variable_definitions: mutation.variable_definitions,
-                        },
-                    );
+                    mutation.name.as_ref()
                 }
                 graphql_parser::query::OperationDefinition::Subscription(subscription) => {
-                    let subscription_name =
-                        subscription.name.unwrap_or_else(|| no_name_key.to_string());
-                    operation_definitions.insert(
-                        subscription_name,
-                        OperationDefinition {
-                            operation_type: OperationType::Subscription,
-                            selection_set: subscription.selection_set,
-                            directives: subscription.directives,
-                            variable_definitions: subscription.variable_definitions,
-                        },
-                    );
+                    subscription.name.as_ref()
                 }
-            }
+            };
+            operation_definitions.insert(name.map_or(no_name_key, String::as_str), operation);
         }
     }
 
-    match operation_name {
-        Some(name) => {
-            let target_def = operation_definitions.get(name.as_str());
-            match target_def {
-                Some(definition) => {
-                    let definition = definition.clone();
-                    Ok(Operation(Arc::new(OperationInner {
-                        operation_type: definition.operation_type,
-                        fragment_definitions,
-                        directives: definition.directives,
-                        variable_definitions: definition.variable_definitions,
-                        selection_set: definition.selection_set,
-                        errors: Default::default(),
-                        variables,
-                    })))
-                }
-                None => Err(GqlError::new(
+    let target_def = match operation_name {
+        Some(name) => match operation_definitions.get(name.as_str()) {
+            Some(definition) => *definition,
+            None => {
+                return Err(GqlError::new(
                     format!("operationName: {} is not contained in query", name),
                     None,
-                )),
+                ))
             }
-        }
-        None => match operation_definitions.get(&no_name_key.to_string()) {
-            Some(definition) => {
-                let definition = definition.clone();
-                Ok(Operation(Arc::new(OperationInner {
-                    operation_type: definition.operation_type,
-                    fragment_definitions,
-                    directives: definition.directives,
-                    variable_definitions: definition.variable_definitions,
-                    selection_set: definition.selection_set,
-                    errors: Default::default(),
-                    variables,
-                })))
+        },
+        None => match operation_definitions
+            .get(no_name_key)
+            .or_else(|| operation_definitions.values().next())
+        {
+            Some(definition) => *definition,
+            None => return Err(GqlError::new("operation does not exist", None)),
+        },
+    };
+
+    // Only the selected operation is copied out of the document.
+    let definition = match target_def {
+        graphql_parser::query::OperationDefinition::SelectionSet(selection_set) => {
+            OperationDefinition {
+                operation_type: OperationType::Query,
+                position: selection_set.span.0,
+                selection_set: selection_set.clone(),
+                directives: vec![],
+                variable_definitions: vec![],
             }
-            None => match operation_definitions.values().next() {
-                Some(definition) => {
-                    let definition = definition.clone();
-                    Ok(Operation(Arc::new(OperationInner {
-                        operation_type: definition.operation_type,
-                        fragment_definitions,
-                        directives: definition.directives,
-                        variable_definitions: definition.variable_definitions,
-                        selection_set: definition.selection_set,
-                        errors: Default::default(),
-                        variables,
-                    })))
-                }
-                None => Err(GqlError::new("operation does not exist", None)),
-            },
+        }
+        graphql_parser::query::OperationDefinition::Query(query) => OperationDefinition {
+            operation_type: OperationType::Query,
+            position: query.position,
+            selection_set: query.selection_set.clone(),
+            directives: query.directives.clone(),
+            variable_definitions: query.variable_definitions.clone(),
         },
-    }
+        graphql_parser::query::OperationDefinition::Mutation(mutation) => OperationDefinition {
+            operation_type: OperationType::Mutation,
+            position: mutation.position,
+            selection_set: mutation.selection_set.clone(),
+            directives: mutation.directives.clone(),
+            variable_definitions: mutation.variable_definitions.clone(),
+        },
+        graphql_parser::query::OperationDefinition::Subscription(subscription) => {
+            OperationDefinition {
+                operation_type: OperationType::Subscription,
+                position: subscription.position,
+                selection_set: subscription.selection_set.clone(),
+                directives: subscription.directives.clone(),
+                variable_definitions: subscription.variable_definitions.clone(),
+            }
+        }
+    };
+
+    Ok(Operation(Arc::new(OperationInner {
+        operation_type: definition.operation_type,
+        position: definition.position,
+        fragment_definitions: Arc::new(fragment_definitions),
+        directives: Arc::new(definition.directives),
+        variable_definitions: Arc::new(definition.variable_definitions),
+        selection_set: Arc::new(definition.selection_set),
+        errors: Default::default(),
+        variables,
+        data,
+        incremental: Default::default(),
+    })))
 }
 
 #[cfg(test)]
@@ -214,9 +219,11 @@ mod tests {
     fn build_single_operation() {
         let parsed_query =
             graphql_parser::parse_query::<String>(r#"query GetPerson { persons { name age } }"#)
-                .unwrap();
+                .unwrap()
+                .into_static();
 
-        let operation = build_operation(&parsed_query, None, Default::default());
+        let operation =
+            build_operation(&parsed_query, None, Default::default(), Default::default());
         assert!(operation.is_ok());
         assert_eq!(operation.unwrap().operation_type.to_string(), "Query");
     }
@@ -226,12 +233,14 @@ mod tests {
         let parsed_query = graphql_parser::parse_query::<String>(
             r#"query GetPerson { persons { name age } } query GetPet { pets { name kind } }"#,
         )
-        .unwrap();
+        .unwrap()
+        .into_static();
 
         let operation = build_operation(
             &parsed_query,
             Some("GetPerson".to_string()),
             Default::default(),
+            Default::default(),
         );
         assert!(operation.is_ok());
         assert_eq!(operation.unwrap().operation_type.to_string(), "Query");
@@ -242,9 +251,11 @@ mod tests {
         let parsed_query = graphql_parser::parse_query::<String>(
             r#"query GetPerson { persons { name age } } query GetPet { pets { name kind } }"#,
         )
-        .unwrap();
+        .unwrap()
+        .into_static();
 
-        let operation = build_operation(&parsed_query, None, Default::default());
+        let operation =
+            build_operation(&parsed_query, None, Default::default(), Default::default());
         assert!(operation.is_err());
     }
 }
@@ -0,0 +1,137 @@
+use std::sync::Mutex;
+
+use lru::LruCache;
+use sha2::{Digest, Sha256};
+
+use crate::{GqlError, GqlErrorType, GqlTypedError, GqlValue, Request};
+
+/// Stores the queries of Automatic Persisted Queries by their SHA-256 hash.
+#[async_trait::async_trait]
+pub trait PersistedQueryStore: Send + Sync + 'static {
+    async fn get(&self, hash: &str) -> Option<String>;
+
+    async fn set(&self, hash: String, query: String);
+}
+
+/// Keeps the most recently used queries in memory.
+pub struct LruPersistedQueryStore {
+    queries: Mutex<LruCache<String, String>>,
+}
+
+impl LruPersistedQueryStore {
+    pub fn new(capacity: usize) -> Self {
+        LruPersistedQueryStore {
+            queries: Mutex::new(LruCache::new(capacity.max(1))),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl PersistedQueryStore for LruPersistedQueryStore {
+    async fn get(&self, hash: &str) -> Option<String> {
+        self.queries.lock().unwrap().get(hash).cloned()
+    }
+
+    async fn set(&self, hash: String, query: String) {
+        self.queries.lock().unwrap().put(hash, query);
+    }
+}
+
+/// Resolves the query of a request which has `extensions.persistedQuery`.
+///
+/// A request without a query is looked up by its hash, and a request with a query registers it after the hash is verified.
+/// `resolve_document` checks the resolved request, e.g. that its query is trusted, so a query is only registered if it passes.
+pub(crate) async fn resolve_persisted_query(
+    store: Option<&dyn PersistedQueryStore>,
+    mut request: Request,
+    resolve_document: impl FnOnce(Request) -> Result<Request, GqlError>,
+) -> Result<Request, GqlError> {
+    let persisted_query = match request.extensions.get("persistedQuery") {
+        Some(GqlValue::Object(persisted_query)) => persisted_query,
+        _ => return resolve_document(request),
+    };
+    let store = match store {
+        Some(store) => store,
+        None => {
+            return Err(apq_error(
+                "PersistedQueryNotSupported",
+                GqlErrorType::BadRequest,
+            ))
+        }
+    };
+    if !matches!(persisted_query.get("version"), Some(GqlValue::Number(version)) if version.as_u64() == Some(1))
+    {
+        return Err(apq_error(
+            "Unsupported persisted query version",
+            GqlErrorType::BadRequest,
+        ));
+    }
+    let hash = match persisted_query.get("sha256Hash") {
+        Some(GqlValue::String(hash)) => hash.to_lowercase(),
+        _ => {
+            return Err(apq_error(
+                "persistedQuery.sha256Hash must be a string",
+                GqlErrorType::BadRequest,
+            ))
+        }
+    };
+
+    if request.query.is_empty() {
+        match store.get(&hash).await {
+            Some(query) => request.query = query,
+            None => return Err(apq_error("PersistedQueryNotFound", GqlErrorType::NotFound)),
+        }
+        resolve_document(request)
+    } else {
+        if sha256_hex(&request.query) != hash {
+            return Err(apq_error(
+                "provided sha does not match query",
+                GqlErrorType::BadRequest,
+            ));
+        }
+        let request = resolve_document(request)?;
+        store.set(hash, request.query.clone()).await;
+        Ok(request)
+    }
+}
+
+fn sha256_hex(query: &str) -> String {
+    format!("{:x}", Sha256::digest(query.as_bytes()))
+}
+
+fn apq_error(message: &str, error_type: GqlErrorType) -> GqlError {
+    GqlError::new(message, None).set_extentions(GqlTypedError {
+        error_type,
+        error_detail: None,
+        origin: None,
+        debug_info: None,
+        debug_uri: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{sha256_hex, LruPersistedQueryStore, PersistedQueryStore};
+
+    #[test]
+    fn test_sha256_hex() {
+        assert_eq!(
+            sha256_hex("{ __typename }"),
+            "7f56e67dd21ab3f30d1ff8b7bed08893f0a0db86449836189b361dd1e56ddb4b"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lru() {
+        let store = LruPersistedQueryStore::new(2);
+        store.set("a".to_string(), "{ a }".to_string()).await;
+        store.set("b".to_string(), "{ b }".to_string()).await;
+        assert_eq!(store.get("a").await, Some("{ a }".to_string()));
+
+        // "b" is the least recently used query.
+        store.set("c".to_string(), "{ c }".to_string()).await;
+        assert_eq!(store.get("b").await, None);
+        assert_eq!(store.get("a").await, Some("{ a }".to_string()));
+        assert_eq!(store.get("c").await, Some("{ c }".to_string()));
+    }
+}
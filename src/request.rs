@@ -1,11 +1,21 @@
-use std::collections::HashMap;
+use std::{
+    any::Any,
+    collections::{hash_map::Entry, HashMap},
+};
 
+use bytes::Bytes;
 use futures_util::{pin_mut, AsyncRead, AsyncReadExt};
 use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
 
-use crate::{variables::Variables, GqlValue};
+use crate::{
+    data::Data,
+    types::{Upload, UploadValue, UploadedFiles},
+    variables::Variables,
+    GqlInputType, GqlValue,
+};
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Request {
     #[serde(default)]
@@ -16,36 +26,336 @@ pub struct Request {
     pub variables: Variables,
     #[serde(default)]
     pub extensions: HashMap<String, GqlValue>,
+    /// The ID of a trusted document, which is sent instead of `query`.
+    #[serde(default)]
+    pub document_id: Option<String>,
+    #[serde(skip)]
+    pub data: Data,
+}
+
+impl Request {
+    pub fn data<T: Any + Send + Sync>(mut self, data: T) -> Self {
+        self.data.insert(data);
+        self
+    }
+}
+
+/// A single request, or a batch of requests which is sent as a JSON array.
+#[derive(Serialize, Debug)]
+#[serde(untagged)]
+pub enum BatchRequest {
+    Batch(Vec<Request>),
+    Single(Request),
+}
+
+impl BatchRequest {
+    /// Parses a JSON array as a batch and any other JSON value as a single request, so that the errors of serde
+    /// point at the invalid field.
+    pub fn from_json(data: &[u8]) -> Result<Self, HttpRequestError> {
+        let is_batch = data
+            .iter()
+            .find(|byte| !byte.is_ascii_whitespace())
+            .map(|byte| *byte == b'[')
+            .unwrap_or(false);
+        let batch_request = if is_batch {
+            serde_json::from_slice::<Vec<Request>>(data).map(BatchRequest::Batch)
+        } else {
+            serde_json::from_slice::<Request>(data).map(BatchRequest::Single)
+        };
+        batch_request.map_err(|err| HttpRequestError::InvalidRequest(Box::new(err)))
+    }
+
+    /// Returns the request. A batch is rejected, even if it has a single request.
+    pub fn into_single(self) -> Result<Request, HttpRequestError> {
+        match self {
+            BatchRequest::Single(request) => Ok(request),
+            BatchRequest::Batch(_) => Err(HttpRequestError::InvalidRequest(
+                "batch requests are not supported".into(),
+            )),
+        }
+    }
+
+    /// Adds the data to every request of the batch.
+    pub fn data<T: Any + Clone + Send + Sync>(self, data: T) -> Self {
+        match self {
+            BatchRequest::Single(request) => BatchRequest::Single(request.data(data)),
+            BatchRequest::Batch(requests) => BatchRequest::Batch(
+                requests
+                    .into_iter()
+                    .map(|request| request.data(data.clone()))
+                    .collect(),
+            ),
+        }
+    }
 }
 
+impl From<Request> for BatchRequest {
+    fn from(request: Request) -> Self {
+        BatchRequest::Single(request)
+    }
+}
+
+/// Receives a JSON object. A batch of requests is rejected.
 pub async fn receive_http_request(
     body: impl AsyncRead + Send,
 ) -> Result<Request, HttpRequestError> {
+    let data = read_body(body).await?;
+    serde_json::from_slice::<Request>(&data)
+        .map_err(|err| HttpRequestError::InvalidRequest(Box::new(err)))
+}
+
+/// Receives a JSON object, or a JSON array of objects.
+pub async fn receive_batch_http_request(
+    body: impl AsyncRead + Send,
+) -> Result<BatchRequest, HttpRequestError> {
     receive_json_body(body).await
 }
 
-pub async fn receive_json_body(body: impl AsyncRead) -> Result<Request, HttpRequestError> {
+pub async fn receive_json_body(body: impl AsyncRead) -> Result<BatchRequest, HttpRequestError> {
+    let data = read_body(body).await?;
+    BatchRequest::from_json(&data)
+}
+
+async fn read_body(body: impl AsyncRead) -> Result<Vec<u8>, HttpRequestError> {
     let mut data = Vec::new();
     pin_mut!(body);
 
     body.read_to_end(&mut data)
         .await
         .map_err(HttpRequestError::Io)?;
-    Ok(serde_json::from_slice::<Request>(&data)
-        .map_err(|err| HttpRequestError::InvalidRequest(Box::new(err)))?)
+    Ok(data)
+}
+
+/// Limits on the fields of a multipart request. `None` means unlimited.
+///
+/// By default, a request has at most 10 files of at most 10 MiB each, and its `operations` and `map` fields are
+/// at most 1 MiB each.
+#[derive(Debug, Clone, Copy)]
+pub struct MultipartOptions {
+    pub max_file_size: Option<usize>,
+    pub max_num_files: Option<usize>,
+    /// The maximum size of the `operations` and `map` fields, which are read into memory.
+    pub max_field_size: Option<usize>,
+}
+
+impl Default for MultipartOptions {
+    fn default() -> Self {
+        MultipartOptions {
+            max_file_size: Some(10 * 1024 * 1024),
+            max_num_files: Some(10),
+            max_field_size: Some(1024 * 1024),
+        }
+    }
+}
+
+/// Receives a request of the GraphQL multipart request spec, whose `operations` field is a request or a batch of
+/// requests, and whose `map` field maps the file fields to the paths of the variables where they are used.
+///
+/// https://github.com/jaydenseric/graphql-multipart-request-spec
+pub async fn receive_multipart_request(
+    body: impl AsyncRead + Send,
+    content_type: &str,
+    options: MultipartOptions,
+) -> Result<BatchRequest, HttpRequestError> {
+    let boundary = multer::parse_boundary(content_type)
+        .map_err(|err| HttpRequestError::InvalidRequest(Box::new(err)))?;
+    let stream = futures_util::stream::unfold(Box::pin(body), |mut body| async move {
+        let mut chunk = vec![0; 4096];
+        match body.read(&mut chunk).await {
+            Ok(0) => None,
+            Ok(size) => {
+                chunk.truncate(size);
+                Some((Ok(Bytes::from(chunk)), body))
+            }
+            Err(err) => Some((Err(err), body)),
+        }
+    });
+    let mut multipart = multer::Multipart::new(stream, boundary);
+
+    let mut batch_request = None;
+    let mut map = None;
+    let mut files = Vec::new();
+    let mut file_paths = Vec::new();
+    while let Some(mut field) = multipart.next_field().await.map_err(invalid_multipart)? {
+        let name = field.name().unwrap_or_default().to_string();
+        match name.as_str() {
+            "operations" => {
+                let operations = read_field(&mut field, &name, options.max_field_size).await?;
+                batch_request = Some(BatchRequest::from_json(&operations)?);
+            }
+            "map" => {
+                let map_field = read_field(&mut field, &name, options.max_field_size).await?;
+                let file_map = serde_json::from_slice::<HashMap<String, Vec<String>>>(&map_field)
+                    .map_err(|err| HttpRequestError::InvalidRequest(Box::new(err)))?;
+                if let Some(max_num_files) = options.max_num_files {
+                    if file_map.len() > max_num_files {
+                        return Err(HttpRequestError::PayloadTooLarge(format!(
+                            "the request has more than {} files",
+                            max_num_files
+                        )));
+                    }
+                }
+                map = Some(file_map);
+            }
+            _ => {
+                let paths = match map.as_mut().and_then(|map| map.remove(&name)) {
+                    Some(paths) => paths,
+                    None => continue,
+                };
+                let filename = field.file_name().unwrap_or_default().to_string();
+                let content_type = field.content_type().map(ToString::to_string);
+                let mut content =
+                    tokio::fs::File::from_std(tempfile::tempfile().map_err(HttpRequestError::Io)?);
+                let mut size = 0;
+                while let Some(chunk) = field.chunk().await.map_err(invalid_multipart)? {
+                    size += chunk.len();
+                    if let Some(max_file_size) = options.max_file_size {
+                        if size > max_file_size {
+                            return Err(HttpRequestError::PayloadTooLarge(format!(
+                                "the file \"{}\" is larger than {} bytes",
+                                filename, max_file_size
+                            )));
+                        }
+                    }
+                    content
+                        .write_all(&chunk)
+                        .await
+                        .map_err(HttpRequestError::Io)?;
+                }
+                content.flush().await.map_err(HttpRequestError::Io)?;
+                file_paths.push((files.len(), paths));
+                files.push(UploadValue {
+                    filename,
+                    content_type,
+                    content: content.into_std().await,
+                    size,
+                });
+            }
+        }
+    }
+
+    let mut batch_request = batch_request.ok_or_else(|| {
+        HttpRequestError::InvalidRequest("the operations field is missing".into())
+    })?;
+    match map {
+        Some(map) if map.is_empty() => {}
+        Some(map) => {
+            let mut missing_files = map.into_keys().collect::<Vec<_>>();
+            missing_files.sort();
+            return Err(HttpRequestError::InvalidRequest(
+                format!("the files {} are missing", missing_files.join(", ")).into(),
+            ));
+        }
+        None => {
+            return Err(HttpRequestError::InvalidRequest(
+                "the map field is missing".into(),
+            ))
+        }
+    }
+
+    let token = Upload::token();
+    let mut request_files = match &batch_request {
+        BatchRequest::Single(_) => vec![UploadedFiles::default()],
+        BatchRequest::Batch(requests) => {
+            requests.iter().map(|_| UploadedFiles::default()).collect()
+        }
+    };
+    for (index, paths) in file_paths {
+        for path in paths {
+            let upload = Upload::new(&token, index);
+            let request_index = set_upload(&mut batch_request, &path, &upload)?;
+            if let Entry::Vacant(entry) = request_files[request_index].0.entry(upload.0) {
+                entry.insert(files[index].try_clone().map_err(HttpRequestError::Io)?);
+            }
+        }
+    }
+    Ok(match batch_request {
+        BatchRequest::Single(request) => {
+            BatchRequest::Single(request.data(request_files.remove(0)))
+        }
+        BatchRequest::Batch(requests) => BatchRequest::Batch(
+            requests
+                .into_iter()
+                .zip(request_files)
+                .map(|(request, files)| request.data(files))
+                .collect(),
+        ),
+    })
+}
+
+/// Replaces the variable at `path`, e.g. `variables.file` or `0.variables.files.1` for a batch, with the upload,
+/// and returns the index of the request.
+fn set_upload(
+    batch_request: &mut BatchRequest,
+    path: &str,
+    upload: &Upload,
+) -> Result<usize, HttpRequestError> {
+    let invalid_path =
+        || HttpRequestError::InvalidRequest(format!("invalid file path \"{}\"", path).into());
+    let mut segments = path.split('.');
+    let (request_index, request) = match batch_request {
+        BatchRequest::Single(request) => (0, request),
+        BatchRequest::Batch(requests) => segments
+            .next()
+            .and_then(|segment| segment.parse::<usize>().ok())
+            .and_then(|i| requests.get_mut(i).map(|request| (i, request)))
+            .ok_or_else(invalid_path)?,
+    };
+    if segments.next() != Some("variables") {
+        return Err(invalid_path());
+    }
+    let name = segments.next().ok_or_else(invalid_path)?;
+    let mut value = request.variables.0.get_mut(name).ok_or_else(invalid_path)?;
+    for segment in segments {
+        value = match value {
+            GqlValue::Object(object) => object.get_mut(segment),
+            GqlValue::List(list) => segment.parse::<usize>().ok().and_then(|i| list.get_mut(i)),
+            _ => None,
+        }
+        .ok_or_else(invalid_path)?;
+    }
+    *value = upload.to_gql_value();
+    Ok(request_index)
+}
+
+/// Reads a field which is not a file into memory.
+async fn read_field(
+    field: &mut multer::Field<'_>,
+    name: &str,
+    max_field_size: Option<usize>,
+) -> Result<Vec<u8>, HttpRequestError> {
+    let mut bytes = Vec::new();
+    while let Some(chunk) = field.chunk().await.map_err(invalid_multipart)? {
+        bytes.extend_from_slice(&chunk);
+        if let Some(max_field_size) = max_field_size {
+            if bytes.len() > max_field_size {
+                return Err(HttpRequestError::PayloadTooLarge(format!(
+                    "the field \"{}\" is larger than {} bytes",
+                    name, max_field_size
+                )));
+            }
+        }
+    }
+    Ok(bytes)
+}
+
+fn invalid_multipart(err: multer::Error) -> HttpRequestError {
+    HttpRequestError::InvalidRequest(Box::new(err))
 }
 
 #[derive(Debug)]
 pub enum HttpRequestError {
     Io(std::io::Error),
     InvalidRequest(Box<dyn std::error::Error + Send + Sync>),
+    /// A multipart request exceeds the limits of `MultipartOptions`.
+    PayloadTooLarge(String),
 }
 
 #[cfg(test)]
 mod tests {
     use serde_json::Number;
 
-    use crate::{GqlValue, Request};
+    use crate::{BatchRequest, GqlValue, HttpRequestError, Request};
 
     #[test]
     fn test_operation_name() {
@@ -89,4 +399,40 @@ mod tests {
         assert!(req.operation_name.is_none());
         assert!(req.variables.0.is_empty());
     }
+
+    #[test]
+    fn test_batch_request() {
+        let query_doc =
+            r#"[{"query": "{ hero }"}, {"query": "{ droids }", "operationName": "droids"}]"#;
+        let req = BatchRequest::from_json(query_doc.as_bytes()).unwrap();
+        match req {
+            BatchRequest::Batch(requests) => {
+                assert_eq!(requests.len(), 2);
+                assert_eq!(requests[0].query, "{ hero }");
+                assert_eq!(requests[1].operation_name, Some("droids".to_string()));
+            }
+            BatchRequest::Single(_) => panic!("expected a batch"),
+        }
+
+        let query_doc = r#"  {"query": "{ hero }"}"#;
+        let req = BatchRequest::from_json(query_doc.as_bytes()).unwrap();
+        assert_eq!(req.into_single().unwrap().query, "{ hero }");
+
+        let query_doc = r#"[{"query": "{ hero }"}]"#;
+        let req = BatchRequest::from_json(query_doc.as_bytes()).unwrap();
+        assert!(req.into_single().is_err());
+    }
+
+    #[test]
+    fn test_batch_request_error() {
+        let query_doc = r#"[{"query": "{ hero }"}, {"query": 1}]"#;
+        let error = match BatchRequest::from_json(query_doc.as_bytes()) {
+            Err(HttpRequestError::InvalidRequest(error)) => error.to_string(),
+            _ => panic!("expected an invalid request"),
+        };
+        assert_eq!(
+            error,
+            "invalid type: integer `1`, expected a string at line 1 column 35"
+        );
+    }
 }
@@ -1,10 +1,130 @@
 use std::collections::{HashSet, LinkedList, VecDeque};
 
+use futures_util::{future::join_all, stream::FuturesUnordered, Future, StreamExt};
+
 use crate::{
-    CollectFields, Context, FieldResolver, GqlValue, ResolverResult, SelectionSetContext,
-    SelectionSetResolver,
+    error::PathSegment,
+    incremental::{drive_with, DeferredFuture, IncrementalScope, StreamDirective},
+    CollectFields, Context, FieldResolver, GqlError, GqlValue, GqlValueType, IncrementalResponse,
+    ResolverResult, SelectionSetContext, SelectionSetResolver,
 };
 
+use super::complete_value;
+
+async fn resolve_list<'a, T: SelectionSetResolver + 'a>(
+    ctx: &SelectionSetContext<'_>,
+    items: impl Iterator<Item = &'a T> + Send,
+) -> ResolverResult<GqlValue> {
+    let item_type = ctx.field_type().and_then(|ty| ty.list_item_type());
+    // The items after `initialCount` of a `@stream` list are sent after the list.
+    let initial_count = ctx
+        .stream
+        .as_ref()
+        .map_or(usize::MAX, |stream| stream.initial_count);
+    let mut items = items.enumerate();
+    // Items are resolved concurrently so that a `DataLoader` can batch the keys of every item.
+    let results = join_all(items.by_ref().take(initial_count).map(|item| {
+        let incremental = ctx.incremental.as_ref().map(IncrementalScope::child);
+        resolve_item(ctx, item_type, item, incremental)
+    }))
+    .await;
+
+    let mut values = Vec::new();
+    let mut deferred = Vec::new();
+    for (result, rest) in results {
+        values.push(result?);
+        deferred.extend(rest);
+    }
+    let value = GqlValue::List(values);
+    if let (Some(stream), Some(scope)) = (&ctx.stream, &ctx.incremental) {
+        let streamed_items = items.collect::<Vec<_>>();
+        if !streamed_items.is_empty() {
+            deferred.push(stream_items(ctx, item_type, stream, scope, streamed_items));
+        }
+    }
+    if deferred.is_empty() {
+        return Ok(value);
+    }
+    // The list is sent without its deferred parts, which are resolved after it has been sent.
+    if let Some(scope) = &ctx.incremental {
+        scope.publish(value.clone());
+    }
+    join_all(deferred).await;
+    Ok(value)
+}
+
+/// Resolves an item of a list. An item with deferred parts is returned with the future which resolves them.
+fn resolve_item<'a, T: SelectionSetResolver + 'a>(
+    ctx: &SelectionSetContext<'a>,
+    item_type: Option<&'a GqlValueType>,
+    (index, item): (usize, &'a T),
+    incremental: Option<IncrementalScope>,
+) -> impl Future<Output = (ResolverResult<GqlValue>, Option<DeferredFuture<'a>>)> + Send + 'a {
+    let mut ctx_item = ctx.clone();
+    ctx_item.path.push(PathSegment::Index(index));
+    ctx_item.stream = None;
+    ctx_item.incremental = incremental.clone();
+    let future = async move {
+        let value = item.resolve_selection_set(&ctx_item).await;
+        complete_value(&ctx_item, item_type, value, || {
+            GqlError::new(
+                "Cannot return null for non-nullable list item",
+                ctx_item.field_position,
+            )
+        })
+    };
+    async move {
+        match incremental {
+            Some(scope) => scope.resolve(future, |value| value).await,
+            None => (future.await, None),
+        }
+    }
+}
+
+/// Sends the items after `initialCount` of a `@stream` list one at a time, once the list has been sent.
+fn stream_items<'a, T: SelectionSetResolver + 'a>(
+    ctx: &SelectionSetContext<'a>,
+    item_type: Option<&'a GqlValueType>,
+    stream: &StreamDirective,
+    scope: &IncrementalScope,
+    items: Vec<(usize, &'a T)>,
+) -> DeferredFuture<'a> {
+    let ctx = ctx.clone();
+    let label = stream.label.clone();
+    let scope = scope.clone();
+    Box::pin(async move {
+        scope.payload.wait_sent().await;
+        let mut deferred = FuturesUnordered::new();
+        for item in items {
+            let mut path = ctx.path.clone();
+            path.push(PathSegment::Index(item.0));
+            let incremental = scope.deferred();
+            let resolve = resolve_item(&ctx, item_type, item, Some(incremental.clone()));
+            let (result, rest) = drive_with(resolve, &mut deferred).await;
+            let is_error = result.is_err();
+            let items = match result {
+                Ok(value) => Some(vec![value]),
+                Err(error) => {
+                    incremental.payload.errors.lock().unwrap().push(error);
+                    None
+                }
+            };
+            incremental.send(IncrementalResponse {
+                items,
+                path: Some(path),
+                label: label.clone(),
+                ..Default::default()
+            });
+            deferred.extend(rest);
+            // The null of a non-null item ends the stream.
+            if is_error {
+                break;
+            }
+        }
+        while deferred.next().await.is_some() {}
+    })
+}
+
 #[async_trait::async_trait]
 impl<T: FieldResolver, const N: usize> FieldResolver for [T; N] {
     async fn resolve_field(&self, ctx: &Context<'_>) -> ResolverResult<Option<GqlValue>> {
@@ -30,12 +150,7 @@ impl<T: SelectionSetResolver, const N: usize> SelectionSetResolver for [T; N] {
         &self,
         ctx: &SelectionSetContext<'_>,
     ) -> ResolverResult<GqlValue> {
-        let mut result = Vec::new();
-        for value in self {
-            let v = value.resolve_selection_set(ctx).await?;
-            result.push(v);
-        }
-        Ok(GqlValue::List(result))
+        resolve_list(ctx, self.iter()).await
     }
 }
 
@@ -63,12 +178,7 @@ impl<T: SelectionSetResolver> SelectionSetResolver for HashSet<T> {
         &self,
         ctx: &SelectionSetContext<'_>,
     ) -> ResolverResult<GqlValue> {
-        let mut result = Vec::new();
-        for value in self {
-            let v = value.resolve_selection_set(ctx).await?;
-            result.push(v);
-        }
-        Ok(GqlValue::List(result))
+        resolve_list(ctx, self.iter()).await
     }
 }
 
@@ -96,12 +206,7 @@ impl<'a, T: SelectionSetResolver + 'a> SelectionSetResolver for &'a [T] {
         &self,
         ctx: &SelectionSetContext<'_>,
     ) -> ResolverResult<GqlValue> {
-        let mut result = Vec::new();
-        for value in self.iter() {
-            let v = value.resolve_selection_set(ctx).await?;
-            result.push(v);
-        }
-        Ok(GqlValue::List(result))
+        resolve_list(ctx, self.iter()).await
     }
 }
 
@@ -129,12 +234,7 @@ impl<T: SelectionSetResolver> SelectionSetResolver for VecDeque<T> {
         &self,
         ctx: &SelectionSetContext<'_>,
     ) -> ResolverResult<GqlValue> {
-        let mut result = Vec::new();
-        for value in self.iter() {
-            let v = value.resolve_selection_set(ctx).await?;
-            result.push(v);
-        }
-        Ok(GqlValue::List(result))
+        resolve_list(ctx, self.iter()).await
     }
 }
 
@@ -162,12 +262,7 @@ impl<T: SelectionSetResolver> SelectionSetResolver for LinkedList<T> {
         &self,
         ctx: &SelectionSetContext<'_>,
     ) -> ResolverResult<GqlValue> {
-        let mut result = Vec::new();
-        for value in self.iter() {
-            let v = value.resolve_selection_set(ctx).await?;
-            result.push(v);
-        }
-        Ok(GqlValue::List(result))
+        resolve_list(ctx, self.iter()).await
     }
 }
 
@@ -195,11 +290,6 @@ impl<T: SelectionSetResolver> SelectionSetResolver for Vec<T> {
         &self,
         ctx: &SelectionSetContext<'_>,
     ) -> ResolverResult<GqlValue> {
-        let mut result = Vec::new();
-        for value in self.iter() {
-            let v = value.resolve_selection_set(ctx).await?;
-            result.push(v);
-        }
-        Ok(GqlValue::List(result))
+        resolve_list(ctx, self.iter()).await
     }
 }
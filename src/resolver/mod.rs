@@ -6,18 +6,23 @@ mod object;
 mod optional;
 mod string;
 
-use std::{collections::BTreeMap, sync::Arc};
+use std::sync::Arc;
 
 use async_trait::async_trait;
 use futures_util::{
-    future::{try_join_all, BoxFuture},
-    FutureExt,
+    future::{join_all, try_join_all, BoxFuture},
+    stream::BoxStream,
+    Future, FutureExt,
 };
-use graphql_parser::query::{Selection, TypeCondition};
+use graphql_parser::query::{Directive, Selection, SelectionSet, TypeCondition};
+use indexmap::IndexMap;
 
 use crate::{
-    context::{Context, SelectionSetContext},
-    GqlDirective, GqlError, GqlValue, ResolverResult, TypeDefinition,
+    context::{Context, ExecutionContext, SelectionSetContext},
+    error::PathSegment,
+    incremental::{DeferredFuture, IncrementalScope, PayloadState},
+    telemetry, GqlDirective, GqlError, GqlValue, GqlValueType, IncrementalResponse, ResolverResult,
+    TypeDefinition,
 };
 
 #[async_trait]
@@ -28,6 +33,16 @@ pub trait SelectionSetResolver: CollectFields {
     ) -> ResolverResult<GqlValue>;
 }
 
+pub type SubscriptionStream<'a> = BoxStream<'a, ResolverResult<GqlValue>>;
+
+#[async_trait]
+pub trait SubscriptionResolver: SelectionSetResolver {
+    async fn resolve_subscription_field<'a>(
+        &'a self,
+        ctx: &'a Context<'_>,
+    ) -> ResolverResult<Option<SubscriptionStream<'a>>>;
+}
+
 #[async_trait]
 pub trait CollectFields: FieldResolver {
     fn introspection_type_name(&self) -> String {
@@ -107,29 +122,30 @@ async fn resolve_selection<'a, 'ctx: 'a, T: CollectFields + SelectionSetResolver
     root_type: &'a T,
     parallel: bool,
 ) -> ResolverResult<GqlValue> {
-    let mut fields = Fields(Vec::new());
+    let mut fields = Fields::default();
     fields.collect_fields(ctx, root_type)?;
 
-    let res = if parallel {
-        try_join_all(fields.0).await?
-    } else {
-        let mut results = Vec::new();
-        for resolver in fields.0 {
-            results.push(resolver.await?);
+    if !parallel {
+        let mut gql_obj_map = IndexMap::new();
+        for field in fields.fields {
+            build_gql_object(&mut gql_obj_map, field.future.await?);
         }
-        results
-    };
-
-    let mut gql_obj_map = BTreeMap::new();
-
-    for value in res {
-        build_gql_object(&mut gql_obj_map, value);
+        return Ok(GqlValue::Object(gql_obj_map));
     }
 
-    Ok(GqlValue::Object(gql_obj_map))
+    let (value, deferred) = fields.resolve().await?;
+    if deferred.is_empty() {
+        return Ok(value);
+    }
+    // The value is sent without its deferred parts, which are resolved after it has been sent.
+    if let Some(scope) = &ctx.incremental {
+        scope.publish(value.clone());
+    }
+    join_all(deferred).await;
+    Ok(value)
 }
 
-fn build_gql_object(target_obj: &mut BTreeMap<String, GqlValue>, gql_value: (String, GqlValue)) {
+fn build_gql_object(target_obj: &mut IndexMap<String, GqlValue>, gql_value: (String, GqlValue)) {
     let (field_name, value) = gql_value;
     if let Some(prev_value) = target_obj.get_mut(&field_name) {
         match prev_value {
@@ -160,10 +176,120 @@ fn build_gql_object(target_obj: &mut BTreeMap<String, GqlValue>, gql_value: (Str
     }
 }
 
+/// Records the error of a nullable value and resolves it to null.
+/// The error of a non-null value is returned so that the null propagates to the parent.
+/// Resolves a field in its span through the `resolve_field` extension hook, and completes its value.
+async fn resolve_field_value(
+    ctx_field: &Context<'_>,
+    type_name: &str,
+    resolve_fut: impl Future<Output = ResolverResult<Option<GqlValue>>> + Send,
+) -> ResolverResult<GqlValue> {
+    let field = ctx_field.item;
+    let field_type = ctx_field
+        .field_definition
+        .map(|field_def| &field_def.meta_type);
+    let result = telemetry::resolve_in_span(
+        telemetry::resolve_span(type_name, &field.name, &ctx_field.path),
+        ctx_field
+            .extensions
+            .resolve_field(ctx_field, type_name, resolve_fut),
+    )
+    .await
+    .map(Option::unwrap_or_default);
+    complete_value(ctx_field, field_type, result, || {
+        GqlError::new(
+            format!(
+                "Cannot return null for non-nullable field {}.{}",
+                type_name, field.name
+            ),
+            Some(field.position),
+        )
+    })
+}
+
+pub(crate) fn complete_value<T>(
+    ctx: &ExecutionContext<'_, T>,
+    value_type: Option<&GqlValueType>,
+    result: ResolverResult<GqlValue>,
+    null_error: impl FnOnce() -> GqlError,
+) -> ResolverResult<GqlValue> {
+    let is_non_null = value_type.map_or(false, GqlValueType::is_non_null);
+    let (mut error, is_recorded) = match result {
+        Ok(GqlValue::Null) if is_non_null => (null_error(), false),
+        Ok(value) => return Ok(value),
+        // The errors of resolvers are recorded in their spans.
+        Err(error) => (error, true),
+    };
+    if error.path.is_empty() {
+        error.path = ctx.path.clone();
+    }
+    if !is_recorded {
+        telemetry::record_error(&error);
+    }
+
+    if is_non_null {
+        Err(error)
+    } else {
+        // The error has been recorded as a tracing event above or in the span of its resolver,
+        // so it is not passed to `add_error`.
+        ctx.push_error(error);
+        Ok(GqlValue::Null)
+    }
+}
+
 pub type ResolveFieldFuture<'a> = BoxFuture<'a, ResolverResult<(String, GqlValue)>>;
-pub struct Fields<'a>(Vec<ResolveFieldFuture<'a>>);
+
+/// The fields of a selection set. The fields of deferred fragments are collected separately.
+#[derive(Default)]
+pub struct Fields<'a> {
+    fields: Vec<CollectedField<'a>>,
+    deferred: Vec<DeferredFragment<'a>>,
+}
+
+struct CollectedField<'a> {
+    response_name: String,
+    incremental: Option<IncrementalScope>,
+    future: ResolveFieldFuture<'a>,
+}
+
+/// A `@defer` fragment, which is sent in its own payload after the payload of its parent.
+struct DeferredFragment<'a> {
+    path: Vec<PathSegment>,
+    label: Option<String>,
+    parent: Arc<PayloadState>,
+    incremental: IncrementalScope,
+    fields: Fields<'a>,
+}
 
 impl<'a> Fields<'a> {
+    /// Resolves the fields concurrently. The value is returned with the futures which resolve its deferred parts.
+    fn resolve(self) -> BoxFuture<'a, ResolverResult<(GqlValue, Vec<DeferredFuture<'a>>)>> {
+        Box::pin(async move {
+            let results = try_join_all(self.fields.into_iter().map(|field| async move {
+                match field.incremental {
+                    Some(scope) => {
+                        let response_name = field.response_name;
+                        let (result, rest) = scope
+                            .resolve(field.future, |value| (response_name, value))
+                            .await;
+                        result.map(|value| (value, rest))
+                    }
+                    None => field.future.await.map(|value| (value, None)),
+                }
+            }))
+            .await?;
+
+            let mut gql_obj_map = IndexMap::new();
+            let mut deferred = Vec::new();
+            for (value, rest) in results {
+                build_gql_object(&mut gql_obj_map, value);
+                deferred.extend(rest);
+            }
+            deferred.extend(self.deferred.into_iter().map(DeferredFragment::resolve));
+            Ok((GqlValue::Object(gql_obj_map), deferred))
+        })
+    }
+
     pub fn collect_fields<'ctx: 'a, T: CollectFields + ?Sized>(
         &mut self,
         ctx: &SelectionSetContext<'ctx>,
@@ -175,25 +301,44 @@ impl<'a> Fields<'a> {
                     if ctx.is_skip(&field.directives) {
                         continue;
                     }
+                    let response_name = field.alias.clone().unwrap_or_else(|| field.name.clone());
                     if field.name == "__typename" {
-                        ctx.with_field(field);
-                        let field_name = field.name.clone();
                         let type_name = root_type.introspection_type_name();
-
-                        self.0.push(Box::pin(async move {
-                            Ok((field_name, GqlValue::String(type_name)))
-                        }));
+                        let value = (response_name.clone(), GqlValue::String(type_name));
+                        self.fields.push(CollectedField {
+                            response_name,
+                            incremental: None,
+                            future: Box::pin(async move { Ok(value) }),
+                        });
                         continue;
                     }
+                    let incremental = ctx.incremental.as_ref().map(IncrementalScope::child);
+                    // `@stream` is ignored unless the operation is executed incrementally.
+                    let stream = incremental
+                        .as_ref()
+                        .and_then(|_| ctx.stream_directive(&field.directives));
 
-                    self.0.push(Box::pin({
+                    let future = Box::pin({
                         let ctx = ctx.clone();
+                        let incremental = incremental.clone();
                         async move {
-                            let ctx_field = &ctx.with_field(field);
-                            let field_name = ctx_field.item.name.clone();
+                            let field_name = field.name.clone();
+                            let response_name =
+                                field.alias.clone().unwrap_or_else(|| field.name.clone());
                             let type_name = T::type_name();
                             let empty_vec = vec![];
 
+                            let field_definition = ctx
+                                .schema
+                                .type_definitions
+                                .get(&type_name)
+                                .and_then(|ty_def| ty_def.get_field_by_name(&field_name));
+                            let mut ctx_field = ctx.with_field(field);
+                            ctx_field.field_definition = field_definition;
+                            ctx_field.incremental = incremental;
+                            ctx_field.stream = stream;
+                            let ctx_field = &ctx_field;
+
                             let query_directives = &field.directives;
                             let schema_ty_directives = ctx
                                 .schema
@@ -220,13 +365,9 @@ impl<'a> Fields<'a> {
                                 && schema_impl_interface_directives.is_empty()
                                 && query_directives.is_empty()
                             {
-                                Ok((
-                                    field_name,
-                                    root_type
-                                        .resolve_field(ctx_field)
-                                        .await?
-                                        .unwrap_or_default(),
-                                ))
+                                let value =
+                                    resolve_field_value(ctx_field, &type_name, resolve_fut).await?;
+                                Ok((response_name, value))
                             } else {
                                 let mut resolve_fut = resolve_fut.boxed();
 
@@ -306,12 +447,23 @@ impl<'a> Fields<'a> {
                                         })
                                     }
                                 }
-                                Ok((field_name, resolve_fut.await?.unwrap_or_default()))
+                                let value =
+                                    resolve_field_value(ctx_field, &type_name, resolve_fut).await?;
+                                Ok((response_name, value))
                             }
                         }
-                    }))
+                    });
+                    self.fields.push(CollectedField {
+                        response_name,
+                        incremental,
+                        future,
+                    });
                 }
                 Selection::FragmentSpread(fragment_spread) => {
+                    if ctx.is_skip(&fragment_spread.directives) {
+                        continue;
+                    }
+
                     let operation_fragment = ctx
                         .operation
                         .fragment_definitions
@@ -331,9 +483,13 @@ impl<'a> Fields<'a> {
                         &root_type.introspection_type_name(),
                         Some(&fragment_def.type_condition),
                     ) {
-                        root_type.collect_all_fields(
-                            &ctx.with_selection_set(&fragment_def.selection_set),
-                            self,
+                        self.collect_fragment(
+                            ctx,
+                            &fragment_spread.directives,
+                            &fragment_def.selection_set,
+                            |ctx_fragment, fields| {
+                                root_type.collect_all_fields(ctx_fragment, fields)
+                            },
                         )?;
                     }
                 }
@@ -342,32 +498,89 @@ impl<'a> Fields<'a> {
                         continue;
                     }
 
-                    if is_fragment_condition(
+                    let is_condition = is_fragment_condition(
                         ctx,
                         &root_type.introspection_type_name(),
                         inline_fragment.type_condition.as_ref(),
-                    ) {
-                        root_type.collect_all_fields(
-                            &ctx.with_selection_set(&inline_fragment.selection_set),
-                            self,
-                        )?;
-                    } else if inline_fragment.type_condition.is_none() {
-                        self.collect_fields(
-                            &ctx.with_selection_set(&inline_fragment.selection_set),
-                            root_type,
-                        )?;
+                    );
+                    if !is_condition && inline_fragment.type_condition.is_some() {
+                        continue;
                     }
+                    self.collect_fragment(
+                        ctx,
+                        &inline_fragment.directives,
+                        &inline_fragment.selection_set,
+                        |ctx_fragment, fields| {
+                            if is_condition {
+                                root_type.collect_all_fields(ctx_fragment, fields)
+                            } else {
+                                fields.collect_fields(ctx_fragment, root_type)
+                            }
+                        },
+                    )?;
                 }
             }
         }
         Ok(())
     }
+
+    /// Collects the fields of a fragment. The fields of a `@defer` fragment are collected into a deferred fragment
+    /// when the operation is executed incrementally.
+    fn collect_fragment<'ctx: 'a>(
+        &mut self,
+        ctx: &SelectionSetContext<'ctx>,
+        directives: &'ctx [Directive<'static, String>],
+        selection_set: &'ctx SelectionSet<'static, String>,
+        collect: impl FnOnce(&SelectionSetContext<'ctx>, &mut Fields<'a>) -> ResolverResult<()>,
+    ) -> ResolverResult<()> {
+        let mut ctx_fragment = ctx.with_selection_set(selection_set);
+        let (defer, scope) = match (ctx.defer_directive(directives), &ctx.incremental) {
+            (Some(defer), Some(scope)) => (defer, scope),
+            _ => return collect(&ctx_fragment, self),
+        };
+
+        let incremental = scope.deferred();
+        ctx_fragment.incremental = Some(incremental.clone());
+        let mut fields = Fields::default();
+        collect(&ctx_fragment, &mut fields)?;
+        self.deferred.push(DeferredFragment {
+            path: ctx.path.clone(),
+            label: defer.label,
+            parent: scope.payload.clone(),
+            incremental,
+            fields,
+        });
+        Ok(())
+    }
 }
 
-fn is_fragment_condition<'a, 'ctx: 'a>(
-    ctx: &SelectionSetContext<'ctx>,
+impl<'a> DeferredFragment<'a> {
+    /// Resolves the fragment once the payload of its parent has been sent, and sends its payload.
+    fn resolve(self) -> DeferredFuture<'a> {
+        Box::pin(async move {
+            self.parent.wait_sent().await;
+            let (data, deferred) = match self.fields.resolve().await {
+                Ok(resolved) => resolved,
+                Err(error) => {
+                    self.incremental.payload.errors.lock().unwrap().push(error);
+                    (GqlValue::Null, Vec::new())
+                }
+            };
+            self.incremental.send(IncrementalResponse {
+                data: Some(data),
+                path: Some(self.path),
+                label: self.label,
+                ..Default::default()
+            });
+            join_all(deferred).await;
+        })
+    }
+}
+
+fn is_fragment_condition(
+    ctx: &SelectionSetContext<'_>,
     type_name: &str,
-    ty_cond: Option<&TypeCondition<'a, String>>,
+    ty_cond: Option<&TypeCondition<'_, String>>,
 ) -> bool {
     match ty_cond {
         Some(cond) => {
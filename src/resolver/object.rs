@@ -1,5 +1,6 @@
 use std::collections::{BTreeMap, HashMap};
 
+use indexmap::IndexMap;
 use serde::Serialize;
 
 use crate::{
@@ -14,7 +15,7 @@ where
     V: Serialize + Send + Sync,
 {
     async fn resolve_field(&self, _ctx: &Context<'_>) -> ResolverResult<Option<GqlValue>> {
-        let mut map = BTreeMap::new();
+        let mut map = IndexMap::new();
         for (name, v) in self {
             map.insert(
                 name.to_string(),
@@ -45,7 +46,7 @@ where
         &self,
         _ctx: &SelectionSetContext<'_>,
     ) -> ResolverResult<GqlValue> {
-        let mut map = BTreeMap::new();
+        let mut map = IndexMap::new();
         for (name, v) in self {
             map.insert(
                 name.to_string(),
@@ -63,7 +64,7 @@ where
     V: Serialize + Send + Sync,
 {
     async fn resolve_field(&self, _ctx: &Context<'_>) -> ResolverResult<Option<GqlValue>> {
-        let mut map = BTreeMap::new();
+        let mut map = IndexMap::new();
         for (name, v) in self {
             map.insert(
                 name.to_string(),
@@ -94,7 +95,7 @@ where
         &self,
         _ctx: &SelectionSetContext<'_>,
     ) -> ResolverResult<GqlValue> {
-        let mut map = BTreeMap::new();
+        let mut map = IndexMap::new();
         for (name, v) in self {
             map.insert(
                 name.to_string(),
@@ -1,7 +1,8 @@
 use http::HeaderMap;
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
-use crate::{error::GqlError, GqlValue};
+use crate::{error::GqlError, GqlValue, PathSegment};
 
 #[derive(Serialize, Deserialize, Default, Debug)]
 pub struct Response {
@@ -9,6 +10,8 @@ pub struct Response {
     pub data: GqlValue,
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub errors: Vec<GqlError>,
+    #[serde(skip_serializing_if = "IndexMap::is_empty", default)]
+    pub extensions: IndexMap<String, GqlValue>,
     #[serde(skip)]
     pub http_headers: HeaderMap<String>,
 }
@@ -18,6 +21,7 @@ impl Response {
         Self {
             data: data.into(),
             errors: vec![],
+            extensions: Default::default(),
             http_headers: Default::default(),
         }
     }
@@ -26,6 +30,7 @@ impl Response {
         Self {
             errors,
             data: Default::default(),
+            extensions: Default::default(),
             http_headers: Default::default(),
         }
     }
@@ -34,6 +39,7 @@ impl Response {
         Self {
             data: data.into(),
             errors,
+            extensions: Default::default(),
             http_headers: Default::default(),
         }
     }
@@ -47,10 +53,70 @@ impl Response {
     }
 }
 
+/// A payload of incremental delivery. The initial payload has `data`, and the following payloads have the `data` of
+/// a `@defer` fragment or the `items` of a `@stream` list at `path`. `hasNext` is false for the last payload.
+#[derive(Serialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct IncrementalResponse {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<GqlValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub items: Option<Vec<GqlValue>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<GqlError>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<Vec<PathSegment>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    pub extensions: IndexMap<String, GqlValue>,
+    pub has_next: bool,
+}
+
+impl IncrementalResponse {
+    /// The initial payload.
+    pub fn initial(response: Response, has_next: bool) -> Self {
+        IncrementalResponse {
+            data: Some(response.data),
+            errors: response.errors,
+            extensions: response.extensions,
+            has_next,
+            ..Default::default()
+        }
+    }
+}
+
+/// The response to a `BatchRequest`. A batch of responses is serialized as a JSON array.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(untagged)]
+#[allow(clippy::large_enum_variant)]
+pub enum BatchResponse {
+    Batch(Vec<Response>),
+    Single(Response),
+}
+
+impl BatchResponse {
+    pub fn is_ok(&self) -> bool {
+        match self {
+            BatchResponse::Single(response) => response.is_ok(),
+            BatchResponse::Batch(responses) => responses.iter().all(Response::is_ok),
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        !self.is_ok()
+    }
+}
+
+impl From<Response> for BatchResponse {
+    fn from(response: Response) -> Self {
+        BatchResponse::Single(response)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use std::collections::BTreeMap;
-
+    use indexmap::IndexMap;
     use serde_json::Number;
 
     use crate::{GqlValue, Response};
@@ -60,7 +126,7 @@ mod tests {
         let boolean = Response::new(GqlValue::Boolean(true));
         assert_eq!(serde_json::to_string(&boolean).unwrap(), r#"{"data":true}"#);
 
-        let map = BTreeMap::from([
+        let map = IndexMap::from([
             ("a".to_string(), GqlValue::Number(Number::from(1))),
             ("b".to_string(), GqlValue::Number(Number::from(2))),
         ]);
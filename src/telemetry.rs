@@ -0,0 +1,123 @@
+//! Spans and events of the `tracing` crate. Every function is a no-op unless the `tracing` feature is enabled.
+
+use std::future::Future;
+
+use crate::{GqlError, OperationType, PathSegment};
+
+#[cfg(feature = "tracing")]
+use tracing::{Instrument, Span};
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct Span;
+
+/// The span of a request. The operation type is recorded once the operation is known.
+#[cfg(feature = "tracing")]
+pub(crate) fn request_span(operation_name: Option<&str>) -> Span {
+    tracing::info_span!(
+        "request",
+        operation_name = operation_name.unwrap_or_default(),
+        operation_type = tracing::field::Empty,
+    )
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn request_span(_operation_name: Option<&str>) -> Span {
+    Span
+}
+
+#[cfg(feature = "tracing")]
+pub(crate) fn record_operation_type(operation_type: &OperationType) {
+    Span::current().record("operation_type", &operation_type.to_string().as_str());
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn record_operation_type(_operation_type: &OperationType) {}
+
+#[cfg(feature = "tracing")]
+pub(crate) fn parse_span() -> Span {
+    tracing::info_span!("parse")
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn parse_span() -> Span {
+    Span
+}
+
+#[cfg(feature = "tracing")]
+pub(crate) fn validate_span() -> Span {
+    tracing::info_span!("validate")
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn validate_span() -> Span {
+    Span
+}
+
+#[cfg(feature = "tracing")]
+pub(crate) fn execute_span() -> Span {
+    tracing::info_span!("execute")
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn execute_span() -> Span {
+    Span
+}
+
+/// The span of a resolver invocation. The path is joined with dots, e.g. `persons.0.name`.
+#[cfg(feature = "tracing")]
+pub(crate) fn resolve_span(parent_type: &str, field_name: &str, path: &[PathSegment]) -> Span {
+    tracing::info_span!(
+        "resolve",
+        parent_type = parent_type,
+        field_name = field_name,
+        path = join_path(path).as_str(),
+    )
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn resolve_span(_parent_type: &str, _field_name: &str, _path: &[PathSegment]) -> Span {
+    Span
+}
+
+/// Records the error as an event of the current span. The path of the error is the path of the span.
+#[cfg(feature = "tracing")]
+pub(crate) fn record_error(error: &GqlError) {
+    tracing::error!("{}", error.message);
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn record_error(_error: &GqlError) {}
+
+#[cfg(feature = "tracing")]
+fn join_path(path: &[PathSegment]) -> String {
+    path.iter()
+        .map(|segment| segment.to_string())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Runs `fut` in `span`.
+#[cfg(feature = "tracing")]
+pub(crate) fn in_span<F: Future>(span: Span, fut: F) -> impl Future<Output = F::Output> {
+    fut.instrument(span)
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn in_span<F: Future>(_span: Span, fut: F) -> F {
+    fut
+}
+
+/// Runs the resolver `fut` in `span` and records its error as an event of the span.
+pub(crate) async fn resolve_in_span<T>(
+    span: Span,
+    fut: impl Future<Output = Result<T, GqlError>>,
+) -> Result<T, GqlError> {
+    in_span(span, async {
+        let result = fut.await;
+        if let Err(error) = &result {
+            record_error(error);
+        }
+        result
+    })
+    .await
+}
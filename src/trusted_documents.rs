@@ -0,0 +1,160 @@
+use std::collections::{HashMap, HashSet};
+
+use graphql_parser::query::{Definition, OperationDefinition};
+
+use crate::{
+    operation::build_operation,
+    types::schema::Schema,
+    validation::{apply_validation, ValidationLimits},
+    GqlError, Request,
+};
+
+type BypassPredicate = Box<dyn Fn(&Request) -> bool + Send + Sync>;
+
+/// The documents which are allowed to be executed, keyed by document ID.
+///
+/// Requests refer to a document with `documentId`. A `query` is rejected unless it is one of the documents
+/// or the bypass predicate accepts the request.
+pub struct TrustedDocuments {
+    documents: HashMap<String, String>,
+    queries: HashSet<String>,
+    bypass: Option<BypassPredicate>,
+}
+
+impl TrustedDocuments {
+    pub fn new(documents: HashMap<String, String>) -> Self {
+        TrustedDocuments {
+            queries: documents.values().cloned().collect(),
+            documents,
+            bypass: None,
+        }
+    }
+
+    /// Reads a JSON manifest which maps document IDs to documents, e.g. `{"id1": "query GetUser { user { name } }"}`.
+    pub fn from_manifest(manifest: &str) -> Result<Self, GqlError> {
+        let documents =
+            serde_json::from_str::<HashMap<String, String>>(manifest).map_err(|err| {
+                GqlError::new(format!("Invalid trusted documents manifest: {}", err), None)
+            })?;
+        Ok(TrustedDocuments::new(documents))
+    }
+
+    /// Allows a request with any `query` if `bypass` returns true, e.g. for requests from internal tools.
+    pub fn bypass(mut self, bypass: impl Fn(&Request) -> bool + Send + Sync + 'static) -> Self {
+        self.bypass = Some(Box::new(bypass));
+        self
+    }
+
+    /// Validates every operation of every document against the schema.
+    pub(crate) fn validate(&self, schema: &Schema) -> Result<(), GqlError> {
+        for (id, document) in &self.documents {
+            let invalid = |message: &str| {
+                GqlError::new(
+                    format!("Trusted document \"{}\" is invalid: {}", id, message),
+                    None,
+                )
+            };
+            let doc = graphql_parser::parse_query::<String>(document)
+                .map_err(|_| invalid("failed to parse query"))?
+                .into_static();
+
+            let operation_names = doc
+                .definitions
+                .iter()
+                .filter_map(|definition| match definition {
+                    Definition::Operation(OperationDefinition::SelectionSet(_)) => Some(None),
+                    Definition::Operation(OperationDefinition::Query(query)) => {
+                        Some(query.name.clone())
+                    }
+                    Definition::Operation(OperationDefinition::Mutation(mutation)) => {
+                        Some(mutation.name.clone())
+                    }
+                    Definition::Operation(OperationDefinition::Subscription(subscription)) => {
+                        Some(subscription.name.clone())
+                    }
+                    Definition::Fragment(_) => None,
+                })
+                .collect::<Vec<_>>();
+            // An anonymous operation could not be selected by a request, so it must be the only operation.
+            if operation_names.len() > 1 && operation_names.iter().any(Option::is_none) {
+                return Err(invalid(
+                    "an anonymous operation must be the only operation in the document",
+                ));
+            }
+            let operation_names = if operation_names.len() > 1 {
+                operation_names
+            } else {
+                vec![None]
+            };
+
+            // The document is parsed once, and each of its operations is validated.
+            let operations = operation_names
+                .iter()
+                .map(|operation_name| {
+                    build_operation(
+                        &doc,
+                        operation_name.clone(),
+                        Default::default(),
+                        Default::default(),
+                    )
+                    .map_err(|err| invalid(&err.message))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            for (operation_name, operation) in operation_names.iter().zip(&operations) {
+                apply_validation(
+                    schema,
+                    &doc,
+                    None,
+                    operation,
+                    operation_name.as_deref(),
+                    &ValidationLimits::default(),
+                )
+                .map_err(|errors| {
+                    let messages = errors
+                        .into_iter()
+                        .map(|err| err.message)
+                        .collect::<Vec<_>>();
+                    invalid(&messages.join(" "))
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Replaces the `documentId` of a request with its document, and rejects queries which are not trusted.
+pub(crate) fn resolve_trusted_document(
+    trusted_documents: Option<&TrustedDocuments>,
+    mut request: Request,
+) -> Result<Request, GqlError> {
+    let trusted_documents = match trusted_documents {
+        Some(trusted_documents) => trusted_documents,
+        None if request.document_id.is_some() => {
+            return Err(GqlError::new("Document IDs are not supported", None))
+        }
+        None => return Ok(request),
+    };
+
+    if let Some(id) = &request.document_id {
+        match trusted_documents.documents.get(id) {
+            Some(document) => request.query = document.clone(),
+            None => {
+                return Err(GqlError::new(
+                    format!("Unknown document ID \"{}\"", id),
+                    None,
+                ))
+            }
+        }
+        return Ok(request);
+    }
+
+    let bypass = matches!(&trusted_documents.bypass, Some(bypass) if bypass(&request));
+    if bypass || trusted_documents.queries.contains(&request.query) {
+        Ok(request)
+    } else {
+        Err(GqlError::new(
+            "Only trusted documents can be executed",
+            None,
+        ))
+    }
+}
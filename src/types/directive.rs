@@ -112,4 +112,102 @@ impl DirectiveDefinition {
             ],
         }
     }
+
+    pub fn cost_directive() -> Self {
+        DirectiveDefinition {
+            position: Pos::default(),
+            name: "cost".to_string(),
+            description: None,
+            arguments: vec![
+                InputValueType {
+                    name: "weight".to_string(),
+                    description: None,
+                    position: Pos::default(),
+                    meta_type: GqlValueType::NamedType("Int".to_string()),
+                    default_value: None,
+                    directives: Default::default(),
+                },
+                InputValueType {
+                    name: "multipliers".to_string(),
+                    description: None,
+                    position: Pos::default(),
+                    meta_type: GqlValueType::ListType(Box::new(GqlValueType::NamedType(
+                        "String".to_string(),
+                    ))),
+                    default_value: None,
+                    directives: Default::default(),
+                },
+            ],
+            locations: vec![DirectiveLocation::FieldDefinition],
+        }
+    }
+
+    pub fn defer_directive() -> Self {
+        DirectiveDefinition {
+            position: Pos::default(),
+            name: "defer".to_string(),
+            description: None,
+            arguments: vec![
+                InputValueType {
+                    name: "if".to_string(),
+                    description: None,
+                    position: Pos::default(),
+                    meta_type: GqlValueType::NonNullType(Box::new(GqlValueType::NamedType(
+                        "Boolean".to_string(),
+                    ))),
+                    default_value: Some(GqlValue::Boolean(true)),
+                    directives: Default::default(),
+                },
+                InputValueType {
+                    name: "label".to_string(),
+                    description: None,
+                    position: Pos::default(),
+                    meta_type: GqlValueType::NamedType("String".to_string()),
+                    default_value: None,
+                    directives: Default::default(),
+                },
+            ],
+            locations: vec![
+                DirectiveLocation::FragmentSpread,
+                DirectiveLocation::InlineFragment,
+            ],
+        }
+    }
+
+    pub fn stream_directive() -> Self {
+        DirectiveDefinition {
+            position: Pos::default(),
+            name: "stream".to_string(),
+            description: None,
+            arguments: vec![
+                InputValueType {
+                    name: "if".to_string(),
+                    description: None,
+                    position: Pos::default(),
+                    meta_type: GqlValueType::NonNullType(Box::new(GqlValueType::NamedType(
+                        "Boolean".to_string(),
+                    ))),
+                    default_value: Some(GqlValue::Boolean(true)),
+                    directives: Default::default(),
+                },
+                InputValueType {
+                    name: "label".to_string(),
+                    description: None,
+                    position: Pos::default(),
+                    meta_type: GqlValueType::NamedType("String".to_string()),
+                    default_value: None,
+                    directives: Default::default(),
+                },
+                InputValueType {
+                    name: "initialCount".to_string(),
+                    description: None,
+                    position: Pos::default(),
+                    meta_type: GqlValueType::NamedType("Int".to_string()),
+                    default_value: Some(GqlValue::Number(0.into())),
+                    directives: Default::default(),
+                },
+            ],
+            locations: vec![DirectiveLocation::Field],
+        }
+    }
 }
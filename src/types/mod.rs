@@ -10,6 +10,7 @@ mod object;
 mod scalar;
 mod type_definition;
 mod union_type;
+mod upload;
 pub mod value;
 mod value_type;
 
@@ -32,3 +33,5 @@ pub use input_object::InputObjectType;
 pub use interface::InterfaceType;
 pub use object::ObjectType;
 pub use union_type::UnionType;
+pub use upload::{Upload, UploadValue};
+pub(crate) use upload::{UploadedFiles, UPLOAD_PREFIX};
@@ -85,6 +85,12 @@ pub fn build_schema(
         "deprecated".to_string(),
         DirectiveDefinition::deprecated_directive(),
     );
+    directives.insert("cost".to_string(), DirectiveDefinition::cost_directive());
+    directives.insert("defer".to_string(), DirectiveDefinition::defer_directive());
+    directives.insert(
+        "stream".to_string(),
+        DirectiveDefinition::stream_directive(),
+    );
 
     let mut definitions = schema_documents.to_vec();
     definitions.push(introspection_sdl());
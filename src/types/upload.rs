@@ -0,0 +1,80 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{Seek, SeekFrom},
+};
+
+use crate::{Context, GqlError, ResolverResult};
+
+/// A file of a multipart request, which is declared as `scalar Upload` in the schema.
+///
+/// https://github.com/jaydenseric/graphql-multipart-request-spec
+#[derive(Clone, Debug)]
+pub struct Upload(pub(crate) String);
+
+/// The prefix of the variable value of an uploaded file.
+pub(crate) const UPLOAD_PREFIX: &str = "#upload:";
+
+/// An uploaded file. The content is spooled to a temporary file, which is deleted when it is dropped.
+#[derive(Debug)]
+pub struct UploadValue {
+    pub filename: String,
+    pub content_type: Option<String>,
+    pub content: File,
+    pub(crate) size: usize,
+}
+
+/// The files of a request of a multipart request, keyed by the variable value of `Upload`.
+#[derive(Default)]
+pub(crate) struct UploadedFiles(pub(crate) HashMap<String, UploadValue>);
+
+impl Upload {
+    /// The upload of the `index`th file of a multipart request. The variable value contains the random `token`
+    /// of the multipart request, so a client cannot forge an upload with a plain JSON variable.
+    pub(crate) fn new(token: &str, index: usize) -> Self {
+        Upload(format!("{}{}:{}", UPLOAD_PREFIX, token, index))
+    }
+
+    /// Generates an unpredictable token for a multipart request with a cryptographically secure RNG.
+    pub(crate) fn token() -> String {
+        format!("{:032x}", rand::random::<u128>())
+    }
+
+    pub fn value(&self, ctx: &Context<'_>) -> ResolverResult<UploadValue> {
+        let file = ctx
+            .data_opt::<UploadedFiles>()
+            .and_then(|files| files.0.get(&self.0))
+            .ok_or_else(|| GqlError::new("The uploaded file does not exist", None))?;
+        file.try_clone().map_err(|err| {
+            GqlError::new(format!("Failed to read the uploaded file: {}", err), None)
+        })
+    }
+}
+
+impl UploadValue {
+    /// Creates a new handle to the same file. The handles share the read position.
+    pub fn try_clone(&self) -> std::io::Result<Self> {
+        Ok(UploadValue {
+            filename: self.filename.clone(),
+            content_type: self.content_type.clone(),
+            content: self.content.try_clone()?,
+            size: self.size,
+        })
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Reads the file from the beginning.
+    pub fn into_read(mut self) -> std::io::Result<impl std::io::Read> {
+        self.content.seek(SeekFrom::Start(0))?;
+        Ok(self.content)
+    }
+
+    /// Reads the file from the beginning without blocking the runtime.
+    pub fn into_async_read(mut self) -> std::io::Result<impl tokio::io::AsyncRead> {
+        self.content.seek(SeekFrom::Start(0))?;
+        Ok(tokio::fs::File::from_std(self.content))
+    }
+}
@@ -3,6 +3,8 @@ pub use serializer::serialize_into_gql_value;
 
 use std::collections::BTreeMap;
 
+use indexmap::IndexMap;
+
 use graphql_parser::schema::Value;
 use serde::ser::Error as SerError;
 use serde::{de::Visitor, Deserialize, Serialize, Serializer};
@@ -16,7 +18,7 @@ pub enum GqlConstValue {
     Null,
     Enum(String),
     List(Vec<GqlValue>),
-    Object(BTreeMap<String, GqlValue>),
+    Object(IndexMap<String, GqlValue>),
 }
 
 impl GqlConstValue {
@@ -61,7 +63,7 @@ pub enum GqlValue {
     Null,
     Enum(String),
     List(Vec<GqlValue>),
-    Object(BTreeMap<String, GqlValue>),
+    Object(IndexMap<String, GqlValue>),
 }
 
 impl ToString for GqlValue {
@@ -175,6 +177,13 @@ impl<'de> Visitor<'de> for GqlValueVisitor {
         Ok(GqlValue::Null)
     }
 
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(GqlValue::Null)
+    }
+
     fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
     where
         A: serde::de::SeqAccess<'de>,
@@ -190,7 +199,7 @@ impl<'de> Visitor<'de> for GqlValueVisitor {
     where
         A: serde::de::MapAccess<'de>,
     {
-        let mut result = BTreeMap::new();
+        let mut result = IndexMap::new();
         while let Some((name, value)) = map.next_entry()? {
             result.insert(name, value);
         }
@@ -234,7 +243,7 @@ impl<'a> From<Value<'a, String>> for GqlValue {
                 GqlValue::List(result)
             }
             Value::Object(obj) => {
-                let mut result = BTreeMap::new();
+                let mut result = IndexMap::new();
                 for (key, value) in obj {
                     result.insert(key, Self::from(value));
                 }
@@ -288,8 +297,14 @@ impl<T: Into<GqlValue>> From<Vec<T>> for GqlValue {
     }
 }
 
+impl From<IndexMap<String, GqlValue>> for GqlValue {
+    fn from(obj: IndexMap<String, GqlValue>) -> Self {
+        GqlValue::Object(obj)
+    }
+}
+
 impl From<BTreeMap<String, GqlValue>> for GqlValue {
     fn from(obj: BTreeMap<String, GqlValue>) -> Self {
-        GqlValue::Object(obj)
+        GqlValue::Object(obj.into_iter().collect())
     }
 }
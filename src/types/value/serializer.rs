@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use indexmap::IndexMap;
 use std::error::Error;
 use std::fmt::Display;
 
@@ -166,7 +166,7 @@ impl ser::Serializer for Serializer {
         T: serde::Serialize,
     {
         value.serialize(self).map(|v| {
-            let mut obj = BTreeMap::new();
+            let mut obj = IndexMap::new();
             obj.insert(variant.to_string(), v);
             GqlValue::Object(obj)
         })
@@ -203,7 +203,7 @@ impl ser::Serializer for Serializer {
 
     fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
         Ok(SerializeMap {
-            map: BTreeMap::new(),
+            map: IndexMap::new(),
             key: None,
         })
     }
@@ -213,7 +213,7 @@ impl ser::Serializer for Serializer {
         _name: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStruct, Self::Error> {
-        Ok(SerializeStruct(BTreeMap::new()))
+        Ok(SerializeStruct(IndexMap::new()))
     }
 
     fn serialize_struct_variant(
@@ -223,7 +223,7 @@ impl ser::Serializer for Serializer {
         variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStructVariant, Self::Error> {
-        Ok(SerializeStructVariant(variant.to_string(), BTreeMap::new()))
+        Ok(SerializeStructVariant(variant.to_string(), IndexMap::new()))
     }
 }
 
@@ -309,14 +309,14 @@ impl ser::SerializeTupleVariant for SerializeTupleVariant {
 
     #[inline]
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        let mut map = BTreeMap::new();
+        let mut map = IndexMap::new();
         map.insert(self.0, GqlValue::List(self.1));
         Ok(GqlValue::Object(map))
     }
 }
 
 struct SerializeMap {
-    map: BTreeMap<String, GqlValue>,
+    map: IndexMap<String, GqlValue>,
     key: Option<String>,
 }
 
@@ -350,7 +350,7 @@ impl ser::SerializeMap for SerializeMap {
     }
 }
 
-struct SerializeStruct(BTreeMap<String, GqlValue>);
+struct SerializeStruct(IndexMap<String, GqlValue>);
 
 impl ser::SerializeStruct for SerializeStruct {
     type Ok = GqlValue;
@@ -376,7 +376,7 @@ impl ser::SerializeStruct for SerializeStruct {
     }
 }
 
-struct SerializeStructVariant(String, BTreeMap<String, GqlValue>);
+struct SerializeStructVariant(String, IndexMap<String, GqlValue>);
 
 impl ser::SerializeStructVariant for SerializeStructVariant {
     type Ok = GqlValue;
@@ -398,7 +398,7 @@ impl ser::SerializeStructVariant for SerializeStructVariant {
 
     #[inline]
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        let mut map = BTreeMap::new();
+        let mut map = IndexMap::new();
         map.insert(self.0, GqlValue::Object(self.1));
         Ok(GqlValue::Object(map))
     }
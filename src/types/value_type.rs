@@ -32,6 +32,14 @@ impl GqlValueType {
         matches!(self, &GqlValueType::NonNullType(_))
     }
 
+    pub fn list_item_type(&self) -> Option<&GqlValueType> {
+        match self {
+            GqlValueType::NamedType(_) => None,
+            GqlValueType::ListType(item_type) => Some(item_type),
+            GqlValueType::NonNullType(non_null_type) => non_null_type.list_item_type(),
+        }
+    }
+
     pub fn is_sub_type(&self, sub: &GqlValueType, default_value: &Option<GqlValue>) -> bool {
         match (self, sub) {
             (GqlValueType::NonNullType(base_type), GqlValueType::NonNullType(sub_type)) => {
@@ -0,0 +1,224 @@
+use std::collections::{HashMap, HashSet};
+
+use graphql_parser::query::{Directive, Field, Selection, SelectionSet, TypeCondition, Value};
+
+use crate::{operation::Operation, types::schema::Schema, FieldType, GqlValue, OperationType};
+
+/// Arguments which multiply the cost of a list field that has no `@cost(multipliers:)`.
+const DEFAULT_MULTIPLIERS: [&str; 2] = ["first", "last"];
+
+/// Computes the cost of an operation.
+///
+/// A field costs the `weight` of its `@cost` directive, or 1 without the directive, plus the cost of its selection set.
+/// The cost is multiplied by the values of the arguments named in `multipliers`.
+/// Selections excluded by `@skip` or `@include` cost nothing.
+///
+/// The walk stops once the cost exceeds `max_complexity`, so the cost of a rejected operation is only a lower bound.
+pub(crate) fn operation_complexity(
+    schema: &Schema,
+    operation: &Operation,
+    max_complexity: usize,
+) -> usize {
+    let root_type = match operation.operation_type {
+        OperationType::Query => &schema.query_type_name,
+        OperationType::Mutation => &schema.mutation_type_name,
+        OperationType::Subscription => &schema.subscription_type_name,
+    };
+    let mut counter = ComplexityCounter {
+        schema,
+        operation,
+        max_complexity,
+        fragments: HashMap::new(),
+        visiting: HashSet::new(),
+    };
+    counter.selection_set_complexity(root_type, &operation.selection_set)
+}
+
+struct ComplexityCounter<'a> {
+    schema: &'a Schema,
+    operation: &'a Operation,
+    max_complexity: usize,
+    /// The cost of each fragment, which is the same wherever it is spread.
+    fragments: HashMap<&'a str, usize>,
+    visiting: HashSet<&'a str>,
+}
+
+impl<'a> ComplexityCounter<'a> {
+    fn selection_set_complexity(
+        &mut self,
+        type_name: &str,
+        selection_set: &'a SelectionSet<'static, String>,
+    ) -> usize {
+        let mut complexity = 0usize;
+        for selection in &selection_set.items {
+            let cost = match selection {
+                Selection::Field(field) if is_excluded(self.operation, &field.directives) => 0,
+                Selection::Field(field) => self.field_complexity(type_name, field),
+                Selection::FragmentSpread(spread)
+                    if is_excluded(self.operation, &spread.directives) =>
+                {
+                    0
+                }
+                Selection::InlineFragment(fragment)
+                    if is_excluded(self.operation, &fragment.directives) =>
+                {
+                    0
+                }
+                Selection::FragmentSpread(spread) => {
+                    self.fragment_complexity(&spread.fragment_name)
+                }
+                Selection::InlineFragment(fragment) => {
+                    let type_name = match &fragment.type_condition {
+                        Some(TypeCondition::On(type_condition)) => type_condition.as_str(),
+                        None => type_name,
+                    };
+                    self.selection_set_complexity(type_name, &fragment.selection_set)
+                }
+            };
+            complexity = complexity.saturating_add(cost);
+            if complexity > self.max_complexity {
+                break;
+            }
+        }
+        complexity
+    }
+
+    fn fragment_complexity(&mut self, name: &'a str) -> usize {
+        if let Some(complexity) = self.fragments.get(name) {
+            return *complexity;
+        }
+        let fragment = match self.operation.fragment_definitions.get(name) {
+            // Cycles are reported by NoFragmentCycles.
+            Some(fragment) if self.visiting.insert(name) => fragment,
+            _ => return 0,
+        };
+        let TypeCondition::On(type_condition) = &fragment.type_condition;
+        let complexity = self.selection_set_complexity(type_condition, &fragment.selection_set);
+        self.visiting.remove(name);
+        self.fragments.insert(name, complexity);
+        complexity
+    }
+
+    fn field_complexity(&mut self, type_name: &str, field: &'a Field<'static, String>) -> usize {
+        let field_def = self
+            .schema
+            .type_definitions
+            .get(type_name)
+            .and_then(|ty_def| ty_def.get_field_by_name(&field.name));
+        let field_def = match field_def {
+            Some(field_def) => field_def,
+            None => return 1,
+        };
+
+        let cost_directive = field_def.directives.iter().find(|dir| dir.name == "cost");
+        let weight = cost_directive
+            .and_then(|dir| dir.arguments.get("weight"))
+            .and_then(|weight| match weight {
+                GqlValue::Number(n) => n.as_u64(),
+                _ => None,
+            })
+            .map_or(1, |weight| weight as usize);
+
+        let multipliers = match cost_directive.and_then(|dir| dir.arguments.get("multipliers")) {
+            Some(GqlValue::List(names)) => names
+                .iter()
+                .filter_map(|name| match name {
+                    GqlValue::String(name) => Some(name.as_str()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>(),
+            _ if field_def.meta_type.list_item_type().is_some() => DEFAULT_MULTIPLIERS.to_vec(),
+            _ => Vec::new(),
+        };
+
+        let children =
+            self.selection_set_complexity(field_def.meta_type.name(), &field.selection_set);
+
+        multipliers
+            .iter()
+            .filter_map(|name| argument_value(self.operation, field_def, field, name))
+            .fold(weight.saturating_add(children), usize::saturating_mul)
+    }
+}
+
+/// Reads an integer argument of the field, falling back to the default value of the argument.
+fn argument_value(
+    operation: &Operation,
+    field_def: &FieldType,
+    field: &Field<'_, String>,
+    name: &str,
+) -> Option<usize> {
+    let value = field
+        .arguments
+        .iter()
+        .find(|(arg_name, _)| arg_name == name)
+        .and_then(|(_, value)| match value {
+            Value::Variable(var_name) => variable_value(operation, var_name),
+            value => int_value(value),
+        })
+        .or_else(|| {
+            let arg_def = field_def.arguments.iter().find(|arg| arg.name == name)?;
+            match &arg_def.default_value {
+                Some(GqlValue::Number(n)) => n.as_i64(),
+                _ => None,
+            }
+        })?;
+    usize::try_from(value).ok()
+}
+
+/// Returns true when the selection is excluded by `@skip(if: true)` or `@include(if: false)`.
+fn is_excluded(operation: &Operation, directives: &[Directive<'_, String>]) -> bool {
+    directives.iter().any(|dir| {
+        let condition = dir
+            .arguments
+            .iter()
+            .find(|(name, _)| name == "if")
+            .and_then(|(_, value)| match value {
+                Value::Boolean(condition) => Some(*condition),
+                Value::Variable(var_name) => boolean_variable_value(operation, var_name),
+                _ => None,
+            });
+        match dir.name.as_str() {
+            "skip" => condition == Some(true),
+            "include" => condition == Some(false),
+            _ => false,
+        }
+    })
+}
+
+fn variable_value(operation: &Operation, name: &str) -> Option<i64> {
+    match operation.variables.0.get(name) {
+        Some(GqlValue::Number(n)) => n.as_i64(),
+        Some(_) => None,
+        None => default_variable_value(operation, name).and_then(int_value),
+    }
+}
+
+fn boolean_variable_value(operation: &Operation, name: &str) -> Option<bool> {
+    match operation.variables.0.get(name) {
+        Some(GqlValue::Boolean(condition)) => Some(*condition),
+        Some(_) => None,
+        None => match default_variable_value(operation, name) {
+            Some(Value::Boolean(condition)) => Some(*condition),
+            _ => None,
+        },
+    }
+}
+
+fn default_variable_value<'a>(
+    operation: &'a Operation,
+    name: &str,
+) -> Option<&'a Value<'static, String>> {
+    operation
+        .variable_definitions
+        .iter()
+        .find(|var_def| var_def.name == name)
+        .and_then(|var_def| var_def.default_value.as_ref())
+}
+
+fn int_value(value: &Value<'_, String>) -> Option<i64> {
+    match value {
+        Value::Int(n) => n.as_i64(),
+        _ => None,
+    }
+}
@@ -2,19 +2,62 @@ use graphql_parser::query::Document;
 
 use crate::{operation::Operation, types::schema::Schema, GqlError, Variables};
 
-use self::visitor::{visit, NewVisitor, ValidationContext};
+use self::{
+    complexity::operation_complexity,
+    visitor::{visit, NewVisitor, ValidationContext},
+};
 
+mod complexity;
 mod rules;
 mod test_utils;
 mod utils;
 mod visitor;
 
+/// Limits on the size of an operation. `None` means unlimited.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ValidationLimits {
+    pub max_complexity: Option<usize>,
+    pub max_depth: Option<usize>,
+    pub max_aliases: Option<usize>,
+    pub max_fields: Option<usize>,
+    pub max_root_fields: Option<usize>,
+    pub max_directives: Option<usize>,
+}
+
+/// The result of a valid operation.
+#[derive(Debug, Clone, Copy)]
+pub struct ValidationResult {
+    /// The cost of the operation, which is computed only with `Container::max_complexity`.
+    pub complexity: Option<usize>,
+}
+
 pub fn apply_validation<'a>(
     schema: &'a Schema,
-    query_doc: &'a Document<'a, String>,
+    query_doc: &'a Document<'static, String>,
+    variables: Option<&'a Variables>,
+    operation: &'a Operation,
+    operation_name: Option<&'a str>,
+    limits: &ValidationLimits,
+) -> Result<ValidationResult, Vec<GqlError>> {
+    apply_validation_rules(
+        schema,
+        query_doc,
+        variables,
+        operation,
+        operation_name,
+        limits,
+    )?;
+    check_complexity(schema, operation, limits)
+}
+
+/// Applies the validation rules, which depend only on the document and not on the values of the variables.
+pub(crate) fn apply_validation_rules<'a>(
+    schema: &'a Schema,
+    query_doc: &'a Document<'static, String>,
     variables: Option<&'a Variables>,
-    operation: &'a Operation<'a>,
+    operation: &'a Operation,
     operation_name: Option<&'a str>,
+    limits: &ValidationLimits,
 ) -> Result<(), Vec<GqlError>> {
     let mut ctx = ValidationContext::new(schema, variables, operation);
     let mut visitor = NewVisitor
@@ -25,6 +68,7 @@ pub fn apply_validation<'a>(
         .with(rules::KnownDirectives::default())
         .with(rules::KnownFragmentName::default())
         .with(rules::KnownTypeNames::default())
+        .with(rules::MaxLimits::new(*limits))
         .with(rules::NoFragmentCycles::default())
         .with(rules::NoUndefinedVariables::default())
         // .with(rules::NoUnusedFragment::default())
@@ -44,6 +88,31 @@ pub fn apply_validation<'a>(
     if !ctx.errors.is_empty() {
         return Err(ctx.errors.into_iter().map(|v| v.into()).collect());
     }
-
     Ok(())
 }
+
+/// Computes the complexity of the operation if it is limited. The complexity depends on the values of the variables.
+pub(crate) fn check_complexity(
+    schema: &Schema,
+    operation: &Operation,
+    limits: &ValidationLimits,
+) -> Result<ValidationResult, Vec<GqlError>> {
+    let max_complexity = match limits.max_complexity {
+        Some(max_complexity) => max_complexity,
+        None => return Ok(ValidationResult { complexity: None }),
+    };
+    let complexity = operation_complexity(schema, operation, max_complexity);
+    if complexity > max_complexity {
+        return Err(vec![GqlError::new(
+            format!(
+                "Operation exceeds the maximum complexity of {}.",
+                max_complexity
+            ),
+            Some(operation.position),
+        )]);
+    }
+
+    Ok(ValidationResult {
+        complexity: Some(complexity),
+    })
+}
@@ -4,14 +4,14 @@ use crate::validation::visitor::{ValidationContext, Visitor};
 
 #[derive(Default)]
 pub struct ArgumentsOfCorrectType<'a> {
-    pub current_args: Option<&'a Vec<(String, Value<'a, String>)>>,
+    pub current_args: Option<&'a Vec<(String, Value<'static, String>)>>,
 }
 
 impl<'a> Visitor<'a> for ArgumentsOfCorrectType<'a> {
     fn enter_directive(
         &mut self,
         _ctx: &mut ValidationContext,
-        directive: &'a graphql_parser::schema::Directive<'a, String>,
+        directive: &'a graphql_parser::schema::Directive<'static, String>,
     ) {
         self.current_args = Some(&directive.arguments);
     }
@@ -19,7 +19,7 @@ impl<'a> Visitor<'a> for ArgumentsOfCorrectType<'a> {
     fn exit_directive(
         &mut self,
         _ctx: &mut ValidationContext,
-        _directive: &'a graphql_parser::schema::Directive<'a, String>,
+        _directive: &'a graphql_parser::schema::Directive<'static, String>,
     ) {
         self.current_args = None;
     }
@@ -27,7 +27,7 @@ impl<'a> Visitor<'a> for ArgumentsOfCorrectType<'a> {
     fn enter_field(
         &mut self,
         _ctx: &mut ValidationContext,
-        field: &'a graphql_parser::query::Field<'a, String>,
+        field: &'a graphql_parser::query::Field<'static, String>,
     ) {
         self.current_args = Some(&field.arguments);
     }
@@ -35,7 +35,7 @@ impl<'a> Visitor<'a> for ArgumentsOfCorrectType<'a> {
     fn exit_field(
         &mut self,
         _ctx: &mut ValidationContext,
-        _field: &'a graphql_parser::query::Field<'a, String>,
+        _field: &'a graphql_parser::query::Field<'static, String>,
     ) {
         self.current_args = None;
     }
@@ -44,7 +44,7 @@ impl<'a> Visitor<'a> for ArgumentsOfCorrectType<'a> {
         &mut self,
         _ctx: &mut ValidationContext,
         arg_name: &'a str,
-        _arg_value: &'a Value<'a, String>,
+        _arg_value: &'a Value<'static, String>,
     ) {
         match &self.current_args {
             Some(args) => {
@@ -9,7 +9,7 @@ impl<'a> Visitor<'a> for DefaultValueOfCorrectType {
     fn enter_variable_definition(
         &mut self,
         ctx: &mut crate::validation::visitor::ValidationContext,
-        variable_definition: &'a graphql_parser::query::VariableDefinition<'a, String>,
+        variable_definition: &'a graphql_parser::query::VariableDefinition<'static, String>,
     ) {
         if let Some(value) = &variable_definition.default_value {
             if matches!(&variable_definition.var_type, Type::NonNullType(_)) {
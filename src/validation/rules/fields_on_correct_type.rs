@@ -6,7 +6,7 @@ use crate::validation::visitor::{ValidationContext, Visitor};
 pub struct FieldsOnCorrectType;
 
 impl<'a> Visitor<'a> for FieldsOnCorrectType {
-    fn enter_field(&mut self, ctx: &mut ValidationContext, field: &'a Field<'a, String>) {
+    fn enter_field(&mut self, ctx: &mut ValidationContext, field: &'a Field<'static, String>) {
         if let Some(parent_type) = ctx.parent_type() {
             if field.name == "__typename" || field.name == "__type" || field.name == "__schema" {
                 return;
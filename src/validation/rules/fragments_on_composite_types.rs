@@ -10,7 +10,7 @@ impl<'a> Visitor<'a> for FragmentsOnCompositeTypes {
         &mut self,
         ctx: &mut ValidationContext,
         name: &'a str,
-        fragment_definition: &'a FragmentDefinition<'a, String>,
+        fragment_definition: &'a FragmentDefinition<'static, String>,
     ) {
         if let Some(current_type) = ctx.current_type() {
             let target_type = ctx.schema.type_definitions.get(current_type.name());
@@ -29,7 +29,7 @@ impl<'a> Visitor<'a> for FragmentsOnCompositeTypes {
     fn enter_inline_fragment(
         &mut self,
         ctx: &mut ValidationContext,
-        inline_fragment: &'a InlineFragment<'a, String>,
+        inline_fragment: &'a InlineFragment<'static, String>,
     ) {
         if let Some(current_type) = ctx.current_type() {
             let target_type = ctx.schema.type_definitions.get(current_type.name());
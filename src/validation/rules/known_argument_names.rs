@@ -23,7 +23,7 @@ impl<'a> Visitor<'a> for KnownArgumentNames<'a> {
     fn enter_directive(
         &mut self,
         ctx: &mut ValidationContext,
-        directive: &'a Directive<'a, String>,
+        directive: &'a Directive<'static, String>,
     ) {
         if let Some(schema_directive) = ctx.schema.directives.get(&directive.name) {
             self.current_args = Some((
@@ -39,12 +39,12 @@ impl<'a> Visitor<'a> for KnownArgumentNames<'a> {
     fn exit_directive(
         &mut self,
         _ctx: &mut ValidationContext,
-        _directive: &'a Directive<'a, String>,
+        _directive: &'a Directive<'static, String>,
     ) {
         self.current_args = None;
     }
 
-    fn enter_field(&mut self, ctx: &mut ValidationContext, field: &'a Field<'a, String>) {
+    fn enter_field(&mut self, ctx: &mut ValidationContext, field: &'a Field<'static, String>) {
         if let Some(parent_type) = ctx.parent_type() {
             if let Some(target_field) = parent_type.get_field_by_name(&field.name) {
                 self.current_args = Some((
@@ -62,7 +62,7 @@ impl<'a> Visitor<'a> for KnownArgumentNames<'a> {
         }
     }
 
-    fn exit_field(&mut self, _ctx: &mut ValidationContext, _field: &'a Field<'a, String>) {
+    fn exit_field(&mut self, _ctx: &mut ValidationContext, _field: &'a Field<'static, String>) {
         self.current_args = None
     }
 
@@ -70,7 +70,7 @@ impl<'a> Visitor<'a> for KnownArgumentNames<'a> {
         &mut self,
         ctx: &mut ValidationContext,
         arg_name: &'a str,
-        _arg_value: &'a Value<'a, String>,
+        _arg_value: &'a Value<'static, String>,
     ) {
         if let Some((args, arg_position)) = &self.current_args {
             if !args.iter().any(|arg| arg == arg_name) {
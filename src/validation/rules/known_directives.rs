@@ -15,7 +15,7 @@ impl<'a> Visitor<'a> for KnownDirectives {
         &mut self,
         _ctx: &mut ValidationContext<'a>,
         _name: Option<&'a str>,
-        operation_definition: &'a OperationDefinition<'a, String>,
+        operation_definition: &'a OperationDefinition<'static, String>,
     ) {
         self.location_stack.push(match operation_definition {
             OperationDefinition::Query(_) => DirectiveLocation::Query,
@@ -29,7 +29,7 @@ impl<'a> Visitor<'a> for KnownDirectives {
         &mut self,
         _ctx: &mut ValidationContext<'a>,
         _name: Option<&'a str>,
-        _operation_definition: &'a OperationDefinition<'a, String>,
+        _operation_definition: &'a OperationDefinition<'static, String>,
     ) {
         self.location_stack.pop();
     }
@@ -38,7 +38,7 @@ impl<'a> Visitor<'a> for KnownDirectives {
         &mut self,
         _ctx: &mut ValidationContext,
         _name: &'a str,
-        _fragment_definition: &'a graphql_parser::query::FragmentDefinition<'a, String>,
+        _fragment_definition: &'a graphql_parser::query::FragmentDefinition<'static, String>,
     ) {
         self.location_stack
             .push(DirectiveLocation::FragmentDefinition);
@@ -48,7 +48,7 @@ impl<'a> Visitor<'a> for KnownDirectives {
         &mut self,
         _ctx: &mut ValidationContext,
         _name: &'a str,
-        _fragment_definition: &'a graphql_parser::query::FragmentDefinition<'a, String>,
+        _fragment_definition: &'a graphql_parser::query::FragmentDefinition<'static, String>,
     ) {
         self.location_stack.pop();
     }
@@ -56,7 +56,7 @@ impl<'a> Visitor<'a> for KnownDirectives {
     fn enter_directive(
         &mut self,
         ctx: &mut ValidationContext,
-        directive: &'a graphql_parser::schema::Directive<'a, String>,
+        directive: &'a graphql_parser::schema::Directive<'static, String>,
     ) {
         let is_exist = ctx.schema.directives.get(&directive.name).is_some();
         if !is_exist {
@@ -70,7 +70,7 @@ impl<'a> Visitor<'a> for KnownDirectives {
     fn enter_field(
         &mut self,
         _ctx: &mut ValidationContext,
-        _field: &'a graphql_parser::query::Field<'a, String>,
+        _field: &'a graphql_parser::query::Field<'static, String>,
     ) {
         self.location_stack.push(DirectiveLocation::Field);
     }
@@ -78,7 +78,7 @@ impl<'a> Visitor<'a> for KnownDirectives {
     fn exit_field(
         &mut self,
         _ctx: &mut ValidationContext,
-        _field: &'a graphql_parser::query::Field<'a, String>,
+        _field: &'a graphql_parser::query::Field<'static, String>,
     ) {
         self.location_stack.pop();
     }
@@ -86,7 +86,7 @@ impl<'a> Visitor<'a> for KnownDirectives {
     fn enter_fragment_spread(
         &mut self,
         _ctx: &mut ValidationContext,
-        _fragment_spread: &'a graphql_parser::query::FragmentSpread<'a, String>,
+        _fragment_spread: &'a graphql_parser::query::FragmentSpread<'static, String>,
     ) {
         self.location_stack.push(DirectiveLocation::FragmentSpread);
     }
@@ -94,7 +94,7 @@ impl<'a> Visitor<'a> for KnownDirectives {
     fn exit_fragment_spread(
         &mut self,
         _ctx: &mut ValidationContext,
-        _fragment_spread: &'a graphql_parser::query::FragmentSpread<'a, String>,
+        _fragment_spread: &'a graphql_parser::query::FragmentSpread<'static, String>,
     ) {
         self.location_stack.pop();
     }
@@ -102,7 +102,7 @@ impl<'a> Visitor<'a> for KnownDirectives {
     fn enter_inline_fragment(
         &mut self,
         _ctx: &mut ValidationContext,
-        _inline_fragment: &'a graphql_parser::query::InlineFragment<'a, String>,
+        _inline_fragment: &'a graphql_parser::query::InlineFragment<'static, String>,
     ) {
         self.location_stack.push(DirectiveLocation::InlineFragment);
     }
@@ -110,7 +110,7 @@ impl<'a> Visitor<'a> for KnownDirectives {
     fn exit_inline_fragment(
         &mut self,
         _ctx: &mut ValidationContext,
-        _inline_fragment: &'a graphql_parser::query::InlineFragment<'a, String>,
+        _inline_fragment: &'a graphql_parser::query::InlineFragment<'static, String>,
     ) {
         self.location_stack.pop();
     }
@@ -9,7 +9,7 @@ impl<'a> Visitor<'a> for KnownFragmentName {
     fn enter_fragment_spread(
         &mut self,
         ctx: &mut ValidationContext,
-        fragment_spread: &'a FragmentSpread<'a, String>,
+        fragment_spread: &'a FragmentSpread<'static, String>,
     ) {
         if !ctx.fragments.contains_key(&fragment_spread.fragment_name) {
             ctx.add_error(
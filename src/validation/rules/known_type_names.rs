@@ -13,7 +13,7 @@ impl<'a> Visitor<'a> for KnownTypeNames {
         &mut self,
         ctx: &mut ValidationContext,
         _name: &'a str,
-        fragment_definition: &'a graphql_parser::query::FragmentDefinition<'a, String>,
+        fragment_definition: &'a graphql_parser::query::FragmentDefinition<'static, String>,
     ) {
         match &fragment_definition.type_condition {
             graphql_parser::query::TypeCondition::On(on_ty) => {
@@ -24,7 +24,7 @@ impl<'a> Visitor<'a> for KnownTypeNames {
     fn enter_variable_definition(
         &mut self,
         ctx: &mut ValidationContext,
-        variable_definition: &'a graphql_parser::query::VariableDefinition<'a, String>,
+        variable_definition: &'a graphql_parser::query::VariableDefinition<'static, String>,
     ) {
         validate(
             ctx,
@@ -36,7 +36,7 @@ impl<'a> Visitor<'a> for KnownTypeNames {
     fn enter_inline_fragment(
         &mut self,
         ctx: &mut ValidationContext,
-        fragment_spread: &'a graphql_parser::query::InlineFragment<'a, String>,
+        fragment_spread: &'a graphql_parser::query::InlineFragment<'static, String>,
     ) {
         if let Some(ty_condition) = &fragment_spread.type_condition {
             match ty_condition {
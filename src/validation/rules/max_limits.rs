@@ -0,0 +1,357 @@
+use graphql_parser::query::OperationDefinition;
+
+use crate::validation::{
+    utils::{count_operation_selections, get_operation_def_position},
+    visitor::{ValidationContext, Visitor},
+    ValidationLimits,
+};
+
+/// Limits the size of an operation. The selections are counted once, and each exceeded limit is reported.
+///
+/// - depth: how deeply fields are nested. `{ hero { friends { name } } }` has depth 3.
+/// - fields: the number of fields, including the fields of each fragment spread.
+/// - root fields: the number of fields selected on the root type.
+/// - aliases: the number of aliased fields, which can make the response much larger than the query.
+/// - directives: the number of directives, including the directives of the operation.
+pub struct MaxLimits {
+    limits: ValidationLimits,
+}
+
+impl MaxLimits {
+    pub fn new(limits: ValidationLimits) -> Self {
+        MaxLimits { limits }
+    }
+}
+
+impl<'a> Visitor<'a> for MaxLimits {
+    fn enter_operation_definition(
+        &mut self,
+        ctx: &mut ValidationContext<'a>,
+        _name: Option<&'a str>,
+        operation_definition: &'a OperationDefinition<'static, String>,
+    ) {
+        let limits = &self.limits;
+        if limits.max_depth.is_none()
+            && limits.max_fields.is_none()
+            && limits.max_root_fields.is_none()
+            && limits.max_aliases.is_none()
+            && limits.max_directives.is_none()
+        {
+            return;
+        }
+        let counts = count_operation_selections(operation_definition, ctx.fragments);
+        let position = get_operation_def_position(operation_definition);
+
+        if let Some(max_root_fields) = limits.max_root_fields {
+            if counts.direct_fields > max_root_fields {
+                ctx.add_error(
+                    format!(
+                        "Operation has {} root fields, which exceeds the maximum of {} root fields.",
+                        counts.direct_fields, max_root_fields
+                    ),
+                    vec![position],
+                );
+            }
+        }
+        if let Some(max_fields) = limits.max_fields {
+            if counts.fields > max_fields {
+                ctx.add_error(
+                    format!(
+                        "Operation has {} fields, which exceeds the maximum of {} fields.",
+                        counts.fields, max_fields
+                    ),
+                    vec![position],
+                );
+            }
+        }
+        if let Some(max_directives) = limits.max_directives {
+            if counts.directives > max_directives {
+                ctx.add_error(
+                    format!(
+                        "Operation has {} directives, which exceeds the maximum of {} directives.",
+                        counts.directives, max_directives
+                    ),
+                    vec![position],
+                );
+            }
+        }
+        if let Some(max_depth) = limits.max_depth {
+            if counts.depth > max_depth {
+                ctx.add_error(
+                    format!(
+                        "Operation has depth {}, which exceeds the maximum depth of {}.",
+                        counts.depth, max_depth
+                    ),
+                    counts.deepest_position.into_iter().collect(),
+                );
+            }
+        }
+        if let Some(max_aliases) = limits.max_aliases {
+            if counts.aliases > max_aliases {
+                ctx.add_error(
+                    format!(
+                        "Operation has {} aliases, which exceeds the maximum of {} aliases.",
+                        counts.aliases, max_aliases
+                    ),
+                    vec![position],
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{check_fails_rule, check_passes_rule};
+
+    use super::*;
+
+    fn max_depth() -> MaxLimits {
+        MaxLimits::new(ValidationLimits {
+            max_depth: Some(3),
+            ..Default::default()
+        })
+    }
+
+    fn max_fields() -> MaxLimits {
+        MaxLimits::new(ValidationLimits {
+            max_fields: Some(4),
+            ..Default::default()
+        })
+    }
+
+    fn max_root_fields() -> MaxLimits {
+        MaxLimits::new(ValidationLimits {
+            max_root_fields: Some(2),
+            ..Default::default()
+        })
+    }
+
+    fn max_aliases() -> MaxLimits {
+        MaxLimits::new(ValidationLimits {
+            max_aliases: Some(2),
+            ..Default::default()
+        })
+    }
+
+    fn max_directives() -> MaxLimits {
+        MaxLimits::new(ValidationLimits {
+            max_directives: Some(2),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn within_max_depth() {
+        let query_doc = r#"
+        {
+            human(id: 1) {
+                friends {
+                    name
+                }
+            }
+        }
+        "#;
+        check_passes_rule!(query_doc, max_depth);
+    }
+
+    #[test]
+    fn exceeds_max_depth() {
+        let query_doc = r#"
+        {
+            human(id: 1) {
+                friends {
+                    friends {
+                        name
+                    }
+                }
+            }
+        }
+        "#;
+        check_fails_rule!(query_doc, max_depth);
+    }
+
+    #[test]
+    fn exceeds_max_depth_in_fragment() {
+        let query_doc = r#"
+        {
+            human(id: 1) {
+                ...friendsFragment
+            }
+        }
+        fragment friendsFragment on Human {
+            friends {
+                ... on Human {
+                    friends {
+                        name
+                    }
+                }
+            }
+        }
+        "#;
+        check_fails_rule!(query_doc, max_depth);
+    }
+
+    #[test]
+    fn within_max_fields() {
+        let query_doc = r#"
+        {
+            human(id: 1) {
+                name
+                friends { name }
+            }
+        }
+        "#;
+        check_passes_rule!(query_doc, max_fields);
+    }
+
+    #[test]
+    fn exceeds_max_fields() {
+        let query_doc = r#"
+        {
+            human(id: 1) {
+                name
+                friends { name }
+            }
+            dog { name }
+        }
+        "#;
+        check_fails_rule!(query_doc, max_fields);
+    }
+
+    #[test]
+    fn exceeds_max_fields_in_fragments() {
+        let query_doc = r#"
+        {
+            human(id: 1) {
+                ...nameFragment
+                friends { ...nameFragment }
+            }
+        }
+        fragment nameFragment on Human {
+            name
+            id
+        }
+        "#;
+        check_fails_rule!(query_doc, max_fields);
+    }
+
+    #[test]
+    fn within_max_root_fields() {
+        let query_doc = r#"
+        {
+            human(id: 1) { name }
+            dog { name }
+        }
+        "#;
+        check_passes_rule!(query_doc, max_root_fields);
+    }
+
+    #[test]
+    fn exceeds_max_root_fields() {
+        let query_doc = r#"
+        {
+            human(id: 1) { name }
+            dog { name }
+            cat { name }
+        }
+        "#;
+        check_fails_rule!(query_doc, max_root_fields);
+    }
+
+    #[test]
+    fn exceeds_max_root_fields_in_fragments() {
+        let query_doc = r#"
+        query {
+            ...rootFragment
+            ... on Query {
+                cat { name }
+            }
+        }
+        fragment rootFragment on Query {
+            human(id: 1) { name }
+            dog { name }
+        }
+        "#;
+        check_fails_rule!(query_doc, max_root_fields);
+    }
+
+    #[test]
+    fn within_max_aliases() {
+        let query_doc = r#"
+        {
+            first: human(id: 1) { name }
+            second: human(id: 2) { name }
+        }
+        "#;
+        check_passes_rule!(query_doc, max_aliases);
+    }
+
+    #[test]
+    fn exceeds_max_aliases() {
+        let query_doc = r#"
+        {
+            first: human(id: 1) { name }
+            second: human(id: 2) { alias: name }
+            third: human(id: 3) { name }
+        }
+        "#;
+        check_fails_rule!(query_doc, max_aliases);
+    }
+
+    #[test]
+    fn exceeds_max_aliases_in_fragments() {
+        let query_doc = r#"
+        {
+            human(id: 1) {
+                ...aliasFragment
+                friends { ...aliasFragment }
+            }
+        }
+        fragment aliasFragment on Human {
+            first: name
+            second: name
+        }
+        "#;
+        check_fails_rule!(query_doc, max_aliases);
+    }
+
+    #[test]
+    fn within_max_directives() {
+        let query_doc = r#"
+        query Test($skip: Boolean!) {
+            human(id: 1) @skip(if: $skip) { name @include(if: true) }
+        }
+        "#;
+        check_passes_rule!(query_doc, max_directives);
+    }
+
+    #[test]
+    fn exceeds_max_directives() {
+        let query_doc = r#"
+        query Test($skip: Boolean!) {
+            human(id: 1) @skip(if: $skip) {
+                name @include(if: true)
+                id @include(if: true)
+            }
+        }
+        "#;
+        check_fails_rule!(query_doc, max_directives);
+    }
+
+    #[test]
+    fn exceeds_max_directives_in_fragments() {
+        let query_doc = r#"
+        {
+            human(id: 1) {
+                ...nameFragment
+                friends { ...nameFragment @include(if: true) }
+            }
+        }
+        fragment nameFragment on Human {
+            name @include(if: true)
+        }
+        "#;
+        check_fails_rule!(query_doc, max_directives);
+    }
+}
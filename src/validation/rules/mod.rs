@@ -6,6 +6,7 @@ mod known_argument_names;
 mod known_directives;
 mod known_fragment_names;
 mod known_type_names;
+mod max_limits;
 mod no_fragment_cycles;
 mod no_undefined_variables;
 mod no_unused_fragments;
@@ -27,6 +28,7 @@ pub use known_argument_names::KnownArgumentNames;
 pub use known_directives::KnownDirectives;
 pub use known_fragment_names::KnownFragmentName;
 pub use known_type_names::KnownTypeNames;
+pub use max_limits::MaxLimits;
 pub use no_fragment_cycles::NoFragmentCycles;
 pub use no_undefined_variables::NoUndefinedVariables;
 pub use no_unused_fragments::NoUnusedFragment;
@@ -48,7 +48,11 @@ pub struct NoFragmentCycles<'a> {
 }
 
 impl<'a> Visitor<'a> for NoFragmentCycles<'a> {
-    fn exit_document(&mut self, ctx: &mut ValidationContext<'a>, _doc: &'a Document<'a, String>) {
+    fn exit_document(
+        &mut self,
+        ctx: &mut ValidationContext<'a>,
+        _doc: &'a Document<'static, String>,
+    ) {
         let mut detector = CycleDetector {
             visited: HashSet::new(),
             fragment_spreads: &self.fragment_spreads,
@@ -69,7 +73,7 @@ impl<'a> Visitor<'a> for NoFragmentCycles<'a> {
         &mut self,
         _ctx: &mut ValidationContext,
         name: &'a str,
-        _fragment_definition: &'a graphql_parser::query::FragmentDefinition<'a, String>,
+        _fragment_definition: &'a graphql_parser::query::FragmentDefinition<'static, String>,
     ) {
         self.current_fragment = Some(name);
         self.fragment_order.push(name);
@@ -79,7 +83,7 @@ impl<'a> Visitor<'a> for NoFragmentCycles<'a> {
         &mut self,
         _ctx: &mut ValidationContext,
         _name: &'a str,
-        _fragment_definition: &'a graphql_parser::query::FragmentDefinition<'a, String>,
+        _fragment_definition: &'a graphql_parser::query::FragmentDefinition<'static, String>,
     ) {
         self.current_fragment = None;
     }
@@ -87,7 +91,7 @@ impl<'a> Visitor<'a> for NoFragmentCycles<'a> {
     fn enter_fragment_spread(
         &mut self,
         _ctx: &mut ValidationContext,
-        fragment_spread: &'a graphql_parser::query::FragmentSpread<'a, String>,
+        fragment_spread: &'a graphql_parser::query::FragmentSpread<'static, String>,
     ) {
         if let Some(current_fragment) = self.current_fragment {
             self.fragment_spreads
@@ -54,7 +54,7 @@ impl<'a> Visitor<'a> for NoUndefinedVariables<'a> {
     fn exit_document(
         &mut self,
         ctx: &mut ValidationContext<'a>,
-        _doc: &'a graphql_parser::query::Document<'a, String>,
+        _doc: &'a graphql_parser::query::Document<'static, String>,
     ) {
         for (name, (ref var_def_pos, ref var_defs)) in &self.defined_variables {
             let mut undefined_vars = Vec::new();
@@ -85,7 +85,7 @@ impl<'a> Visitor<'a> for NoUndefinedVariables<'a> {
         &mut self,
         _ctx: &mut ValidationContext<'a>,
         name: Option<&'a str>,
-        operation_definition: &'a OperationDefinition<'a, String>,
+        operation_definition: &'a OperationDefinition<'static, String>,
     ) {
         self.current_scope = Some(Scope::Operation(name));
         self.defined_variables.insert(
@@ -101,7 +101,7 @@ impl<'a> Visitor<'a> for NoUndefinedVariables<'a> {
         &mut self,
         _ctx: &mut ValidationContext,
         name: &'a str,
-        _fragment_definition: &'a graphql_parser::query::FragmentDefinition<'a, String>,
+        _fragment_definition: &'a graphql_parser::query::FragmentDefinition<'static, String>,
     ) {
         self.current_scope = Some(Scope::Fragment(name));
     }
@@ -109,7 +109,7 @@ impl<'a> Visitor<'a> for NoUndefinedVariables<'a> {
     fn enter_variable_definition(
         &mut self,
         _ctx: &mut ValidationContext,
-        variable_definition: &'a graphql_parser::query::VariableDefinition<'a, String>,
+        variable_definition: &'a graphql_parser::query::VariableDefinition<'static, String>,
     ) {
         if let Some(Scope::Operation(name)) = &self.current_scope {
             if let Some(&mut (_, ref mut vars)) = self.defined_variables.get_mut(name) {
@@ -122,7 +122,7 @@ impl<'a> Visitor<'a> for NoUndefinedVariables<'a> {
         &mut self,
         _ctx: &mut ValidationContext,
         _arg_name: &'a str,
-        arg_value: &'a graphql_parser::schema::Value<'a, String>,
+        arg_value: &'a graphql_parser::schema::Value<'static, String>,
     ) {
         if let Some(scope) = &self.current_scope {
             self.used_variables
@@ -139,7 +139,7 @@ impl<'a> Visitor<'a> for NoUndefinedVariables<'a> {
     fn enter_fragment_spread(
         &mut self,
         _ctx: &mut ValidationContext,
-        fragment_spread: &'a graphql_parser::query::FragmentSpread<'a, String>,
+        fragment_spread: &'a graphql_parser::query::FragmentSpread<'static, String>,
     ) {
         if let Some(scope) = &self.current_scope {
             self.fragment_spreads
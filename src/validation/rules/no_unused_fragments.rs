@@ -39,7 +39,7 @@ impl<'a> Visitor<'a> for NoUnusedFragment<'a> {
     fn exit_document(
         &mut self,
         ctx: &mut ValidationContext<'a>,
-        doc: &'a graphql_parser::query::Document<'a, String>,
+        doc: &'a graphql_parser::query::Document<'static, String>,
     ) {
         let mut reachable = HashSet::new();
 
@@ -60,7 +60,7 @@ impl<'a> Visitor<'a> for NoUnusedFragment<'a> {
         &mut self,
         _ctx: &mut ValidationContext<'a>,
         name: Option<&'a str>,
-        _operation_definition: &'a OperationDefinition<'a, String>,
+        _operation_definition: &'a OperationDefinition<'static, String>,
     ) {
         self.current_scope = Some(Scope::Operation(name));
     }
@@ -69,7 +69,7 @@ impl<'a> Visitor<'a> for NoUnusedFragment<'a> {
         &mut self,
         _ctx: &mut ValidationContext,
         name: &'a str,
-        fragment_definition: &'a FragmentDefinition<'a, String>,
+        fragment_definition: &'a FragmentDefinition<'static, String>,
     ) {
         self.current_scope = Some(Scope::Fragment(name));
         self.fragment_definitions
@@ -79,7 +79,7 @@ impl<'a> Visitor<'a> for NoUnusedFragment<'a> {
     fn enter_fragment_spread(
         &mut self,
         _ctx: &mut ValidationContext,
-        fragment_spread: &'a FragmentSpread<'a, String>,
+        fragment_spread: &'a FragmentSpread<'static, String>,
     ) {
         if let Some(scope) = &self.current_scope {
             self.fragment_spreads
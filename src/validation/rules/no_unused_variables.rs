@@ -45,7 +45,11 @@ impl<'a> NoUnusedVariables<'a> {
 }
 
 impl<'a> Visitor<'a> for NoUnusedVariables<'a> {
-    fn exit_document(&mut self, ctx: &mut ValidationContext<'a>, _doc: &'a Document<'a, String>) {
+    fn exit_document(
+        &mut self,
+        ctx: &mut ValidationContext<'a>,
+        _doc: &'a Document<'static, String>,
+    ) {
         for (name, vars) in &self.defined_variables {
             let mut used_vars = HashSet::new();
             let mut visited = HashSet::new();
@@ -72,7 +76,7 @@ impl<'a> Visitor<'a> for NoUnusedVariables<'a> {
         &mut self,
         _ctx: &mut ValidationContext<'a>,
         name: Option<&'a str>,
-        _operation_definition: &'a graphql_parser::query::OperationDefinition<'a, String>,
+        _operation_definition: &'a graphql_parser::query::OperationDefinition<'static, String>,
     ) {
         self.current_scope = Some(Scope::Operation(name));
         self.defined_variables.insert(name, HashSet::new());
@@ -82,7 +86,7 @@ impl<'a> Visitor<'a> for NoUnusedVariables<'a> {
         &mut self,
         _ctx: &mut ValidationContext,
         name: &'a str,
-        _fragment_definition: &'a graphql_parser::query::FragmentDefinition<'a, String>,
+        _fragment_definition: &'a graphql_parser::query::FragmentDefinition<'static, String>,
     ) {
         self.current_scope = Some(Scope::Fragment(name));
     }
@@ -90,7 +94,7 @@ impl<'a> Visitor<'a> for NoUnusedVariables<'a> {
     fn enter_variable_definition(
         &mut self,
         _ctx: &mut ValidationContext,
-        variable_definition: &'a graphql_parser::query::VariableDefinition<'a, String>,
+        variable_definition: &'a graphql_parser::query::VariableDefinition<'static, String>,
     ) {
         if let Some(Scope::Operation(operation_name)) = &self.current_scope {
             if let Some(vars) = self.defined_variables.get_mut(operation_name) {
@@ -103,7 +107,7 @@ impl<'a> Visitor<'a> for NoUnusedVariables<'a> {
         &mut self,
         _ctx: &mut ValidationContext,
         _arg_name: &'a str,
-        arg_value: &'a graphql_parser::schema::Value<'a, String>,
+        arg_value: &'a graphql_parser::schema::Value<'static, String>,
     ) {
         if let Some(scope) = &self.current_scope {
             self.used_variables
@@ -116,7 +120,7 @@ impl<'a> Visitor<'a> for NoUnusedVariables<'a> {
     fn enter_fragment_spread(
         &mut self,
         _ctx: &mut ValidationContext,
-        fragment_spread: &'a graphql_parser::query::FragmentSpread<'a, String>,
+        fragment_spread: &'a graphql_parser::query::FragmentSpread<'static, String>,
     ) {
         if let Some(scope) = &self.current_scope {
             self.fragment_spreads
@@ -11,7 +11,7 @@ impl<'a> Visitor<'a> for OverlappingFieldsCanBeMerged {
     fn enter_selection_set(
         &mut self,
         ctx: &mut ValidationContext<'a>,
-        selection_set: &'a SelectionSet<'a, String>,
+        selection_set: &'a SelectionSet<'static, String>,
     ) {
         let mut find_conflicts = FindConflicts {
             outputs: Default::default(),
@@ -21,14 +21,14 @@ impl<'a> Visitor<'a> for OverlappingFieldsCanBeMerged {
 }
 
 struct FindConflicts<'a> {
-    outputs: HashMap<&'a str, &'a Field<'a, String>>,
+    outputs: HashMap<&'a str, &'a Field<'static, String>>,
 }
 
 impl<'a> FindConflicts<'a> {
     pub fn find(
         &mut self,
         ctx: &mut ValidationContext<'a>,
-        selection_set: &'a SelectionSet<'a, String>,
+        selection_set: &'a SelectionSet<'static, String>,
     ) {
         for item in &selection_set.items {
             match item {
@@ -55,7 +55,7 @@ impl<'a> FindConflicts<'a> {
         &mut self,
         ctx: &mut ValidationContext<'a>,
         name: &'a str,
-        field: &'a Field<'a, String>,
+        field: &'a Field<'static, String>,
     ) {
         match self.outputs.get(name) {
             Some(prev_field) => {
@@ -69,7 +69,7 @@ impl<'a> FindConflicts<'a> {
                     )
                 }
 
-                if prev_field.arguments.len() != field.arguments.len() {
+                if !same_arguments(prev_field, field) {
                     ctx.add_error(
                         format!(
                             "Fields {} conflict because they have different arguments.",
@@ -78,23 +78,6 @@ impl<'a> FindConflicts<'a> {
                         vec![field.position, prev_field.position],
                     )
                 }
-
-                for (arg_name, arg_value) in &prev_field.arguments {
-                    match field.arguments.iter().find(|(name, _)| name == arg_name) {
-                        Some(arg) => {
-                            if &arg.1 == arg_value {
-                                {}
-                            }
-                        }
-                        None => ctx.add_error(
-                            format!(
-                                "Fields {} conflict because the have different arguments",
-                                name
-                            ),
-                            vec![prev_field.position, field.position],
-                        ),
-                    }
-                }
             }
             None => {
                 self.outputs.insert(name, field);
@@ -103,15 +86,129 @@ impl<'a> FindConflicts<'a> {
     }
 }
 
+fn same_arguments<'a>(a: &Field<'a, String>, b: &Field<'a, String>) -> bool {
+    a.arguments.len() == b.arguments.len()
+        && a.arguments.iter().all(|(arg_name, arg_value)| {
+            b.arguments
+                .iter()
+                .any(|(name, value)| name == arg_name && value == arg_value)
+        })
+}
+
 #[cfg(test)]
 mod tests {
-    use super::OverlappingFieldsCanBeMerged;
+    use crate::{check_fails_rule, check_passes_rule};
+
+    use super::*;
 
-    #[allow(unused)]
     fn factory() -> OverlappingFieldsCanBeMerged {
         OverlappingFieldsCanBeMerged
     }
 
     #[test]
-    fn todo() {}
+    fn identical_fields() {
+        let query_doc = r#"
+        {
+            human(id: 1) {
+                name
+                name
+            }
+        }
+        "#;
+        check_passes_rule!(query_doc, factory);
+    }
+
+    #[test]
+    fn identical_fields_with_identical_args() {
+        let query_doc = r#"
+        {
+            human(id: 1) {
+                height(unit: METER)
+                height(unit: METER)
+            }
+        }
+        "#;
+        check_passes_rule!(query_doc, factory);
+    }
+
+    #[test]
+    fn different_args_with_different_aliases() {
+        let query_doc = r#"
+        {
+            human(id: 1) {
+                meters: height(unit: METER)
+                feet: height(unit: FOOT)
+            }
+        }
+        "#;
+        check_passes_rule!(query_doc, factory);
+    }
+
+    #[test]
+    fn same_aliases_with_different_field_targets() {
+        let query_doc = r#"
+        {
+            human(id: 1) {
+                label: name
+                label: homePlanet
+            }
+        }
+        "#;
+        check_fails_rule!(query_doc, factory);
+    }
+
+    #[test]
+    fn alias_masking_direct_field_access() {
+        let query_doc = r#"
+        {
+            human(id: 1) {
+                name: homePlanet
+                name
+            }
+        }
+        "#;
+        check_fails_rule!(query_doc, factory);
+    }
+
+    #[test]
+    fn different_args_second_adds_an_argument() {
+        let query_doc = r#"
+        {
+            human(id: 1) {
+                height
+                height(unit: FOOT)
+            }
+        }
+        "#;
+        check_fails_rule!(query_doc, factory);
+    }
+
+    #[test]
+    fn conflicting_args() {
+        let query_doc = r#"
+        {
+            human(id: 1) {
+                height(unit: METER)
+                height(unit: FOOT)
+            }
+        }
+        "#;
+        check_fails_rule!(query_doc, factory);
+    }
+
+    #[test]
+    fn conflicting_args_through_fragment() {
+        let query_doc = r#"
+        {
+            human(id: 1) {
+                height(unit: METER)
+                ...heightInFeet
+            }
+        }
+        fragment heightInFeet on Human {
+            height(unit: FOOT)
+        }
+        "#;
+        check_fails_rule!(query_doc, factory);
+    }
 }
@@ -6,11 +6,15 @@ use crate::validation::visitor::{ValidationContext, Visitor};
 
 #[derive(Default)]
 pub struct PossibleFragmentSpreads<'a> {
-    fragment_types: HashMap<&'a str, &'a TypeCondition<'a, String>>,
+    fragment_types: HashMap<&'a str, &'a TypeCondition<'static, String>>,
 }
 
 impl<'a> Visitor<'a> for PossibleFragmentSpreads<'a> {
-    fn enter_document(&mut self, _ctx: &mut ValidationContext<'a>, doc: &'a Document<'a, String>) {
+    fn enter_document(
+        &mut self,
+        _ctx: &mut ValidationContext<'a>,
+        doc: &'a Document<'static, String>,
+    ) {
         for def in &doc.definitions {
             if let Definition::Fragment(fragment) = def {
                 self.fragment_types
@@ -22,7 +26,7 @@ impl<'a> Visitor<'a> for PossibleFragmentSpreads<'a> {
     fn enter_fragment_spread(
         &mut self,
         ctx: &mut ValidationContext,
-        fragment_spread: &'a FragmentSpread<'a, String>,
+        fragment_spread: &'a FragmentSpread<'static, String>,
     ) {
         if let Some(fragment_type) = self
             .fragment_types
@@ -12,7 +12,7 @@ impl<'a> Visitor<'a> for ProvidedNonNullArguments {
     fn enter_directive(
         &mut self,
         ctx: &mut ValidationContext,
-        directive: &'a Directive<'a, String>,
+        directive: &'a Directive<'static, String>,
     ) {
         if let Some(schema_directive) = ctx.schema.directives.get(&directive.name) {
             for arg in &schema_directive.arguments {
@@ -37,7 +37,7 @@ impl<'a> Visitor<'a> for ProvidedNonNullArguments {
         }
     }
 
-    fn enter_field(&mut self, ctx: &mut ValidationContext, field: &'a Field<'a, String>) {
+    fn enter_field(&mut self, ctx: &mut ValidationContext, field: &'a Field<'static, String>) {
         if let Some(parent_type) = ctx.parent_type() {
             if let Some(target_field) = parent_type.get_field_by_name(&field.name) {
                 for arg in &target_field.arguments {
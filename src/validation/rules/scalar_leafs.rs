@@ -6,7 +6,7 @@ use crate::validation::visitor::{ValidationContext, Visitor};
 pub struct ScalarLeafs;
 
 impl<'a> Visitor<'a> for ScalarLeafs {
-    fn enter_field(&mut self, ctx: &mut ValidationContext, field: &'a Field<'a, String>) {
+    fn enter_field(&mut self, ctx: &mut ValidationContext, field: &'a Field<'static, String>) {
         if let Some(parent_type) = ctx.parent_type() {
             if let Some(target_field) = parent_type.get_field_by_name(&field.name) {
                 if let Some(ty) = ctx
@@ -13,7 +13,7 @@ impl<'a> Visitor<'a> for UniqueArgumentNames<'a> {
     fn enter_directive(
         &mut self,
         _ctx: &mut crate::validation::visitor::ValidationContext,
-        _directive: &'a graphql_parser::schema::Directive<'a, String>,
+        _directive: &'a graphql_parser::schema::Directive<'static, String>,
     ) {
         self.names.clear();
     }
@@ -21,7 +21,7 @@ impl<'a> Visitor<'a> for UniqueArgumentNames<'a> {
     fn enter_field(
         &mut self,
         _ctx: &mut crate::validation::visitor::ValidationContext,
-        _field: &'a graphql_parser::query::Field<'a, String>,
+        _field: &'a graphql_parser::query::Field<'static, String>,
     ) {
         self.names.clear();
     }
@@ -30,7 +30,7 @@ impl<'a> Visitor<'a> for UniqueArgumentNames<'a> {
         &mut self,
         ctx: &mut crate::validation::visitor::ValidationContext,
         arg_name: &'a str,
-        _arg_value: &'a Value<'a, String>,
+        _arg_value: &'a Value<'static, String>,
     ) {
         if !self.names.insert(arg_name) {
             ctx.add_error(format!("{} is already contained.", arg_name), vec![])
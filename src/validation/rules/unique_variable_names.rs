@@ -12,7 +12,7 @@ impl<'a> Visitor<'a> for UniqueVariableNames<'a> {
         &mut self,
         _ctx: &mut ValidationContext<'a>,
         _name: Option<&'a str>,
-        _operation_definition: &'a graphql_parser::query::OperationDefinition<'a, String>,
+        _operation_definition: &'a graphql_parser::query::OperationDefinition<'static, String>,
     ) {
         self.names.clear();
     }
@@ -20,7 +20,7 @@ impl<'a> Visitor<'a> for UniqueVariableNames<'a> {
     fn enter_variable_definition(
         &mut self,
         ctx: &mut ValidationContext,
-        variable_definition: &'a graphql_parser::query::VariableDefinition<'a, String>,
+        variable_definition: &'a graphql_parser::query::VariableDefinition<'static, String>,
     ) {
         if !self.names.insert(&variable_definition.name) {
             ctx.add_error(
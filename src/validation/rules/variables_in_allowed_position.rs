@@ -20,7 +20,7 @@ use crate::{
 pub struct VariablesInAllowedPosition<'a> {
     current_scope: Option<Scope<'a>>,
     variable_usages: HashMap<Scope<'a>, Vec<(&'a str, Pos, GqlValueType)>>,
-    variable_definitions: HashMap<Scope<'a>, Vec<&'a VariableDefinition<'a, String>>>,
+    variable_definitions: HashMap<Scope<'a>, Vec<&'a VariableDefinition<'static, String>>>,
     fragment_spreads: HashMap<Scope<'a>, HashSet<&'a str>>,
 }
 
@@ -28,7 +28,7 @@ impl<'a> VariablesInAllowedPosition<'a> {
     fn collect_incorret_variables(
         &self,
         scope: &Scope<'a>,
-        variable_defs: &[&VariableDefinition<'a, String>],
+        variable_defs: &[&VariableDefinition<'static, String>],
         ctx: &mut ValidationContext<'a>,
         visited: &mut HashSet<Scope<'a>>,
     ) {
@@ -69,7 +69,11 @@ impl<'a> VariablesInAllowedPosition<'a> {
 }
 
 impl<'a> Visitor<'a> for VariablesInAllowedPosition<'a> {
-    fn exit_document(&mut self, ctx: &mut ValidationContext<'a>, _doc: &'a Document<'a, String>) {
+    fn exit_document(
+        &mut self,
+        ctx: &mut ValidationContext<'a>,
+        _doc: &'a Document<'static, String>,
+    ) {
         for (scope, variable_defs) in &self.variable_definitions {
             self.collect_incorret_variables(scope, variable_defs, ctx, &mut HashSet::new());
         }
@@ -79,7 +83,7 @@ impl<'a> Visitor<'a> for VariablesInAllowedPosition<'a> {
         &mut self,
         _ctx: &mut ValidationContext<'a>,
         name: Option<&'a str>,
-        _operation_definition: &'a OperationDefinition<'a, String>,
+        _operation_definition: &'a OperationDefinition<'static, String>,
     ) {
         self.current_scope = Some(Scope::Operation(name))
     }
@@ -88,7 +92,7 @@ impl<'a> Visitor<'a> for VariablesInAllowedPosition<'a> {
         &mut self,
         _ctx: &mut ValidationContext,
         name: &'a str,
-        _fragment_definition: &'a FragmentDefinition<'a, String>,
+        _fragment_definition: &'a FragmentDefinition<'static, String>,
     ) {
         self.current_scope = Some(Scope::Fragment(name))
     }
@@ -96,7 +100,7 @@ impl<'a> Visitor<'a> for VariablesInAllowedPosition<'a> {
     fn enter_variable_definition(
         &mut self,
         _ctx: &mut ValidationContext,
-        variable_definition: &'a VariableDefinition<'a, String>,
+        variable_definition: &'a VariableDefinition<'static, String>,
     ) {
         if let Some(scope) = &self.current_scope {
             self.variable_definitions
@@ -109,7 +113,7 @@ impl<'a> Visitor<'a> for VariablesInAllowedPosition<'a> {
     fn enter_fragment_spread(
         &mut self,
         _ctx: &mut ValidationContext,
-        fragment_spread: &'a FragmentSpread<'a, String>,
+        fragment_spread: &'a FragmentSpread<'static, String>,
     ) {
         if let Some(scope) = &self.current_scope {
             self.fragment_spreads
@@ -123,7 +127,7 @@ impl<'a> Visitor<'a> for VariablesInAllowedPosition<'a> {
         &mut self,
         _ctx: &mut ValidationContext,
         expected_type: &Option<GqlValueType>,
-        value: &'a Value<'a, String>,
+        value: &'a Value<'static, String>,
         pos: Pos,
     ) {
         if let Value::Variable(var_name) = value {
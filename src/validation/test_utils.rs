@@ -12,9 +12,9 @@ use super::visitor::{visit, ValidationContext, ValidationError, Visitor};
 
 #[allow(dead_code)]
 pub(crate) fn validate<'a, V, F>(
-    doc: &'a Document<'a, String>,
+    doc: &'a Document<'static, String>,
     schema: &'a Schema,
-    operation: &'a Operation<'a>,
+    operation: &'a Operation,
     factory: F,
 ) -> Result<(), Vec<ValidationError>>
 where
@@ -53,9 +53,9 @@ macro_rules! check_fails_rule {
 
 #[allow(dead_code)]
 pub(crate) fn assert_passes_rule<'a, V, F>(
-    doc: &'a Document<'a, String>,
+    doc: &'a Document<'static, String>,
     schema: &'a Schema,
-    operation: &'a Operation<'a>,
+    operation: &'a Operation,
     factory: F,
 ) where
     V: Visitor<'a> + 'a,
@@ -74,9 +74,9 @@ pub(crate) fn assert_passes_rule<'a, V, F>(
 
 #[allow(dead_code)]
 pub(crate) fn assert_fails_rule<'a, V, F>(
-    doc: &'a Document<'a, String>,
+    doc: &'a Document<'static, String>,
     schema: &'a Schema,
-    operation: &'a Operation<'a>,
+    operation: &'a Operation,
     factory: F,
 ) where
     V: Visitor<'a> + 'a,
@@ -99,6 +99,6 @@ pub(crate) fn parse_test_query(query_doc: &str) -> Document<'_, String> {
 }
 
 #[allow(dead_code)]
-pub(crate) fn build_test_operation<'a>(doc: &'a Document<'a, String>) -> Operation<'a> {
-    build_operation(doc, None, Default::default()).unwrap()
+pub(crate) fn build_test_operation(doc: &Document<'static, String>) -> Operation {
+    build_operation(doc, None, Default::default(), Default::default()).unwrap()
 }
@@ -1,7 +1,7 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use graphql_parser::{
-    query::{OperationDefinition, TypeCondition},
+    query::{FragmentDefinition, OperationDefinition, Selection, SelectionSet, TypeCondition},
     schema::{Type, Value},
     Pos,
 };
@@ -136,13 +136,13 @@ pub fn get_type_name(ty: &Type<'_, String>) -> String {
     }
 }
 
-pub fn referenced_variables<'a>(value: &'a Value<'a, String>) -> Vec<&'a str> {
+pub fn referenced_variables<'a>(value: &'a Value<'static, String>) -> Vec<&'a str> {
     let mut vars = Vec::new();
     referenced_variables_to_vec(value, &mut vars);
     vars
 }
 
-fn referenced_variables_to_vec<'a>(value: &'a Value<'a, String>, vars: &mut Vec<&'a str>) {
+fn referenced_variables_to_vec<'a>(value: &'a Value<'static, String>, vars: &mut Vec<&'a str>) {
     match value {
         Value::Variable(name) => {
             vars.push(name);
@@ -175,3 +175,123 @@ pub fn get_fragment_definition_on_str(
         None
     }
 }
+
+/// The size of a selection set, including the fragments it spreads.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SelectionCounts {
+    /// The depth of the deepest field, where the fields of the selection set have depth 1.
+    pub depth: usize,
+    /// The position of the deepest field.
+    pub deepest_position: Option<Pos>,
+    pub fields: usize,
+    /// The fields of the selection set itself, not of nested selection sets.
+    pub direct_fields: usize,
+    pub aliases: usize,
+    pub directives: usize,
+}
+
+impl SelectionCounts {
+    fn add(&mut self, other: SelectionCounts) {
+        if other.depth > self.depth {
+            self.depth = other.depth;
+            self.deepest_position = other.deepest_position;
+        }
+        self.fields = self.fields.saturating_add(other.fields);
+        self.direct_fields = self.direct_fields.saturating_add(other.direct_fields);
+        self.aliases = self.aliases.saturating_add(other.aliases);
+        self.directives = self.directives.saturating_add(other.directives);
+    }
+}
+
+/// Counts the fields of a selection set. A fragment is counted every time it is spread,
+/// and the counts of each fragment are cached so that nested spreads are not walked repeatedly.
+pub fn count_selections<'a>(
+    selection_set: &'a SelectionSet<'static, String>,
+    fragments: &'a HashMap<String, FragmentDefinition<'static, String>>,
+    cache: &mut HashMap<&'a str, SelectionCounts>,
+    visiting: &mut HashSet<&'a str>,
+) -> SelectionCounts {
+    let mut counts = SelectionCounts::default();
+
+    for selection in &selection_set.items {
+        match selection {
+            Selection::Field(field) => {
+                let children = count_selections(&field.selection_set, fragments, cache, visiting);
+                counts.add(SelectionCounts {
+                    depth: children.depth + 1,
+                    deepest_position: children.deepest_position.or(Some(field.position)),
+                    fields: children.fields.saturating_add(1),
+                    direct_fields: 1,
+                    aliases: children
+                        .aliases
+                        .saturating_add(usize::from(field.alias.is_some())),
+                    directives: children.directives.saturating_add(field.directives.len()),
+                });
+            }
+            Selection::FragmentSpread(spread) => {
+                let name = spread.fragment_name.as_str();
+                let mut fragment_counts = match cache.get(name) {
+                    Some(fragment_counts) => *fragment_counts,
+                    // Cycles are reported by NoFragmentCycles.
+                    None if visiting.contains(name) => SelectionCounts::default(),
+                    None => match fragments.get(name) {
+                        Some(fragment) => {
+                            visiting.insert(name);
+                            let mut fragment_counts = count_selections(
+                                &fragment.selection_set,
+                                fragments,
+                                cache,
+                                visiting,
+                            );
+                            visiting.remove(name);
+                            fragment_counts.directives = fragment_counts
+                                .directives
+                                .saturating_add(fragment.directives.len());
+                            cache.insert(name, fragment_counts);
+                            fragment_counts
+                        }
+                        None => SelectionCounts::default(),
+                    },
+                };
+                fragment_counts.directives = fragment_counts
+                    .directives
+                    .saturating_add(spread.directives.len());
+                counts.add(fragment_counts);
+            }
+            Selection::InlineFragment(fragment) => {
+                let mut fragment_counts =
+                    count_selections(&fragment.selection_set, fragments, cache, visiting);
+                fragment_counts.directives = fragment_counts
+                    .directives
+                    .saturating_add(fragment.directives.len());
+                counts.add(fragment_counts);
+            }
+        }
+    }
+    counts
+}
+
+/// Counts the fields of an operation. The directives of the operation itself are included.
+pub fn count_operation_selections<'a>(
+    operation_definition: &'a OperationDefinition<'static, String>,
+    fragments: &'a HashMap<String, FragmentDefinition<'static, String>>,
+) -> SelectionCounts {
+    let (selection_set, directives) = match operation_definition {
+        OperationDefinition::SelectionSet(selection_set) => (selection_set, 0),
+        OperationDefinition::Query(query) => (&query.selection_set, query.directives.len()),
+        OperationDefinition::Mutation(mutation) => {
+            (&mutation.selection_set, mutation.directives.len())
+        }
+        OperationDefinition::Subscription(subscription) => {
+            (&subscription.selection_set, subscription.directives.len())
+        }
+    };
+    let mut counts = count_selections(
+        selection_set,
+        fragments,
+        &mut HashMap::new(),
+        &mut HashSet::new(),
+    );
+    counts.directives = counts.directives.saturating_add(directives);
+    counts
+}
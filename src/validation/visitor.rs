@@ -46,7 +46,7 @@ impl From<ValidationError> for GqlError {
 pub struct ValidationContext<'a> {
     pub(crate) schema: &'a Schema,
     pub(crate) errors: Vec<ValidationError>,
-    pub(crate) fragments: &'a HashMap<String, FragmentDefinition<'a, String>>,
+    pub(crate) fragments: &'a HashMap<String, FragmentDefinition<'static, String>>,
     // pub(crate) variables: Option<&'a Variables>,
     pub type_stack: Vec<Option<&'a TypeDefinition>>,
     pub input_type: Vec<Option<GqlValueType>>,
@@ -55,7 +55,7 @@ impl<'a> ValidationContext<'a> {
     pub fn new(
         schema: &'a Schema,
         _variables: Option<&'a Variables>,
-        operation: &'a Operation<'a>,
+        operation: &'a Operation,
     ) -> Self {
         ValidationContext {
             schema,
@@ -134,12 +134,20 @@ where
     A: Visitor<'a> + 'a,
     B: Visitor<'a> + 'a,
 {
-    fn enter_document(&mut self, ctx: &mut ValidationContext<'a>, doc: &'a Document<'a, String>) {
+    fn enter_document(
+        &mut self,
+        ctx: &mut ValidationContext<'a>,
+        doc: &'a Document<'static, String>,
+    ) {
         self.0.enter_document(ctx, doc);
         self.1.enter_document(ctx, doc);
     }
 
-    fn exit_document(&mut self, ctx: &mut ValidationContext<'a>, doc: &'a Document<'a, String>) {
+    fn exit_document(
+        &mut self,
+        ctx: &mut ValidationContext<'a>,
+        doc: &'a Document<'static, String>,
+    ) {
         self.0.exit_document(ctx, doc);
         self.1.exit_document(ctx, doc);
     }
@@ -148,7 +156,7 @@ where
         &mut self,
         ctx: &mut ValidationContext<'a>,
         name: Option<&'a str>,
-        operation_definition: &'a OperationDefinition<'a, String>,
+        operation_definition: &'a OperationDefinition<'static, String>,
     ) {
         self.0
             .enter_operation_definition(ctx, name, operation_definition);
@@ -160,7 +168,7 @@ where
         &mut self,
         ctx: &mut ValidationContext<'a>,
         name: Option<&'a str>,
-        operation_definition: &'a OperationDefinition<'a, String>,
+        operation_definition: &'a OperationDefinition<'static, String>,
     ) {
         self.0
             .exit_operation_definition(ctx, name, operation_definition);
@@ -172,7 +180,7 @@ where
         &mut self,
         ctx: &mut ValidationContext,
         name: &'a str,
-        fragment_definition: &'a FragmentDefinition<'a, String>,
+        fragment_definition: &'a FragmentDefinition<'static, String>,
     ) {
         self.0
             .enter_fragment_definition(ctx, name, fragment_definition);
@@ -184,7 +192,7 @@ where
         &mut self,
         ctx: &mut ValidationContext,
         name: &'a str,
-        fragment_definition: &'a FragmentDefinition<'a, String>,
+        fragment_definition: &'a FragmentDefinition<'static, String>,
     ) {
         self.0
             .exit_fragment_definition(ctx, name, fragment_definition);
@@ -195,7 +203,7 @@ where
     fn enter_selection_set(
         &mut self,
         ctx: &mut ValidationContext<'a>,
-        selection_set: &'a SelectionSet<'a, String>,
+        selection_set: &'a SelectionSet<'static, String>,
     ) {
         self.0.enter_selection_set(ctx, selection_set);
         self.1.enter_selection_set(ctx, selection_set);
@@ -204,7 +212,7 @@ where
     fn exit_selection_set(
         &mut self,
         ctx: &mut ValidationContext,
-        selection_set: &'a SelectionSet<'a, String>,
+        selection_set: &'a SelectionSet<'static, String>,
     ) {
         self.0.exit_selection_set(ctx, selection_set);
         self.1.exit_selection_set(ctx, selection_set);
@@ -213,7 +221,7 @@ where
     fn enter_selection(
         &mut self,
         ctx: &mut ValidationContext,
-        selection: &'a Selection<'a, String>,
+        selection: &'a Selection<'static, String>,
     ) {
         self.0.enter_selection(ctx, selection);
         self.1.enter_selection(ctx, selection);
@@ -222,7 +230,7 @@ where
     fn exit_selection(
         &mut self,
         ctx: &mut ValidationContext,
-        selection: &'a Selection<'a, String>,
+        selection: &'a Selection<'static, String>,
     ) {
         self.0.exit_selection(ctx, selection);
         self.1.exit_selection(ctx, selection);
@@ -231,7 +239,7 @@ where
     fn enter_directive(
         &mut self,
         ctx: &mut ValidationContext,
-        directive: &'a Directive<'a, String>,
+        directive: &'a Directive<'static, String>,
     ) {
         self.0.enter_directive(ctx, directive);
         self.1.enter_directive(ctx, directive);
@@ -240,18 +248,18 @@ where
     fn exit_directive(
         &mut self,
         ctx: &mut ValidationContext,
-        directive: &'a Directive<'a, String>,
+        directive: &'a Directive<'static, String>,
     ) {
         self.0.exit_directive(ctx, directive);
         self.1.exit_directive(ctx, directive);
     }
 
-    fn enter_field(&mut self, ctx: &mut ValidationContext, field: &'a Field<'a, String>) {
+    fn enter_field(&mut self, ctx: &mut ValidationContext, field: &'a Field<'static, String>) {
         self.0.enter_field(ctx, field);
         self.1.enter_field(ctx, field);
     }
 
-    fn exit_field(&mut self, ctx: &mut ValidationContext, field: &'a Field<'a, String>) {
+    fn exit_field(&mut self, ctx: &mut ValidationContext, field: &'a Field<'static, String>) {
         self.0.exit_field(ctx, field);
         self.1.exit_field(ctx, field);
     }
@@ -259,7 +267,7 @@ where
     fn enter_variable_definition(
         &mut self,
         ctx: &mut ValidationContext,
-        variable_definition: &'a VariableDefinition<'a, String>,
+        variable_definition: &'a VariableDefinition<'static, String>,
     ) {
         self.0.enter_variable_definition(ctx, variable_definition);
         self.1.enter_variable_definition(ctx, variable_definition);
@@ -268,7 +276,7 @@ where
     fn exit_variable_definition(
         &mut self,
         ctx: &mut ValidationContext,
-        variable_definition: &'a VariableDefinition<'a, String>,
+        variable_definition: &'a VariableDefinition<'static, String>,
     ) {
         self.0.exit_variable_definition(ctx, variable_definition);
         self.1.exit_variable_definition(ctx, variable_definition);
@@ -277,7 +285,7 @@ where
     fn enter_fragment_spread(
         &mut self,
         ctx: &mut ValidationContext,
-        fragment_spread: &'a FragmentSpread<'a, String>,
+        fragment_spread: &'a FragmentSpread<'static, String>,
     ) {
         self.0.enter_fragment_spread(ctx, fragment_spread);
         self.1.enter_fragment_spread(ctx, fragment_spread);
@@ -286,7 +294,7 @@ where
     fn exit_fragment_spread(
         &mut self,
         ctx: &mut ValidationContext,
-        fragment_spread: &'a FragmentSpread<'a, String>,
+        fragment_spread: &'a FragmentSpread<'static, String>,
     ) {
         self.0.exit_fragment_spread(ctx, fragment_spread);
         self.1.exit_fragment_spread(ctx, fragment_spread);
@@ -295,7 +303,7 @@ where
     fn enter_inline_fragment(
         &mut self,
         ctx: &mut ValidationContext,
-        inline_fragment: &'a InlineFragment<'a, String>,
+        inline_fragment: &'a InlineFragment<'static, String>,
     ) {
         self.0.enter_inline_fragment(ctx, inline_fragment);
         self.1.enter_inline_fragment(ctx, inline_fragment);
@@ -304,7 +312,7 @@ where
     fn exit_inline_fragment(
         &mut self,
         ctx: &mut ValidationContext,
-        inline_fragment: &'a InlineFragment<'a, String>,
+        inline_fragment: &'a InlineFragment<'static, String>,
     ) {
         self.0.exit_inline_fragment(ctx, inline_fragment);
         self.1.exit_inline_fragment(ctx, inline_fragment);
@@ -314,7 +322,7 @@ where
         &mut self,
         ctx: &mut ValidationContext,
         arg_name: &'a str,
-        arg_value: &'a Value<'a, String>,
+        arg_value: &'a Value<'static, String>,
     ) {
         self.0.enter_argument(ctx, arg_name, arg_value);
         self.1.enter_argument(ctx, arg_name, arg_value);
@@ -324,7 +332,7 @@ where
         &mut self,
         ctx: &mut ValidationContext,
         arg_name: &'a str,
-        arg_value: &'a Value<'a, String>,
+        arg_value: &'a Value<'static, String>,
     ) {
         self.0.exit_argument(ctx, arg_name, arg_value);
         self.1.exit_argument(ctx, arg_name, arg_value);
@@ -334,7 +342,7 @@ where
         &mut self,
         ctx: &mut ValidationContext,
         expected_type: &Option<GqlValueType>,
-        value: &'a Value<'a, String>,
+        value: &'a Value<'static, String>,
         pos: Pos,
     ) {
         self.0.enter_input_value(ctx, expected_type, value, pos);
@@ -345,7 +353,7 @@ where
         &mut self,
         ctx: &mut ValidationContext,
         expected_type: &Option<GqlValueType>,
-        value: &'a Value<'a, String>,
+        value: &'a Value<'static, String>,
         pos: Pos,
     ) {
         self.0.exit_input_value(ctx, expected_type, value, pos);
@@ -354,21 +362,30 @@ where
 }
 
 pub trait Visitor<'a> {
-    fn enter_document(&mut self, _ctx: &mut ValidationContext<'a>, _doc: &'a Document<'a, String>) {
+    fn enter_document(
+        &mut self,
+        _ctx: &mut ValidationContext<'a>,
+        _doc: &'a Document<'static, String>,
+    ) {
+    }
+    fn exit_document(
+        &mut self,
+        _ctx: &mut ValidationContext<'a>,
+        _doc: &'a Document<'static, String>,
+    ) {
     }
-    fn exit_document(&mut self, _ctx: &mut ValidationContext<'a>, _doc: &'a Document<'a, String>) {}
     fn enter_operation_definition(
         &mut self,
         _ctx: &mut ValidationContext<'a>,
         _name: Option<&'a str>,
-        _operation_definition: &'a OperationDefinition<'a, String>,
+        _operation_definition: &'a OperationDefinition<'static, String>,
     ) {
     }
     fn exit_operation_definition(
         &mut self,
         _ctx: &mut ValidationContext<'a>,
         _name: Option<&'a str>,
-        _operation_definition: &'a OperationDefinition<'a, String>,
+        _operation_definition: &'a OperationDefinition<'static, String>,
     ) {
     }
 
@@ -376,98 +393,98 @@ pub trait Visitor<'a> {
         &mut self,
         _ctx: &mut ValidationContext,
         _name: &'a str,
-        _fragment_definition: &'a FragmentDefinition<'a, String>,
+        _fragment_definition: &'a FragmentDefinition<'static, String>,
     ) {
     }
     fn exit_fragment_definition(
         &mut self,
         _ctx: &mut ValidationContext,
         _name: &'a str,
-        _fragment_definition: &'a FragmentDefinition<'a, String>,
+        _fragment_definition: &'a FragmentDefinition<'static, String>,
     ) {
     }
 
     fn enter_selection_set(
         &mut self,
         _ctx: &mut ValidationContext<'a>,
-        _selection_set: &'a SelectionSet<'a, String>,
+        _selection_set: &'a SelectionSet<'static, String>,
     ) {
     }
     fn exit_selection_set(
         &mut self,
         _ctx: &mut ValidationContext,
-        _selection_set: &'a SelectionSet<'a, String>,
+        _selection_set: &'a SelectionSet<'static, String>,
     ) {
     }
 
     fn enter_selection(
         &mut self,
         _ctx: &mut ValidationContext,
-        _selection: &'a Selection<'a, String>,
+        _selection: &'a Selection<'static, String>,
     ) {
     }
 
     fn exit_selection(
         &mut self,
         _ctx: &mut ValidationContext,
-        _selection: &'a Selection<'a, String>,
+        _selection: &'a Selection<'static, String>,
     ) {
     }
     fn enter_directive(
         &mut self,
         _ctx: &mut ValidationContext,
-        _directive: &'a Directive<'a, String>,
+        _directive: &'a Directive<'static, String>,
     ) {
     }
     fn exit_directive(
         &mut self,
         _ctx: &mut ValidationContext,
-        _directive: &'a Directive<'a, String>,
+        _directive: &'a Directive<'static, String>,
     ) {
     }
 
-    fn enter_field(&mut self, _ctx: &mut ValidationContext, _field: &'a Field<'a, String>) {}
-    fn exit_field(&mut self, _ctx: &mut ValidationContext, _field: &'a Field<'a, String>) {}
+    fn enter_field(&mut self, _ctx: &mut ValidationContext, _field: &'a Field<'static, String>) {}
+    fn exit_field(&mut self, _ctx: &mut ValidationContext, _field: &'a Field<'static, String>) {}
 
     fn enter_variable_definition(
         &mut self,
         _ctx: &mut ValidationContext,
-        _variable_definition: &'a VariableDefinition<'a, String>,
+        _variable_definition: &'a VariableDefinition<'static, String>,
     ) {
     }
 
     fn exit_variable_definition(
         &mut self,
         _ctx: &mut ValidationContext,
-        _variable_definition: &'a VariableDefinition<'a, String>,
+        _variable_definition: &'a VariableDefinition<'static, String>,
     ) {
     }
 
     fn enter_fragment_spread(
         &mut self,
         _ctx: &mut ValidationContext,
-        _fragment_spread: &'a FragmentSpread<'a, String>,
+        _fragment_spread: &'a FragmentSpread<'static, String>,
     ) {
     }
 
     fn exit_fragment_spread(
         &mut self,
         _ctx: &mut ValidationContext,
-        _fragment_spread: &'a FragmentSpread<'a, String>,
+        _fragment_spread: &'a FragmentSpread<'static, String>,
     ) {
     }
 
     fn enter_inline_fragment(
         &mut self,
         _ctx: &mut ValidationContext,
-        _inline_fragment: &'a InlineFragment<'a, String>,
+        _inline_fragment: &'a InlineFragment<'static, String>,
     ) {
     }
 
     fn exit_inline_fragment(
         &mut self,
         _ctx: &mut ValidationContext,
-        _inline_fragment: &'a InlineFragment<'a, String>,
+        _inline_fragment: &'a InlineFragment<'static, String>,
     ) {
     }
 
@@ -475,14 +492,14 @@ pub trait Visitor<'a> {
         &mut self,
         _ctx: &mut ValidationContext,
         _arg_name: &'a str,
-        _arg_value: &'a Value<'a, String>,
+        _arg_value: &'a Value<'static, String>,
     ) {
     }
     fn exit_argument(
         &mut self,
         _ctx: &mut ValidationContext,
         _arg_name: &'a str,
-        _arg_value: &'a Value<'a, String>,
+        _arg_value: &'a Value<'static, String>,
     ) {
     }
 
@@ -490,7 +507,7 @@ pub trait Visitor<'a> {
         &mut self,
         _ctx: &mut ValidationContext,
         _expected_type: &Option<GqlValueType>,
-        _value: &'a Value<'a, String>,
+        _value: &'a Value<'static, String>,
         _pos: Pos,
     ) {
     }
@@ -499,7 +516,7 @@ pub trait Visitor<'a> {
         &mut self,
         _ctx: &mut ValidationContext,
         _expected_type: &Option<GqlValueType>,
-        _value: &'a Value<'a, String>,
+        _value: &'a Value<'static, String>,
         _pos: Pos,
     ) {
     }
@@ -508,7 +525,7 @@ pub trait Visitor<'a> {
 pub fn visit<'a, T: Visitor<'a>>(
     visitor: &mut T,
     ctx: &mut ValidationContext<'a>,
-    doc: &'a Document<'a, String>,
+    doc: &'a Document<'static, String>,
     operation_name: Option<&'a str>,
 ) {
     visitor.enter_document(ctx, doc);
@@ -519,7 +536,7 @@ pub fn visit<'a, T: Visitor<'a>>(
 fn visit_definitions<'a, T: Visitor<'a>>(
     visitor: &mut T,
     ctx: &mut ValidationContext<'a>,
-    definitions: &'a [Definition<'a, String>],
+    definitions: &'a [Definition<'static, String>],
     operation_name: Option<&'a str>,
 ) {
     for def in definitions {
@@ -550,7 +567,7 @@ fn visit_operation_definition<'a, T: Visitor<'a>>(
     visitor: &mut T,
     ctx: &mut ValidationContext<'a>,
     name: Option<&'a str>,
-    operation_definition: &'a OperationDefinition<'a, String>,
+    operation_definition: &'a OperationDefinition<'static, String>,
 ) {
     visitor.enter_operation_definition(ctx, name, operation_definition);
 
@@ -593,7 +610,7 @@ fn visit_operation_definition<'a, T: Visitor<'a>>(
 fn visit_selection_set<'a, T: Visitor<'a>>(
     visitor: &mut T,
     ctx: &mut ValidationContext<'a>,
-    selection_set: &'a SelectionSet<'a, String>,
+    selection_set: &'a SelectionSet<'static, String>,
 ) {
     if !selection_set.items.is_empty() {
         visitor.enter_selection_set(ctx, selection_set);
@@ -607,7 +624,7 @@ fn visit_selection_set<'a, T: Visitor<'a>>(
 fn visit_selection<'a, T: Visitor<'a>>(
     visitor: &mut T,
     ctx: &mut ValidationContext<'a>,
-    selection: &'a Selection<'a, String>,
+    selection: &'a Selection<'static, String>,
 ) {
     visitor.enter_selection(ctx, selection);
     match selection {
@@ -641,7 +658,7 @@ fn visit_selection<'a, T: Visitor<'a>>(
 fn visit_field<'a, T: Visitor<'a>>(
     visitor: &mut T,
     ctx: &mut ValidationContext<'a>,
-    field: &'a Field<'a, String>,
+    field: &'a Field<'static, String>,
 ) {
     visitor.enter_field(ctx, field);
 
@@ -671,7 +688,7 @@ fn visit_fragment_definition<'a, T: Visitor<'a>>(
     visitor: &mut T,
     ctx: &mut ValidationContext<'a>,
     name: &'a str,
-    fragment_definition: &'a FragmentDefinition<'a, String>,
+    fragment_definition: &'a FragmentDefinition<'static, String>,
 ) {
     visitor.enter_fragment_definition(ctx, name, fragment_definition);
     visit_directives(visitor, ctx, &fragment_definition.directives);
@@ -682,7 +699,7 @@ fn visit_fragment_definition<'a, T: Visitor<'a>>(
 fn visit_fragment_spread<'a, T: Visitor<'a>>(
     visitor: &mut T,
     ctx: &mut ValidationContext<'a>,
-    fragment_spread: &'a FragmentSpread<'a, String>,
+    fragment_spread: &'a FragmentSpread<'static, String>,
 ) {
     visitor.enter_fragment_spread(ctx, fragment_spread);
     visit_directives(visitor, ctx, &fragment_spread.directives);
@@ -692,7 +709,7 @@ fn visit_fragment_spread<'a, T: Visitor<'a>>(
 fn visit_inline_fragment<'a, T: Visitor<'a>>(
     visitor: &mut T,
     ctx: &mut ValidationContext<'a>,
-    inline_fragment: &'a InlineFragment<'a, String>,
+    inline_fragment: &'a InlineFragment<'static, String>,
 ) {
     visitor.enter_inline_fragment(ctx, inline_fragment);
     visit_directives(visitor, ctx, &inline_fragment.directives);
@@ -703,7 +720,7 @@ fn visit_inline_fragment<'a, T: Visitor<'a>>(
 fn visit_directives<'a, T: Visitor<'a>>(
     visitor: &mut T,
     ctx: &mut ValidationContext<'a>,
-    directives: &'a [Directive<'a, String>],
+    directives: &'a [Directive<'static, String>],
 ) {
     for directive in directives {
         visitor.enter_directive(ctx, directive);
@@ -733,7 +750,7 @@ fn visit_directives<'a, T: Visitor<'a>>(
 fn exit_definition<'a, T: Visitor<'a>>(
     visitor: &mut T,
     ctx: &mut ValidationContext<'a>,
-    definition: &'a Definition<'a, String>,
+    definition: &'a Definition<'static, String>,
     operation_name: Option<&'a str>,
 ) {
     match definition {
@@ -747,7 +764,7 @@ fn exit_definition<'a, T: Visitor<'a>>(
 fn visit_variable_definitions<'a, T: Visitor<'a>>(
     visitor: &mut T,
     ctx: &mut ValidationContext<'a>,
-    variable_definitions: &'a [VariableDefinition<'a, String>],
+    variable_definitions: &'a [VariableDefinition<'static, String>],
 ) {
     for def in variable_definitions {
         visitor.enter_variable_definition(ctx, def);
@@ -760,7 +777,7 @@ fn visit_input_value<'a, T: Visitor<'a>>(
     ctx: &mut ValidationContext<'a>,
     pos: Pos,
     expected_type: Option<GqlValueType>,
-    value: &'a Value<'a, String>,
+    value: &'a Value<'static, String>,
 ) {
     visitor.enter_input_value(ctx, &expected_type, value, pos);
     match value {
@@ -1,8 +1,9 @@
 use std::collections::BTreeMap;
 
+use graphql_parser::query::VariableDefinition;
 use serde::{Deserialize, Serialize};
 
-use crate::GqlValue;
+use crate::{input::coerce_input_value, types::schema::Schema, GqlError, GqlValue, GqlValueType};
 
 #[derive(Serialize, Clone, Default, Debug)]
 pub struct Variables(pub BTreeMap<String, GqlValue>);
@@ -17,3 +18,55 @@ impl<'de> Deserialize<'de> for Variables {
         ))
     }
 }
+
+/// Coerces the variables of a request against the variable definitions of the operation.
+/// Default values are applied and the values are converted to the declared types.
+pub(crate) fn coerce_variables(
+    schema: &Schema,
+    variable_definitions: &[VariableDefinition<'_, String>],
+    variables: &Variables,
+) -> Result<Variables, Vec<GqlError>> {
+    let mut coerced_variables = BTreeMap::new();
+    let mut errors = Vec::new();
+
+    for var_def in variable_definitions {
+        let var_type = GqlValueType::from(var_def.var_type.clone());
+        let value = match variables.0.get(&var_def.name) {
+            Some(value) => value.clone(),
+            None => match &var_def.default_value {
+                Some(default_value) => GqlValue::from(default_value.clone()),
+                None => {
+                    if var_type.is_non_null() {
+                        errors.push(GqlError::new(
+                            format!(
+                                "Variable \"${}\" of required type \"{}\" was not provided.",
+                                var_def.name, var_def.var_type
+                            ),
+                            Some(var_def.position),
+                        ));
+                    }
+                    continue;
+                }
+            },
+        };
+
+        match coerce_input_value(schema, &var_type, value) {
+            Ok(value) => {
+                coerced_variables.insert(var_def.name.clone(), value);
+            }
+            Err(reason) => errors.push(GqlError::new(
+                format!(
+                    "Variable \"${}\" got invalid value; {}",
+                    var_def.name, reason
+                ),
+                Some(var_def.position),
+            )),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(Variables(coerced_variables))
+    } else {
+        Err(errors)
+    }
+}
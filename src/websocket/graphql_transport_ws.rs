@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{GqlError, GqlValue, Request, Response};
+
+use super::{ClientMessage, ServerMessage};
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ProtocolClientMessage {
+    ConnectionInit {
+        payload: Option<GqlValue>,
+    },
+    Subscribe {
+        id: String,
+        payload: Request,
+    },
+    Complete {
+        id: String,
+    },
+    Ping {
+        payload: Option<GqlValue>,
+    },
+    Pong {
+        #[allow(dead_code)]
+        payload: Option<GqlValue>,
+    },
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ProtocolServerMessage<'a> {
+    ConnectionAck,
+    Next {
+        id: &'a str,
+        payload: &'a Response,
+    },
+    Error {
+        id: &'a str,
+        payload: &'a [GqlError],
+    },
+    Complete {
+        id: &'a str,
+    },
+    Pong {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        payload: Option<GqlValue>,
+    },
+}
+
+pub(crate) fn decode(message: &[u8]) -> serde_json::Result<ClientMessage> {
+    let message = match serde_json::from_slice(message)? {
+        ProtocolClientMessage::ConnectionInit { payload } => {
+            ClientMessage::ConnectionInit { payload }
+        }
+        ProtocolClientMessage::Subscribe { id, payload } => {
+            ClientMessage::Subscribe { id, payload }
+        }
+        ProtocolClientMessage::Complete { id } => ClientMessage::Complete { id },
+        ProtocolClientMessage::Ping { payload } => ClientMessage::Ping { payload },
+        ProtocolClientMessage::Pong { .. } => ClientMessage::Pong,
+    };
+    Ok(message)
+}
+
+pub(crate) fn encode(message: ServerMessage<'_>) -> String {
+    let message = match message {
+        ServerMessage::ConnectionAck => ProtocolServerMessage::ConnectionAck,
+        ServerMessage::Next { id, payload } => ProtocolServerMessage::Next { id, payload },
+        ServerMessage::Error { id, payload } => ProtocolServerMessage::Error { id, payload },
+        ServerMessage::Complete { id } => ProtocolServerMessage::Complete { id },
+        ServerMessage::Pong { payload } => ProtocolServerMessage::Pong { payload },
+    };
+    serde_json::to_string(&message).unwrap()
+}
@@ -0,0 +1,351 @@
+mod graphql_transport_ws;
+mod subscriptions_transport_ws;
+
+use std::{collections::HashMap, time::Duration};
+
+use futures_util::{
+    future::{BoxFuture, Either},
+    stream::{self, AbortHandle, BoxStream, SelectAll, Stream},
+    Future, FutureExt, StreamExt,
+};
+
+use crate::{
+    executor::{execute_subscription, SubscriptionResponse},
+    Container, Data, GqlError, GqlValue, Request, Response, SelectionSetResolver,
+    SubscriptionResolver,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WsMessage {
+    Text(String),
+    Close(u16, String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WebSocketProtocol {
+    /// The `graphql-transport-ws` protocol implemented by the `graphql-ws` library.
+    GraphQLTransportWs,
+    /// The legacy protocol implemented by the `subscriptions-transport-ws` library.
+    SubscriptionsTransportWs,
+}
+
+impl WebSocketProtocol {
+    pub const ALL: [WebSocketProtocol; 2] = [
+        WebSocketProtocol::GraphQLTransportWs,
+        WebSocketProtocol::SubscriptionsTransportWs,
+    ];
+
+    pub fn sec_websocket_protocol(&self) -> &'static str {
+        match self {
+            WebSocketProtocol::GraphQLTransportWs => "graphql-transport-ws",
+            WebSocketProtocol::SubscriptionsTransportWs => "graphql-ws",
+        }
+    }
+
+    /// Selects the first protocol listed in a `Sec-WebSocket-Protocol` header value that is supported.
+    pub fn from_sec_websocket_protocol(header: &str) -> Option<Self> {
+        header.split(',').map(str::trim).find_map(|name| {
+            Self::ALL
+                .into_iter()
+                .find(|protocol| protocol.sec_websocket_protocol() == name)
+        })
+    }
+
+    fn decode(&self, message: &[u8]) -> serde_json::Result<ClientMessage> {
+        match self {
+            WebSocketProtocol::GraphQLTransportWs => graphql_transport_ws::decode(message),
+            WebSocketProtocol::SubscriptionsTransportWs => {
+                subscriptions_transport_ws::decode(message)
+            }
+        }
+    }
+
+    fn encode(&self, message: ServerMessage<'_>) -> WsMessage {
+        let text = match self {
+            WebSocketProtocol::GraphQLTransportWs => graphql_transport_ws::encode(message),
+            WebSocketProtocol::SubscriptionsTransportWs => {
+                subscriptions_transport_ws::encode(message)
+            }
+        };
+        WsMessage::Text(text)
+    }
+
+    fn close(&self, code: u16, reason: String) -> Vec<WsMessage> {
+        match self {
+            WebSocketProtocol::GraphQLTransportWs => vec![WsMessage::Close(code, reason)],
+            WebSocketProtocol::SubscriptionsTransportWs => vec![
+                WsMessage::Text(subscriptions_transport_ws::connection_error(&reason)),
+                WsMessage::Close(code, reason),
+            ],
+        }
+    }
+}
+
+enum ClientMessage {
+    ConnectionInit { payload: Option<GqlValue> },
+    Subscribe { id: String, payload: Request },
+    Complete { id: String },
+    Ping { payload: Option<GqlValue> },
+    Pong,
+    ConnectionTerminate,
+}
+
+enum ServerMessage<'a> {
+    ConnectionAck,
+    Next {
+        id: &'a str,
+        payload: &'a Response,
+    },
+    Error {
+        id: &'a str,
+        payload: &'a [GqlError],
+    },
+    Complete {
+        id: &'a str,
+    },
+    Pong {
+        payload: Option<GqlValue>,
+    },
+}
+
+pub(crate) type ConnectionInitFn =
+    Box<dyn FnOnce(GqlValue) -> BoxFuture<'static, Result<Data, GqlError>> + Send>;
+
+pub struct WebSocketConnection<Query, Mutation, Subscription>
+where
+    Query: SelectionSetResolver + 'static,
+    Mutation: SelectionSetResolver + 'static,
+    Subscription: SubscriptionResolver + 'static,
+{
+    container: Container<Query, Mutation, Subscription>,
+    protocol: WebSocketProtocol,
+    on_connection_init: ConnectionInitFn,
+    connection_init_timeout: Duration,
+}
+
+impl<Query, Mutation, Subscription> WebSocketConnection<Query, Mutation, Subscription>
+where
+    Query: SelectionSetResolver + 'static,
+    Mutation: SelectionSetResolver + 'static,
+    Subscription: SubscriptionResolver + 'static,
+{
+    pub fn new(
+        container: &Container<Query, Mutation, Subscription>,
+        protocol: WebSocketProtocol,
+    ) -> Self {
+        WebSocketConnection {
+            container: container.clone(),
+            protocol,
+            on_connection_init: Box::new(|_| async { Ok(Data::default()) }.boxed()),
+            connection_init_timeout: Duration::from_secs(3),
+        }
+    }
+
+    pub fn on_connection_init<F, Fut>(mut self, callback: F) -> Self
+    where
+        F: FnOnce(GqlValue) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<Data, GqlError>> + Send + 'static,
+    {
+        self.on_connection_init = Box::new(move |payload| callback(payload).boxed());
+        self
+    }
+
+    /// Closes the connection if the client does not send `connection_init` within the timeout. Defaults to 3 seconds.
+    pub fn connection_init_timeout(mut self, timeout: Duration) -> Self {
+        self.connection_init_timeout = timeout;
+        self
+    }
+
+    pub fn serve<S, T>(self, incoming: S) -> BoxStream<'static, WsMessage>
+    where
+        S: Stream<Item = T> + Send + Unpin + 'static,
+        T: AsRef<[u8]> + Send + 'static,
+    {
+        serve(
+            self.container,
+            self.protocol,
+            self.on_connection_init,
+            self.connection_init_timeout,
+            incoming,
+        )
+    }
+}
+
+type SubscriptionEvent = (String, Option<SubscriptionResponse>);
+
+fn serve<Query, Mutation, Subscription, S, T>(
+    container: Container<Query, Mutation, Subscription>,
+    protocol: WebSocketProtocol,
+    on_connection_init: ConnectionInitFn,
+    connection_init_timeout: Duration,
+    mut incoming: S,
+) -> BoxStream<'static, WsMessage>
+where
+    Query: SelectionSetResolver + 'static,
+    Mutation: SelectionSetResolver + 'static,
+    Subscription: SubscriptionResolver + 'static,
+    S: Stream<Item = T> + Send + Unpin + 'static,
+    T: AsRef<[u8]> + Send + 'static,
+{
+    Box::pin(async_stream::stream! {
+        let mut on_connection_init = Some(on_connection_init);
+        let mut connection_data: Option<Data> = None;
+        let mut subscribers: HashMap<String, AbortHandle> = HashMap::new();
+        let mut events: SelectAll<BoxStream<'static, SubscriptionEvent>> = SelectAll::new();
+        events.push(stream::pending().boxed());
+        let connection_init_timeout = tokio::time::sleep(connection_init_timeout);
+        tokio::pin!(connection_init_timeout);
+
+        loop {
+            let event = tokio::select! {
+                message = incoming.next() => Some(Either::Left(message)),
+                event = events.next() => Some(Either::Right(event)),
+                _ = &mut connection_init_timeout, if on_connection_init.is_some() => None,
+            };
+            let event = match event {
+                Some(Either::Left(Some(message))) => Either::Left(message),
+                Some(Either::Left(None)) => break,
+                Some(Either::Right(Some(event))) => Either::Right(event),
+                Some(Either::Right(None)) => continue,
+                None => {
+                    for message in protocol.close(4408, "Connection initialisation timeout".to_string()) {
+                        yield message;
+                    }
+                    break;
+                }
+            };
+
+            match event {
+                Either::Left(message) => {
+                    let message = match protocol.decode(message.as_ref()) {
+                        Ok(message) => message,
+                        Err(err) => {
+                            for message in protocol.close(4400, format!("Invalid message received: {}", err)) {
+                                yield message;
+                            }
+                            break;
+                        }
+                    };
+
+                    match message {
+                        ClientMessage::ConnectionInit { payload } => {
+                            let callback = match on_connection_init.take() {
+                                Some(callback) => callback,
+                                None => {
+                                    for message in protocol.close(4429, "Too many initialisation requests".to_string()) {
+                                        yield message;
+                                    }
+                                    break;
+                                }
+                            };
+                            match callback(payload.unwrap_or_default()).await {
+                                Ok(data) => {
+                                    connection_data = Some(data);
+                                    yield protocol.encode(ServerMessage::ConnectionAck);
+                                }
+                                Err(err) => {
+                                    for message in protocol.close(4403, err.message) {
+                                        yield message;
+                                    }
+                                    break;
+                                }
+                            }
+                        }
+                        ClientMessage::Subscribe { id, payload } => {
+                            let data = match &connection_data {
+                                Some(data) => data.clone(),
+                                None => {
+                                    for message in protocol.close(4401, "Unauthorized".to_string()) {
+                                        yield message;
+                                    }
+                                    break;
+                                }
+                            };
+                            if subscribers.contains_key(&id) {
+                                for message in protocol.close(4409, format!("Subscriber for {} already exists", id)) {
+                                    yield message;
+                                }
+                                break;
+                            }
+
+                            let mut request = payload;
+                            request.data.merge(data);
+                            let (responses, handle) = stream::abortable(execute_subscription(&container, request));
+                            subscribers.insert(id.clone(), handle);
+                            events.push(
+                                responses
+                                    .map(Some)
+                                    .chain(stream::once(async { None }))
+                                    .map(move |response| (id.clone(), response))
+                                    .boxed(),
+                            );
+                        }
+                        ClientMessage::Complete { id } => {
+                            if let Some(handle) = subscribers.remove(&id) {
+                                handle.abort();
+                            }
+                        }
+                        ClientMessage::Ping { payload } => {
+                            yield protocol.encode(ServerMessage::Pong { payload });
+                        }
+                        ClientMessage::Pong => {}
+                        ClientMessage::ConnectionTerminate => break,
+                    }
+                }
+                Either::Right((id, Some(response))) => {
+                    if !subscribers.contains_key(&id) {
+                        continue;
+                    }
+                    match response {
+                        // A request which fails before its execution ends the subscription with the errors.
+                        SubscriptionResponse::RequestError(response) => {
+                            if let Some(handle) = subscribers.remove(&id) {
+                                handle.abort();
+                            }
+                            yield protocol.encode(ServerMessage::Error { id: &id, payload: &response.errors });
+                        }
+                        SubscriptionResponse::Executed(response) => {
+                            yield protocol.encode(ServerMessage::Next { id: &id, payload: &response });
+                        }
+                    }
+                }
+                Either::Right((id, None)) => {
+                    if subscribers.remove(&id).is_some() {
+                        yield protocol.encode(ServerMessage::Complete { id: &id });
+                    }
+                }
+            }
+        }
+
+        for (_, handle) in subscribers.drain() {
+            handle.abort();
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WebSocketProtocol;
+
+    #[test]
+    fn test_from_sec_websocket_protocol() {
+        assert_eq!(
+            WebSocketProtocol::from_sec_websocket_protocol("graphql-transport-ws"),
+            Some(WebSocketProtocol::GraphQLTransportWs)
+        );
+        assert_eq!(
+            WebSocketProtocol::from_sec_websocket_protocol("graphql-ws"),
+            Some(WebSocketProtocol::SubscriptionsTransportWs)
+        );
+        assert_eq!(
+            WebSocketProtocol::from_sec_websocket_protocol(
+                "unknown, graphql-ws , graphql-transport-ws"
+            ),
+            Some(WebSocketProtocol::SubscriptionsTransportWs)
+        );
+        assert_eq!(
+            WebSocketProtocol::from_sec_websocket_protocol("unknown"),
+            None
+        );
+    }
+}
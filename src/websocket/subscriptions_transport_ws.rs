@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{GqlError, GqlValue, Request, Response};
+
+use super::{ClientMessage, ServerMessage};
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ProtocolClientMessage {
+    ConnectionInit { payload: Option<GqlValue> },
+    Start { id: String, payload: Request },
+    Stop { id: String },
+    ConnectionTerminate,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ProtocolServerMessage<'a> {
+    ConnectionAck,
+    ConnectionError {
+        payload: ConnectionErrorPayload<'a>,
+    },
+    #[serde(rename = "ka")]
+    KeepAlive,
+    Data {
+        id: &'a str,
+        payload: &'a Response,
+    },
+    Error {
+        id: &'a str,
+        payload: &'a GqlError,
+    },
+    Complete {
+        id: &'a str,
+    },
+}
+
+#[derive(Serialize)]
+struct ConnectionErrorPayload<'a> {
+    message: &'a str,
+}
+
+pub(crate) fn decode(message: &[u8]) -> serde_json::Result<ClientMessage> {
+    let message = match serde_json::from_slice(message)? {
+        ProtocolClientMessage::ConnectionInit { payload } => {
+            ClientMessage::ConnectionInit { payload }
+        }
+        ProtocolClientMessage::Start { id, payload } => ClientMessage::Subscribe { id, payload },
+        ProtocolClientMessage::Stop { id } => ClientMessage::Complete { id },
+        ProtocolClientMessage::ConnectionTerminate => ClientMessage::ConnectionTerminate,
+    };
+    Ok(message)
+}
+
+pub(crate) fn encode(message: ServerMessage<'_>) -> String {
+    let message = match message {
+        ServerMessage::ConnectionAck => ProtocolServerMessage::ConnectionAck,
+        ServerMessage::Next { id, payload } => ProtocolServerMessage::Data { id, payload },
+        // The payload of a legacy error is a single error object.
+        ServerMessage::Error { id, payload } => match payload.first() {
+            Some(error) => ProtocolServerMessage::Error { id, payload: error },
+            None => ProtocolServerMessage::Complete { id },
+        },
+        ServerMessage::Complete { id } => ProtocolServerMessage::Complete { id },
+        // The legacy protocol has no ping/pong, keep-alive is the closest equivalent.
+        ServerMessage::Pong { .. } => ProtocolServerMessage::KeepAlive,
+    };
+    serde_json::to_string(&message).unwrap()
+}
+
+pub(crate) fn connection_error(message: &str) -> String {
+    let message = ProtocolServerMessage::ConnectionError {
+        payload: ConnectionErrorPayload { message },
+    };
+    serde_json::to_string(&message).unwrap()
+}
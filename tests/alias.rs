@@ -0,0 +1,111 @@
+use rusty_gql::*;
+
+#[tokio::test]
+pub async fn test_alias() {
+    struct Person {
+        name: String,
+        age: i32,
+    }
+
+    #[GqlType]
+    impl Person {
+        async fn name(&self) -> String {
+            self.name.clone()
+        }
+        async fn age(&self) -> i32 {
+            self.age
+        }
+    }
+
+    struct Query;
+
+    #[GqlType]
+    impl Query {
+        async fn twice_value(&self, value: i32) -> i32 {
+            value * 2
+        }
+        async fn persons(&self) -> Vec<Person> {
+            vec![
+                Person {
+                    name: "Tom".to_string(),
+                    age: 20,
+                },
+                Person {
+                    name: "Mary".to_string(),
+                    age: 28,
+                },
+            ]
+        }
+    }
+    let contents = schema_content("./tests/schemas/test_schema.graphql");
+
+    let container = Container::new(
+        &vec![contents.as_str()],
+        Query,
+        EmptyMutation,
+        EmptySubscription,
+        Default::default(),
+    )
+    .unwrap();
+
+    let query_doc = r#"{ a: twice_value(value: 1) b: twice_value(value: 2) }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let expected_response = r#"{"data":{"a":2,"b":4}}"#;
+    check_gql_response(req, expected_response, &container).await;
+
+    let query_doc = r#"{ twice_value(value: 1) double: twice_value(value: 2) }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let expected_response = r#"{"data":{"twice_value":2,"double":4}}"#;
+    check_gql_response(req, expected_response, &container).await;
+
+    let query_doc = r#"{ a: persons { fullName: name } b: persons { age type: __typename } }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let expected_response = r#"{"data":{"a":[{"fullName":"Tom"},{"fullName":"Mary"}],"b":[{"age":20,"type":"Person"},{"age":28,"type":"Person"}]}}"#;
+    check_gql_response(req, expected_response, &container).await;
+
+    let query_doc = r#"{ a: persons { name } a: persons { age } }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let expected_response = r#"{"data":{"a":[{"name":"Tom","age":20},{"name":"Mary","age":28}]}}"#;
+    check_gql_response(req, expected_response, &container).await;
+}
+
+#[tokio::test]
+pub async fn test_alias_conflict() {
+    struct Query;
+
+    #[GqlType]
+    impl Query {
+        async fn twice_value(&self, value: i32) -> i32 {
+            value * 2
+        }
+        async fn value(&self) -> Option<i32> {
+            Some(1)
+        }
+    }
+    let contents = schema_content("./tests/schemas/test_schema.graphql");
+
+    let container = Container::new(
+        &vec![contents.as_str()],
+        Query,
+        EmptyMutation,
+        EmptySubscription,
+        Default::default(),
+    )
+    .unwrap();
+
+    let query_doc = r#"{ a: twice_value(value: 1) a: twice_value(value: 2) }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let response = execute(&container, req).await;
+    assert_eq!(
+        response.errors[0].message,
+        "Fields a conflict because they have different arguments."
+    );
+
+    let query_doc = r#"{ a: twice_value(value: 1) a: value }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let response = execute(&container, req).await;
+    assert_eq!(
+        response.errors[0].message,
+        "Fields a conflict because twice_value and value are different fields."
+    );
+}
@@ -0,0 +1,68 @@
+use rusty_gql::*;
+
+struct Query;
+
+#[GqlType]
+impl Query {
+    async fn value(&self, value: i32) -> i32 {
+        value
+    }
+}
+
+fn build_container() -> Container<Query, EmptyMutation, EmptySubscription> {
+    let schema = "type Query { value(value: Int!): Int! }";
+    Container::new(
+        &[schema],
+        Query,
+        EmptyMutation,
+        EmptySubscription,
+        Default::default(),
+    )
+    .unwrap()
+}
+
+fn build_batch_request(body: &str) -> BatchRequest {
+    BatchRequest::from_json(body.as_bytes()).unwrap()
+}
+
+#[tokio::test]
+pub async fn test_batch() {
+    let container = build_container();
+
+    let req = build_batch_request(
+        r#"[{"query": "{ value(value: 1) }"}, {"query": "{ unknown }"}, {"query": "{ value(value: 3) }"}]"#,
+    );
+    let response = execute_batch(&container, req).await;
+    assert_eq!(
+        serde_json::to_string(&response).unwrap(),
+        r#"[{"data":{"value":1}},{"data":null,"errors":[{"message":"Unknown field \"unknown\" on type \"Query\"","locations":[{"line":1,"column":3}],"path":[],"extensions":null}]},{"data":{"value":3}}]"#
+    );
+    assert!(response.is_error());
+
+    let req = build_batch_request(r#"{"query": "{ value(value: 1) }"}"#);
+    let response = execute_batch(&container, req).await;
+    assert_eq!(
+        serde_json::to_string(&response).unwrap(),
+        r#"{"data":{"value":1}}"#
+    );
+}
+
+#[tokio::test]
+pub async fn test_max_batch_size() {
+    let container = build_container().max_batch_size(2);
+
+    let req = build_batch_request(
+        r#"[{"query": "{ value(value: 1) }"}, {"query": "{ value(value: 2) }"}]"#,
+    );
+    let response = execute_batch(&container, req).await;
+    assert!(response.is_ok());
+
+    let req = build_batch_request(
+        r#"[{"query": "{ value(value: 1) }"}, {"query": "{ value(value: 2) }"}, {"query": "{ value(value: 3) }"}]"#,
+    );
+    let response = execute_batch(&container, req).await;
+    assert_eq!(
+        serde_json::to_string(&response).unwrap(),
+        r#"{"data":null,"errors":[{"message":"Batch has 3 requests, which exceeds the maximum batch size of 2.","locations":[],"path":[],"extensions":null}]}"#
+    );
+}
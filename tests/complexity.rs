@@ -0,0 +1,188 @@
+use std::collections::BTreeMap;
+
+use rusty_gql::*;
+
+#[derive(Clone)]
+struct Person {
+    name: String,
+}
+
+#[GqlType]
+impl Person {
+    async fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    async fn friends(&self, first: Option<i32>) -> Vec<Person> {
+        persons(first.unwrap_or(1))
+    }
+}
+
+fn persons(count: i32) -> Vec<Person> {
+    (0..count)
+        .map(|i| Person {
+            name: format!("person{}", i),
+        })
+        .collect()
+}
+
+struct Query;
+
+#[GqlType]
+impl Query {
+    async fn persons(&self, first: Option<i32>) -> Vec<Person> {
+        persons(first.unwrap_or(1))
+    }
+
+    async fn search(&self, limit: Option<i32>) -> Vec<Person> {
+        persons(limit.unwrap_or(5))
+    }
+
+    async fn expensive(&self) -> Option<String> {
+        Some("expensive".to_string())
+    }
+}
+
+fn build_container(max_complexity: usize) -> Container<Query, EmptyMutation, EmptySubscription> {
+    build_container_without_limits().max_complexity(max_complexity)
+}
+
+fn build_container_without_limits() -> Container<Query, EmptyMutation, EmptySubscription> {
+    let contents = schema_content("./tests/schemas/complexity.graphql");
+    Container::new(
+        &vec![contents.as_str()],
+        Query,
+        EmptyMutation,
+        EmptySubscription,
+        Default::default(),
+    )
+    .unwrap()
+}
+
+async fn complexity(
+    container: &Container<Query, EmptyMutation, EmptySubscription>,
+    req: Request,
+) -> GqlValue {
+    let response = execute(container, req).await;
+    assert!(response.is_ok(), "{:?}", response.errors);
+    response.extensions["complexity"].clone()
+}
+
+#[tokio::test]
+pub async fn test_complexity() {
+    let container = build_container(100);
+
+    // friends: (1 + 1) * 2, persons: (1 + 1 + 4) * 3, expensive: 10
+    let query_doc = r#"{ persons(first: 3) { name friends(first: 2) { name } } expensive }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    assert_eq!(complexity(&container, req).await, GqlValue::from(28));
+
+    // (2 + 1) * the default value of limit
+    let query_doc = r#"{ search { name } }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    assert_eq!(complexity(&container, req).await, GqlValue::from(15));
+
+    let query_doc = r#"query Search($limit: Int) { search(limit: $limit) { ...PersonFragment } } fragment PersonFragment on Person { name }"#;
+    let variables = Variables(BTreeMap::from([("limit".to_string(), GqlValue::from(2))]));
+    let req = build_test_request(query_doc, None, variables);
+    assert_eq!(complexity(&container, req).await, GqlValue::from(6));
+
+    // The fields excluded by `@skip` and `@include` cost nothing.
+    let query_doc = r#"query Persons($skip: Boolean!) { persons(first: 3) { name friends(first: 2) @skip(if: $skip) { name } } expensive @include(if: false) }"#;
+    let variables = Variables(BTreeMap::from([(
+        "skip".to_string(),
+        GqlValue::Boolean(true),
+    )]));
+    let req = build_test_request(query_doc, None, variables);
+    assert_eq!(complexity(&container, req).await, GqlValue::from(6));
+}
+
+#[tokio::test]
+pub async fn test_max_complexity() {
+    let container = build_container(20);
+
+    let query_doc = r#"{ persons(first: 3) { name friends(first: 2) { name } } expensive }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let expected_response = r#"{"data":null,"errors":[{"message":"Operation exceeds the maximum complexity of 20.","locations":[{"line":1,"column":1}],"path":[],"extensions":null}]}"#;
+    check_gql_response(req, expected_response, &container).await;
+}
+
+#[tokio::test]
+pub async fn test_complexity_of_nested_fragments() {
+    let container = build_container(5000);
+
+    // Each fragment spreads the next one twice: F0 costs 2^11 - 1.
+    let mut query_doc = "{ persons { ...F0 } }".to_string();
+    for i in 0..10 {
+        query_doc.push_str(&format!(
+            " fragment F{} on Person {{ friends {{ ...F{} ...F{} }} }}",
+            i,
+            i + 1,
+            i + 1
+        ));
+    }
+    query_doc.push_str(" fragment F10 on Person { name }");
+    let req = build_test_request(&query_doc, None, Default::default());
+    assert_eq!(complexity(&container, req).await, GqlValue::from(2048));
+
+    let container = build_container(1000);
+    let req = build_test_request(&query_doc, None, Default::default());
+    let response = execute(&container, req).await;
+    assert_eq!(
+        response.errors[0].message,
+        "Operation exceeds the maximum complexity of 1000."
+    );
+}
+
+#[tokio::test]
+pub async fn test_query_limits() {
+    let container = build_container_without_limits()
+        .max_depth(2)
+        .max_aliases(1)
+        .max_fields(4)
+        .max_root_fields(1)
+        .max_directives(1);
+
+    let query_doc = r#"{ persons { name } }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let expected_response = r#"{"data":{"persons":[{"name":"person0"}]}}"#;
+    check_gql_response(req, expected_response, &container).await;
+
+    let query_doc = r#"{ persons { ...Friends } }
+fragment Friends on Person { friends { name } }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let expected_response = r#"{"data":null,"errors":[{"message":"Operation has depth 3, which exceeds the maximum depth of 2.","locations":[{"line":2,"column":40}],"path":[],"extensions":null}]}"#;
+    check_gql_response(req, expected_response, &container).await;
+
+    let query_doc = r#"{ first: persons { name } second: expensive }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let response = execute(&container, req).await;
+    let messages = response
+        .errors
+        .iter()
+        .map(|error| error.message.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec![
+            "Operation has 2 root fields, which exceeds the maximum of 1 root fields.",
+            "Operation has 2 aliases, which exceeds the maximum of 1 aliases.",
+        ]
+    );
+
+    let query_doc = r#"{ persons { name @include(if: true) friends { name id: name } } }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let response = execute(&container, req).await;
+    let messages = response
+        .errors
+        .iter()
+        .map(|error| error.message.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec![
+            "Operation has 5 fields, which exceeds the maximum of 4 fields.",
+            "Operation has depth 3, which exceeds the maximum depth of 2.",
+        ]
+    );
+}
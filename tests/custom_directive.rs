@@ -98,6 +98,7 @@ pub async fn test_directive() {
 
     let query_doc = r#"{ person(id: 1) {name age} }"#;
     let req = build_test_request(query_doc, None, Default::default());
-    let expected_response = r#"{"data":{"person":{"age":20,"name":null}}}"#;
+    // `name` is non-null, so the null returned by the directive propagates to `person`.
+    let expected_response = r#"{"data":{"person":null},"errors":[{"message":"Cannot return null for non-nullable field Person.name","locations":[{"line":1,"column":18}],"path":["person","name"],"extensions":null}]}"#;
     check_gql_response(req, expected_response, &container).await;
 }
@@ -0,0 +1,79 @@
+use rusty_gql::*;
+
+struct Pool {
+    name: String,
+}
+
+struct CurrentUser(String);
+
+struct Query;
+
+#[GqlType]
+impl Query {
+    async fn value(&self, ctx: &Context<'_>) -> Result<String, GqlError> {
+        let pool = ctx.data::<Pool>()?;
+        let user = ctx.data::<CurrentUser>()?;
+        Ok(format!("{}:{}", pool.name, user.0))
+    }
+
+    async fn user(&self, ctx: &Context<'_>) -> Option<String> {
+        ctx.data_opt::<CurrentUser>().map(|user| user.0.clone())
+    }
+}
+
+fn build_container() -> Container<Query, EmptyMutation, EmptySubscription> {
+    let mut data = Data::default();
+    data.insert(Pool {
+        name: "pool".to_string(),
+    });
+    data.insert(CurrentUser("anonymous".to_string()));
+
+    let contents = schema_content("./tests/schemas/data.graphql");
+    Container::new_with_data(
+        &vec![contents.as_str()],
+        Query,
+        EmptyMutation,
+        EmptySubscription,
+        Default::default(),
+        data,
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+pub async fn test_schema_data() {
+    let container = build_container();
+
+    let req = build_test_request("{ value }", None, Default::default());
+    let expected_response = r#"{"data":{"value":"pool:anonymous"}}"#;
+    check_gql_response(req, expected_response, &container).await;
+}
+
+#[tokio::test]
+pub async fn test_request_data() {
+    let container = build_container();
+
+    let req = build_test_request("{ value user }", None, Default::default())
+        .data(CurrentUser("tom".to_string()));
+    let expected_response = r#"{"data":{"value":"pool:tom","user":"tom"}}"#;
+    check_gql_response(req, expected_response, &container).await;
+}
+
+#[tokio::test]
+pub async fn test_missing_data() {
+    let contents = schema_content("./tests/schemas/data.graphql");
+    let container = Container::new(
+        &vec![contents.as_str()],
+        Query,
+        EmptyMutation,
+        EmptySubscription,
+        Default::default(),
+    )
+    .unwrap();
+
+    let req = build_test_request("{ value }", None, Default::default());
+    let res = execute(&container, req).await;
+    assert!(res.errors[0]
+        .message
+        .contains("Data of type data::Pool does not exist"));
+}
@@ -0,0 +1,73 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use rusty_gql::*;
+
+struct NameLoader(Arc<Mutex<Vec<Vec<i32>>>>);
+
+#[async_trait::async_trait]
+impl Loader<i32, String> for NameLoader {
+    async fn load(&self, keys: &[i32]) -> HashMap<i32, String> {
+        let mut batch = keys.to_vec();
+        batch.sort_unstable();
+        self.0.lock().unwrap().push(batch);
+        keys.iter()
+            .map(|id| (*id, format!("person{}", id)))
+            .collect()
+    }
+}
+
+struct Person {
+    id: i32,
+}
+
+#[GqlType]
+impl Person {
+    async fn id(&self) -> i32 {
+        self.id
+    }
+
+    async fn name(&self, ctx: &Context<'_>) -> Result<Option<String>, GqlError> {
+        let loader = ctx.data::<DataLoader<i32, String>>()?;
+        Ok(loader.load_one(self.id).await)
+    }
+
+    async fn best_friend(&self) -> Option<Person> {
+        Some(Person {
+            id: (self.id + 1) % 3,
+        })
+    }
+}
+
+struct Query;
+
+#[GqlType]
+impl Query {
+    async fn persons(&self) -> Vec<Person> {
+        (0..3).map(|id| Person { id }).collect()
+    }
+}
+
+#[tokio::test]
+pub async fn test_dataloader() {
+    let contents = schema_content("./tests/schemas/dataloader.graphql");
+    let container = Container::new(
+        &vec![contents.as_str()],
+        Query,
+        EmptyMutation,
+        EmptySubscription,
+        Default::default(),
+    )
+    .unwrap();
+
+    let batches = Arc::new(Mutex::new(Vec::new()));
+    let query_doc = r#"{ persons { name best_friend { name } } }"#;
+    let req = build_test_request(query_doc, None, Default::default())
+        .data(DataLoader::new(NameLoader(batches.clone())));
+    let expected_response = r#"{"data":{"persons":[{"name":"person0","best_friend":{"name":"person1"}},{"name":"person1","best_friend":{"name":"person2"}},{"name":"person2","best_friend":{"name":"person0"}}]}}"#;
+    check_gql_response(req, expected_response, &container).await;
+
+    assert_eq!(*batches.lock().unwrap(), vec![vec![0, 1, 2]]);
+}
@@ -0,0 +1,160 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+
+use graphql_parser::query::Document;
+use rusty_gql::*;
+
+struct Person {
+    name: String,
+}
+
+#[GqlType]
+impl Person {
+    async fn name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+struct Query;
+
+#[GqlType]
+impl Query {
+    async fn persons(&self, first: Option<i32>) -> Vec<Person> {
+        (0..first.unwrap_or(1))
+            .map(|i| Person {
+                name: format!("person{}", i),
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Default)]
+struct PhaseLog(Arc<Mutex<Vec<&'static str>>>);
+
+impl PhaseLog {
+    fn take(&self) -> Vec<&'static str> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+impl ExtensionFactory for PhaseLog {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(self.clone())
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for PhaseLog {
+    async fn parse_query<'a>(
+        &self,
+        _ctx: &ExtensionContext<'_>,
+        next: NextFut<'_, Result<Document<'a, String>, GqlError>>,
+    ) -> Result<Document<'a, String>, GqlError> {
+        self.0.lock().unwrap().push("parse");
+        next.await
+    }
+
+    async fn validation(
+        &self,
+        _ctx: &ExtensionContext<'_>,
+        next: NextFut<'_, Result<ValidationResult, Vec<GqlError>>>,
+    ) -> Result<ValidationResult, Vec<GqlError>> {
+        self.0.lock().unwrap().push("validation");
+        next.await
+    }
+}
+
+fn first(first: i32) -> Variables {
+    Variables(BTreeMap::from([(
+        "first".to_string(),
+        GqlValue::from(first),
+    )]))
+}
+
+fn build_container(log: &PhaseLog) -> Container<Query, EmptyMutation, EmptySubscription> {
+    let contents = schema_content("./tests/schemas/document_cache.graphql");
+    Container::new(
+        &vec![contents.as_str()],
+        Query,
+        EmptyMutation,
+        EmptySubscription,
+        Default::default(),
+    )
+    .unwrap()
+    .extension(log.clone())
+    .document_cache(10)
+}
+
+#[tokio::test]
+pub async fn test_document_cache() {
+    let log = PhaseLog::default();
+    let container = build_container(&log);
+
+    let query = r#"query GetPersons($first: Int) { persons(first: $first) { name } }"#;
+    let req = build_test_request(query, None, first(1));
+    let expected_response = r#"{"data":{"persons":[{"name":"person0"}]}}"#;
+    check_gql_response(req, expected_response, &container).await;
+    assert_eq!(log.take(), vec!["parse", "validation"]);
+
+    let req = build_test_request(query, None, first(2));
+    let expected_response = r#"{"data":{"persons":[{"name":"person0"},{"name":"person1"}]}}"#;
+    check_gql_response(req, expected_response, &container).await;
+    assert!(log.take().is_empty());
+}
+
+#[tokio::test]
+pub async fn test_document_cache_per_operation() {
+    let log = PhaseLog::default();
+    let container = build_container(&log);
+
+    let query = r#"query A { persons { name } } query B { persons(first: 2) { name } }"#;
+    let req = build_test_request(query, Some("A".to_string()), Default::default());
+    let expected_response = r#"{"data":{"persons":[{"name":"person0"}]}}"#;
+    check_gql_response(req, expected_response, &container).await;
+    assert_eq!(log.take(), vec!["parse", "validation"]);
+
+    // The document is cached, but operation B has not been validated yet.
+    let req = build_test_request(query, Some("B".to_string()), Default::default());
+    let expected_response = r#"{"data":{"persons":[{"name":"person0"},{"name":"person1"}]}}"#;
+    check_gql_response(req, expected_response, &container).await;
+    assert_eq!(log.take(), vec!["validation"]);
+}
+
+#[tokio::test]
+pub async fn test_document_cache_invalid_query() {
+    let log = PhaseLog::default();
+    let container = build_container(&log);
+
+    let query = r#"{ persons { unknown } }"#;
+    for _ in 0..2 {
+        let req = build_test_request(query, None, Default::default());
+        let response = execute(&container, req).await;
+        assert_eq!(
+            response.errors[0].message,
+            r#"Unknown field "unknown" on type "Person""#
+        );
+    }
+    // The errors of the validation are cached as well.
+    assert_eq!(log.take(), vec!["parse", "validation"]);
+}
+
+#[tokio::test]
+pub async fn test_document_cache_complexity() {
+    let log = PhaseLog::default();
+    let container = build_container(&log).max_complexity(5);
+
+    let query = r#"query GetPersons($first: Int) { persons(first: $first) { name } }"#;
+    let req = build_test_request(query, None, first(2));
+    let response = execute(&container, req).await;
+    assert!(!response.is_error());
+
+    // The complexity depends on the variables, so it is checked for every request.
+    let req = build_test_request(query, None, first(10));
+    let response = execute(&container, req).await;
+    assert_eq!(
+        response.errors[0].message,
+        "Operation exceeds the maximum complexity of 5."
+    );
+}
@@ -33,3 +33,41 @@ pub async fn test_enum() {
     let expected_response = r#"{"data":{"enum_value":"Value0"}}"#;
     check_gql_response(req, expected_response, &container).await;
 }
+
+#[tokio::test]
+pub async fn test_enum_argument_default_value() {
+    struct Query;
+
+    #[derive(GqlEnum)]
+    enum SampleEnum {
+        Value0,
+        Value1,
+    }
+
+    #[GqlType]
+    impl Query {
+        async fn enum_arg(&self, value: Option<SampleEnum>) -> Option<SampleEnum> {
+            value
+        }
+    }
+    let contents = schema_content("./tests/schemas/enum.graphql");
+
+    let container = Container::new(
+        &vec![contents.as_str()],
+        Query,
+        EmptyMutation,
+        EmptySubscription,
+        Default::default(),
+    )
+    .unwrap();
+
+    let query_doc = r#"{ enum_arg }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let expected_response = r#"{"data":{"enum_arg":"Value1"}}"#;
+    check_gql_response(req, expected_response, &container).await;
+
+    let query_doc = r#"{ enum_arg(value: Value0) }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let expected_response = r#"{"data":{"enum_arg":"Value0"}}"#;
+    check_gql_response(req, expected_response, &container).await;
+}
@@ -0,0 +1,342 @@
+use std::sync::{Arc, Mutex};
+
+use futures_util::{stream, Stream, StreamExt};
+use graphql_parser::query::Document;
+use rusty_gql::*;
+
+struct Person {
+    name: String,
+}
+
+#[GqlType]
+impl Person {
+    async fn name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+struct Query;
+
+#[GqlType]
+impl Query {
+    async fn persons(&self) -> Vec<Person> {
+        vec![Person {
+            name: "Alice".to_string(),
+        }]
+    }
+}
+
+struct Subscription;
+
+#[GqlType]
+impl Subscription {
+    async fn persons(&self) -> impl Stream<Item = Person> {
+        stream::iter(vec![
+            Person {
+                name: "Alice".to_string(),
+            },
+            Person {
+                name: "Bob".to_string(),
+            },
+        ])
+    }
+}
+
+struct LogExtension {
+    name: &'static str,
+    log: Arc<Mutex<Vec<String>>>,
+}
+
+impl LogExtension {
+    fn push(&self, entry: String) {
+        self.log
+            .lock()
+            .unwrap()
+            .push(format!("{}:{}", self.name, entry));
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for LogExtension {
+    async fn request(&self, _ctx: &ExtensionContext<'_>, next: NextFut<'_, Response>) -> Response {
+        self.push("request".to_string());
+        let mut response = next.await;
+        response
+            .extensions
+            .insert(self.name.to_string(), GqlValue::Boolean(true));
+        response
+    }
+
+    async fn parse_query<'a>(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        next: NextFut<'_, Result<Document<'a, String>, GqlError>>,
+    ) -> Result<Document<'a, String>, GqlError> {
+        self.push(format!("parse {}", ctx.query));
+        next.await
+    }
+
+    async fn validation(
+        &self,
+        _ctx: &ExtensionContext<'_>,
+        next: NextFut<'_, Result<ValidationResult, Vec<GqlError>>>,
+    ) -> Result<ValidationResult, Vec<GqlError>> {
+        self.push("validation".to_string());
+        next.await
+    }
+
+    async fn execute(&self, _ctx: &ExtensionContext<'_>, next: NextFut<'_, Response>) -> Response {
+        self.push("execute".to_string());
+        next.await
+    }
+
+    async fn resolve_field(
+        &self,
+        ctx: &Context<'_>,
+        parent_type: &str,
+        next: ResolveFut<'_>,
+    ) -> ResolverResult<Option<GqlValue>> {
+        let path = ctx
+            .path
+            .iter()
+            .map(|segment| segment.to_string())
+            .collect::<Vec<_>>()
+            .join(".");
+        self.push(format!("resolve {} {}", parent_type, path));
+        next.await
+    }
+}
+
+struct LogExtensionFactory {
+    name: &'static str,
+    log: Arc<Mutex<Vec<String>>>,
+}
+
+impl ExtensionFactory for LogExtensionFactory {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(LogExtension {
+            name: self.name,
+            log: self.log.clone(),
+        })
+    }
+}
+
+#[tokio::test]
+pub async fn test_extension_hooks() {
+    let contents = schema_content("./tests/schemas/extension.graphql");
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let container = Container::new(
+        &vec![contents.as_str()],
+        Query,
+        EmptyMutation,
+        EmptySubscription,
+        Default::default(),
+    )
+    .unwrap()
+    .extension(LogExtensionFactory {
+        name: "first",
+        log: log.clone(),
+    })
+    .extension(LogExtensionFactory {
+        name: "second",
+        log: log.clone(),
+    });
+
+    let query_doc = r#"{ persons { name } }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let expected_response =
+        r#"{"data":{"persons":[{"name":"Alice"}]},"extensions":{"second":true,"first":true}}"#;
+    check_gql_response(req, expected_response, &container).await;
+
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec![
+            "first:request",
+            "second:request",
+            "first:parse { persons { name } }",
+            "second:parse { persons { name } }",
+            "first:validation",
+            "second:validation",
+            "first:execute",
+            "second:execute",
+            "first:resolve Query persons",
+            "second:resolve Query persons",
+            "first:resolve Person persons.0.name",
+            "second:resolve Person persons.0.name",
+        ]
+    );
+}
+
+#[tokio::test]
+pub async fn test_extension_on_error() {
+    let contents = schema_content("./tests/schemas/extension.graphql");
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let container = Container::new(
+        &vec![contents.as_str()],
+        Query,
+        EmptyMutation,
+        EmptySubscription,
+        Default::default(),
+    )
+    .unwrap()
+    .extension(LogExtensionFactory {
+        name: "first",
+        log: log.clone(),
+    });
+
+    let query_doc = r#"{ unknown }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let response = execute(&container, req).await;
+    assert!(response.is_error());
+    assert_eq!(
+        response.extensions.get("first"),
+        Some(&GqlValue::Boolean(true))
+    );
+
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec![
+            "first:request",
+            "first:parse { unknown }",
+            "first:validation"
+        ]
+    );
+}
+
+#[tokio::test]
+pub async fn test_extension_hooks_on_subscription() {
+    let contents = schema_content("./tests/schemas/extension.graphql");
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let container = Container::new(
+        &vec![contents.as_str()],
+        Query,
+        EmptyMutation,
+        Subscription,
+        Default::default(),
+    )
+    .unwrap()
+    .extension(LogExtensionFactory {
+        name: "first",
+        log: log.clone(),
+    });
+
+    let query_doc = r#"subscription { persons { name } }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let responses = execute_stream(&container, req)
+        .map(|res| serde_json::to_string(&res).unwrap())
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(
+        responses,
+        vec![
+            r#"{"data":{"persons":{"name":"Alice"}},"extensions":{"first":true}}"#,
+            r#"{"data":{"persons":{"name":"Bob"}},"extensions":{"first":true}}"#,
+        ]
+    );
+
+    // The resolvers of an event run before its response is passed through the hooks.
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec![
+            "first:parse subscription { persons { name } }",
+            "first:validation",
+            "first:resolve Person persons.name",
+            "first:request",
+            "first:execute",
+            "first:resolve Person persons.name",
+            "first:request",
+            "first:execute",
+        ]
+    );
+}
+
+#[tokio::test]
+pub async fn test_apollo_tracing() {
+    let contents = schema_content("./tests/schemas/extension.graphql");
+    let container = Container::new(
+        &vec![contents.as_str()],
+        Query,
+        EmptyMutation,
+        EmptySubscription,
+        Default::default(),
+    )
+    .unwrap()
+    .extension(ApolloTracing);
+
+    let query_doc = r#"{ persons { name } }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let response = serde_json::to_value(execute(&container, req).await).unwrap();
+    let tracing = &response["extensions"]["tracing"];
+
+    assert_eq!(tracing["version"], 1);
+    assert!(tracing["startTime"].is_string());
+    assert!(tracing["endTime"].is_string());
+    assert!(tracing["duration"].is_u64());
+    assert!(tracing["parsing"]["startOffset"].is_u64());
+    assert!(tracing["parsing"]["duration"].is_u64());
+    assert!(tracing["validation"]["startOffset"].is_u64());
+    assert!(tracing["validation"]["duration"].is_u64());
+
+    let resolvers = tracing["execution"]["resolvers"].as_array().unwrap();
+    let fields = resolvers
+        .iter()
+        .map(|resolver| {
+            assert!(resolver["startOffset"].is_u64());
+            assert!(resolver["duration"].is_u64());
+            (
+                resolver["path"].clone(),
+                resolver["parentType"].as_str().unwrap(),
+                resolver["fieldName"].as_str().unwrap(),
+                resolver["returnType"].as_str().unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        fields,
+        vec![
+            (
+                serde_json::json!(["persons"]),
+                "Query",
+                "persons",
+                "[Person!]!"
+            ),
+            (
+                serde_json::json!(["persons", 0, "name"]),
+                "Person",
+                "name",
+                "String!"
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+pub async fn test_apollo_tracing_on_subscription() {
+    let contents = schema_content("./tests/schemas/extension.graphql");
+    let container = Container::new(
+        &vec![contents.as_str()],
+        Query,
+        EmptyMutation,
+        Subscription,
+        Default::default(),
+    )
+    .unwrap()
+    .extension(ApolloTracing);
+
+    let query_doc = r#"subscription { persons { name } }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let responses = execute_stream(&container, req)
+        .map(|res| serde_json::to_value(res).unwrap())
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(responses.len(), 2);
+
+    // Each event has only the resolvers of its own execution.
+    for response in &responses {
+        let resolvers = response["extensions"]["tracing"]["execution"]["resolvers"]
+            .as_array()
+            .unwrap();
+        assert_eq!(resolvers.len(), 1);
+        assert_eq!(resolvers[0]["path"], serde_json::json!(["persons", "name"]));
+    }
+}
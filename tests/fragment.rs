@@ -50,7 +50,7 @@ async fn test_inline_framgnet() {
 
     let query = r#"{ person(id: 1) { ... on Person {name, age, description} } }"#;
     let req = build_test_request(query, None, Default::default());
-    let expected = r#"{"data":{"person":{"age":20,"description":"description","name":"Tom"}}}"#;
+    let expected = r#"{"data":{"person":{"name":"Tom","age":20,"description":"description"}}}"#;
     check_gql_response(req, expected, &container).await;
 }
 
@@ -113,6 +113,6 @@ async fn test_framgnet_spread() {
     }
     "#;
     let req = build_test_request(query, None, Default::default());
-    let expected = r#"{"data":{"person":{"age":20,"name":"Tom"}}}"#;
+    let expected = r#"{"data":{"person":{"name":"Tom","age":20}}}"#;
     check_gql_response(req, expected, &container).await;
 }
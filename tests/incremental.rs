@@ -0,0 +1,206 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use futures_util::StreamExt;
+use rusty_gql::*;
+
+#[derive(Clone)]
+struct Character {
+    name: String,
+    friends: Vec<String>,
+}
+
+#[GqlType]
+impl Character {
+    async fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    async fn friends(&self) -> Vec<Character> {
+        self.friends
+            .iter()
+            .map(|name| Character {
+                name: name.clone(),
+                friends: Vec::new(),
+            })
+            .collect()
+    }
+}
+
+fn luke() -> Character {
+    Character {
+        name: "Luke".to_string(),
+        friends: vec!["Han".to_string(), "Leia".to_string()],
+    }
+}
+
+#[derive(Default)]
+struct Query {
+    calls: Arc<AtomicUsize>,
+}
+
+#[GqlType]
+impl Query {
+    async fn hero(&self) -> Character {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        luke()
+    }
+
+    async fn heroes(&self) -> Vec<Character> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        vec![
+            luke(),
+            Character {
+                name: "Han".to_string(),
+                friends: vec!["Chewbacca".to_string()],
+            },
+        ]
+    }
+
+    async fn numbers(&self) -> Vec<i32> {
+        vec![1, 2, 3, 4]
+    }
+}
+
+struct Mutation;
+
+#[GqlType]
+impl Mutation {
+    async fn create_hero(&self, name: String) -> Character {
+        Character {
+            name,
+            friends: Vec::new(),
+        }
+    }
+}
+
+fn build_container() -> Container<Query, Mutation, EmptySubscription> {
+    build_container_with(Query::default())
+}
+
+fn build_container_with(query: Query) -> Container<Query, Mutation, EmptySubscription> {
+    let contents = schema_content("./tests/schemas/incremental.graphql");
+    Container::new(
+        &[contents.as_str()],
+        query,
+        Mutation,
+        EmptySubscription,
+        Default::default(),
+    )
+    .unwrap()
+}
+
+async fn execute_payloads(
+    container: &Container<Query, Mutation, EmptySubscription>,
+    query_doc: &str,
+) -> Vec<String> {
+    let req = build_test_request(query_doc, None, Default::default());
+    execute_incremental(container, req)
+        .map(|payload| serde_json::to_string(&payload).unwrap())
+        .collect()
+        .await
+}
+
+#[tokio::test]
+pub async fn test_defer() {
+    let container = build_container();
+
+    let query_doc = r#"{ hero { name ... @defer(label: "friends") { friends { name } } } }"#;
+    assert_eq!(
+        execute_payloads(&container, query_doc).await,
+        vec![
+            r#"{"data":{"hero":{"name":"Luke"}},"hasNext":true}"#,
+            r#"{"data":{"friends":[{"name":"Han"},{"name":"Leia"}]},"path":["hero"],"label":"friends","hasNext":false}"#,
+        ]
+    );
+
+    let query_doc = r#"{ heroes { name ...Friends @defer } } fragment Friends on Character { friends { name } }"#;
+    assert_eq!(
+        execute_payloads(&container, query_doc).await,
+        vec![
+            r#"{"data":{"heroes":[{"name":"Luke"},{"name":"Han"}]},"hasNext":true}"#,
+            r#"{"data":{"friends":[{"name":"Han"},{"name":"Leia"}]},"path":["heroes",0],"hasNext":true}"#,
+            r#"{"data":{"friends":[{"name":"Chewbacca"}]},"path":["heroes",1],"hasNext":false}"#,
+        ]
+    );
+
+    let query_doc = r#"{ hero { name ... @defer(if: false) { friends { name } } } }"#;
+    assert_eq!(
+        execute_payloads(&container, query_doc).await,
+        vec![
+            r#"{"data":{"hero":{"name":"Luke","friends":[{"name":"Han"},{"name":"Leia"}]}},"hasNext":false}"#,
+        ]
+    );
+}
+
+#[tokio::test]
+pub async fn test_stream() {
+    let container = build_container();
+
+    let query_doc = r#"{ numbers @stream(initialCount: 1, label: "numbers") hero { name } }"#;
+    assert_eq!(
+        execute_payloads(&container, query_doc).await,
+        vec![
+            r#"{"data":{"numbers":[1],"hero":{"name":"Luke"}},"hasNext":true}"#,
+            r#"{"items":[2],"path":["numbers",1],"label":"numbers","hasNext":true}"#,
+            r#"{"items":[3],"path":["numbers",2],"label":"numbers","hasNext":true}"#,
+            r#"{"items":[4],"path":["numbers",3],"label":"numbers","hasNext":false}"#,
+        ]
+    );
+
+    let query_doc = r#"{ hero { friends @stream { name } } }"#;
+    assert_eq!(
+        execute_payloads(&container, query_doc).await,
+        vec![
+            r#"{"data":{"hero":{"friends":[]}},"hasNext":true}"#,
+            r#"{"items":[{"name":"Han"}],"path":["hero","friends",0],"hasNext":true}"#,
+            r#"{"items":[{"name":"Leia"}],"path":["hero","friends",1],"hasNext":false}"#,
+        ]
+    );
+}
+
+#[tokio::test]
+pub async fn test_incremental_resolves_fields_once() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let container = build_container_with(Query {
+        calls: calls.clone(),
+    });
+
+    let query_doc = r#"{ hero { name ... @defer { friends { name } } } heroes @stream(initialCount: 1) { name ... @defer { friends { name } } } }"#;
+    assert_eq!(
+        execute_payloads(&container, query_doc).await,
+        vec![
+            r#"{"data":{"hero":{"name":"Luke"},"heroes":[{"name":"Luke"}]},"hasNext":true}"#,
+            r#"{"data":{"friends":[{"name":"Han"},{"name":"Leia"}]},"path":["hero"],"hasNext":true}"#,
+            r#"{"data":{"friends":[{"name":"Han"},{"name":"Leia"}]},"path":["heroes",0],"hasNext":true}"#,
+            r#"{"items":[{"name":"Han"}],"path":["heroes",1],"hasNext":true}"#,
+            r#"{"data":{"friends":[{"name":"Chewbacca"}]},"path":["heroes",1],"hasNext":false}"#,
+        ]
+    );
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+pub async fn test_execute_resolves_incremental_directives() {
+    let container = build_container();
+
+    let query_doc =
+        r#"{ numbers @stream(initialCount: 1) hero { name ... @defer { friends { name } } } }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let expected_response = r#"{"data":{"numbers":[1,2,3,4],"hero":{"name":"Luke","friends":[{"name":"Han"},{"name":"Leia"}]}}}"#;
+    check_gql_response(req, expected_response, &container).await;
+}
+
+#[tokio::test]
+pub async fn test_mutation_is_not_deferred() {
+    let container = build_container();
+
+    let query_doc =
+        r#"mutation { create_hero(name: "Rey") { ... @defer { name } friends { name } } }"#;
+    assert_eq!(
+        execute_payloads(&container, query_doc).await,
+        vec![r#"{"data":{"create_hero":{"name":"Rey","friends":[]}},"hasNext":false}"#]
+    );
+}
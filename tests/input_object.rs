@@ -32,3 +32,60 @@ pub async fn test_input_obj() {
     let expected_response = r#"{"data":{"input_test":"test*2"}}"#;
     check_gql_response(req, expected_response, &container).await;
 }
+
+#[tokio::test]
+pub async fn test_default_values() {
+    struct Query;
+
+    #[derive(GqlInputObject)]
+    pub struct NestedInputObj {
+        values: Option<Vec<i32>>,
+    }
+
+    #[derive(GqlInputObject)]
+    pub struct DefaultInputObj {
+        str_value: Option<String>,
+        int_value: Option<i32>,
+        nested: Option<NestedInputObj>,
+    }
+
+    #[GqlType]
+    impl Query {
+        async fn default_test(&self, value: Option<i32>, input: Option<DefaultInputObj>) -> String {
+            let input = input.unwrap();
+            format!(
+                "{:?} {:?} {:?} {:?}",
+                value,
+                input.str_value,
+                input.int_value,
+                input.nested.and_then(|nested| nested.values)
+            )
+        }
+    }
+    let contents = schema_content("./tests/schemas/input_object.graphql");
+
+    let container = Container::new(
+        &vec![contents.as_str()],
+        Query,
+        EmptyMutation,
+        EmptySubscription,
+        Default::default(),
+    )
+    .unwrap();
+
+    let query_doc = r#"{ default_test }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let expected_response = r#"{"data":{"default_test":"Some(10) Some(\"arg\") Some(3) None"}}"#;
+    check_gql_response(req, expected_response, &container).await;
+
+    let query_doc = r#"{ default_test(value: 1, input: { int_value: 5, nested: {} }) }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let expected_response =
+        r#"{"data":{"default_test":"Some(1) Some(\"field\") Some(5) Some([1, 2])"}}"#;
+    check_gql_response(req, expected_response, &container).await;
+
+    let query_doc = r#"query Test($value: Int) { default_test(value: $value) }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let expected_response = r#"{"data":{"default_test":"Some(10) Some(\"arg\") Some(3) None"}}"#;
+    check_gql_response(req, expected_response, &container).await;
+}
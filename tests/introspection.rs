@@ -23,7 +23,7 @@ async fn test_object_introspection() {
 
     let query = r#"{ __type(name: "Cat") { kind name description fields {name description type isDeprecated} interfaces {name} } }"#;
     let req = build_test_request(query, None, Default::default());
-    let expected_response = r#"{"data":{"__type":{"description":null,"fields":[{"description":null,"isDeprecated":false,"name":"name","type":{}},{"description":null,"isDeprecated":false,"name":"meows","type":{}}],"interfaces":[{"name":"Pet"}],"kind":"OBJECT","name":"Cat"}}}"#;
+    let expected_response = r#"{"data":{"__type":{"kind":"OBJECT","name":"Cat","description":null,"fields":[{"name":"name","description":null,"type":{},"isDeprecated":false},{"name":"meows","description":null,"type":{},"isDeprecated":false}],"interfaces":[{"name":"Pet"}]}}}"#;
     check_gql_response(req, expected_response, &container).await;
 }
 
@@ -50,7 +50,7 @@ async fn test_interface_introspection() {
 
     let query = r#"{ __type(name: "Pet") { kind name description fields {name description type isDeprecated} } }"#;
     let req = build_test_request(query, None, Default::default());
-    let expected_response = r#"{"data":{"__type":{"description":null,"fields":[{"description":null,"isDeprecated":false,"name":"name","type":{}}],"kind":"INTERFACE","name":"Pet"}}}"#;
+    let expected_response = r#"{"data":{"__type":{"kind":"INTERFACE","name":"Pet","description":null,"fields":[{"name":"name","description":null,"type":{},"isDeprecated":false}]}}}"#;
     check_gql_response(req, expected_response, &container).await;
 }
 
@@ -77,7 +77,7 @@ async fn test_input_object_introspection() {
 
     let query = r#"{ __type(name: "TestInput") { kind name description inputFields {name} } }"#;
     let req = build_test_request(query, None, Default::default());
-    let expected_response = r#"{"data":{"__type":{"description":null,"inputFields":[{"name":"int_field"},{"name":"str_field"}],"kind":"INPUT_OBJECT","name":"TestInput"}}}"#;
+    let expected_response = r#"{"data":{"__type":{"kind":"INPUT_OBJECT","name":"TestInput","description":null,"inputFields":[{"name":"int_field"},{"name":"str_field"}]}}}"#;
     check_gql_response(req, expected_response, &container).await;
 }
 
@@ -104,7 +104,7 @@ async fn test_enum_introspection() {
 
     let query = r#"{ __type(name: "Country") { kind name description enumValues {name} } }"#;
     let req = build_test_request(query, None, Default::default());
-    let expected_response = r#"{"data":{"__type":{"description":null,"enumValues":[{"name":"JAPAN"},{"name":"AMERICA"},{"name":"CHINA"}],"kind":"ENUM","name":"Country"}}}"#;
+    let expected_response = r#"{"data":{"__type":{"kind":"ENUM","name":"Country","description":null,"enumValues":[{"name":"JAPAN"},{"name":"AMERICA"},{"name":"CHINA"}]}}}"#;
     check_gql_response(req, expected_response, &container).await;
 }
 
@@ -131,7 +131,7 @@ async fn test_union_introspection() {
 
     let query = r#"{ __type(name: "Animal") { kind name description possibleTypes {name} } }"#;
     let req = build_test_request(query, None, Default::default());
-    let expected_response = r#"{"data":{"__type":{"description":null,"kind":"UNION","name":"Animal","possibleTypes":[{"name":"Dog"},{"name":"Cat"}]}}}"#;
+    let expected_response = r#"{"data":{"__type":{"kind":"UNION","name":"Animal","description":null,"possibleTypes":[{"name":"Dog"},{"name":"Cat"}]}}}"#;
     check_gql_response(req, expected_response, &container).await;
 }
 
@@ -159,6 +159,6 @@ async fn test_scalar_introspection() {
     let query = r#"{ __type(name: "DateTime") { kind name description } }"#;
     let req = build_test_request(query, None, Default::default());
     let expected_response =
-        r#"{"data":{"__type":{"description":null,"kind":"SCALAR","name":"DateTime"}}}"#;
+        r#"{"data":{"__type":{"kind":"SCALAR","name":"DateTime","description":null}}}"#;
     check_gql_response(req, expected_response, &container).await;
 }
@@ -0,0 +1,205 @@
+use rusty_gql::*;
+
+struct Person {
+    name: Option<String>,
+    nickname: Option<String>,
+}
+
+#[GqlType]
+impl Person {
+    async fn name(&self) -> Option<String> {
+        self.name.clone()
+    }
+
+    async fn nickname(&self) -> Result<Option<String>, Error> {
+        match &self.nickname {
+            Some(nickname) => Ok(Some(nickname.clone())),
+            None => Err(Error::new("nickname is not found")),
+        }
+    }
+
+    async fn age(&self) -> Result<i32, Error> {
+        Err(Error::new("age is private"))
+    }
+
+    async fn description(&self, ctx: &Context<'_>) -> Option<String> {
+        ctx.add_error(&GqlError::new("description is deprecated", None));
+        None
+    }
+}
+
+fn build_persons() -> Vec<Person> {
+    vec![
+        Person {
+            name: Some("Tom".to_string()),
+            nickname: None,
+        },
+        Person {
+            name: None,
+            nickname: None,
+        },
+    ]
+}
+
+struct Query;
+
+#[GqlType]
+impl Query {
+    async fn person(&self) -> Person {
+        Person {
+            name: None,
+            nickname: Some("Tom".to_string()),
+        }
+    }
+
+    async fn required_person(&self) -> Person {
+        Person {
+            name: None,
+            nickname: Some("Tom".to_string()),
+        }
+    }
+
+    async fn persons(&self) -> Vec<Person> {
+        build_persons()
+    }
+
+    async fn required_persons(&self) -> Vec<Person> {
+        build_persons()
+    }
+
+    async fn nicknames(&self) -> Vec<Option<String>> {
+        vec![Some("Tom".to_string()), None]
+    }
+
+    async fn value(&self) -> i32 {
+        10
+    }
+}
+
+fn build_container() -> Container<Query, EmptyMutation, EmptySubscription> {
+    let contents = schema_content("./tests/schemas/null_propagation.graphql");
+    Container::new(
+        &vec![contents.as_str()],
+        Query,
+        EmptyMutation,
+        EmptySubscription,
+        Default::default(),
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+pub async fn test_nullable_field_error() {
+    let container = build_container();
+
+    let query_doc = r#"{ person { nickname } value }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let expected_response = r#"{"data":{"person":{"nickname":"Tom"},"value":10}}"#;
+    check_gql_response(req, expected_response, &container).await;
+
+    let query_doc = r#"{ persons { nickname } }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let res = execute(&container, req).await;
+    assert_eq!(
+        serde_json::to_string(&res.data).unwrap(),
+        r#"{"persons":[{"nickname":null},{"nickname":null}]}"#
+    );
+    assert_eq!(res.errors.len(), 2);
+    assert_eq!(res.errors[0].message, "nickname is not found");
+}
+
+#[tokio::test]
+pub async fn test_non_null_field_propagates_to_nullable_parent() {
+    let container = build_container();
+
+    let query_doc = r#"{ person { name nickname } value }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let expected_response = r#"{"data":{"person":null,"value":10},"errors":[{"message":"Cannot return null for non-nullable field Person.name","locations":[{"line":1,"column":12}],"path":["person","name"],"extensions":null}]}"#;
+    check_gql_response(req, expected_response, &container).await;
+
+    let query_doc = r#"{ value nicknames }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let expected_response = r#"{"data":{"value":10,"nicknames":null},"errors":[{"message":"Cannot return null for non-nullable list item","locations":[{"line":1,"column":9}],"path":["nicknames",1],"extensions":null}]}"#;
+    check_gql_response(req, expected_response, &container).await;
+
+    let query_doc = r#"{ person { age } }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let expected_response = r#"{"data":{"person":null},"errors":[{"message":"age is private","locations":[{"line":1,"column":12}],"path":["person","age"],"extensions":null}]}"#;
+    check_gql_response(req, expected_response, &container).await;
+}
+
+#[tokio::test]
+pub async fn test_non_null_field_propagates_to_root() {
+    let container = build_container();
+
+    let query_doc = r#"{ required_person { name } value }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let expected_response = r#"{"data":null,"errors":[{"message":"Cannot return null for non-nullable field Person.name","locations":[{"line":1,"column":21}],"path":["required_person","name"],"extensions":null}]}"#;
+    check_gql_response(req, expected_response, &container).await;
+}
+
+#[tokio::test]
+pub async fn test_non_null_field_propagates_to_list_item() {
+    let container = build_container();
+
+    let query_doc = r#"{ persons { name } }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let res = execute(&container, req).await;
+    assert_eq!(
+        serde_json::to_string(&res.data).unwrap(),
+        r#"{"persons":[{"name":"Tom"},null]}"#
+    );
+    assert_eq!(res.errors.len(), 1);
+
+    let query_doc = r#"{ required_persons { name } }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let res = execute(&container, req).await;
+    assert_eq!(
+        serde_json::to_string(&res.data).unwrap(),
+        r#"{"required_persons":null}"#
+    );
+    assert_eq!(res.errors.len(), 1);
+    assert_eq!(
+        res.errors[0].message,
+        "Cannot return null for non-nullable field Person.name"
+    );
+}
+
+#[tokio::test]
+pub async fn test_error_path() {
+    let container = build_container();
+
+    let query_doc = r#"{ persons { nickname } }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let res = execute(&container, req).await;
+    assert_eq!(
+        serde_json::to_string(&res.errors[0].path).unwrap(),
+        r#"["persons",0,"nickname"]"#
+    );
+    assert_eq!(
+        serde_json::to_string(&res.errors[1].path).unwrap(),
+        r#"["persons",1,"nickname"]"#
+    );
+
+    let query_doc = r#"{ people: required_persons { name } }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let res = execute(&container, req).await;
+    assert_eq!(
+        res.errors[0].path,
+        vec![
+            PathSegment::Field("people".to_string()),
+            PathSegment::Index(1),
+            PathSegment::Field("name".to_string()),
+        ]
+    );
+
+    let query_doc = r#"{ persons { description } }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let res = execute(&container, req).await;
+    assert_eq!(res.errors.len(), 2);
+    assert_eq!(res.errors[0].message, "description is deprecated");
+    assert_eq!(
+        serde_json::to_string(&res.errors[1].path).unwrap(),
+        r#"["persons",1,"description"]"#
+    );
+}
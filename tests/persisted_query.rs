@@ -0,0 +1,73 @@
+use rusty_gql::*;
+
+struct Query;
+
+#[GqlType]
+impl Query {
+    async fn value(&self) -> String {
+        "value".to_string()
+    }
+}
+
+// The SHA-256 hash of "{ value }".
+const HASH: &str = "854174ebed716fe24fd6659c30290aecd9bc1d17dc4f47939a1848a1b8ed3c6b";
+
+fn build_container() -> Container<Query, EmptyMutation, EmptySubscription> {
+    let contents = schema_content("./tests/schemas/persisted_query.graphql");
+    Container::new(
+        &vec![contents.as_str()],
+        Query,
+        EmptyMutation,
+        EmptySubscription,
+        Default::default(),
+    )
+    .unwrap()
+}
+
+fn build_request(query: &str, hash: &str) -> Request {
+    let body = serde_json::json!({
+        "query": query,
+        "extensions": {"persistedQuery": {"version": 1, "sha256Hash": hash}}
+    });
+    serde_json::from_value(body).unwrap()
+}
+
+#[tokio::test]
+pub async fn test_persisted_query() {
+    let container = build_container().persisted_query_store(LruPersistedQueryStore::new(10));
+
+    let expected_response = r#"{"data":null,"errors":[{"message":"PersistedQueryNotFound","locations":[],"path":[],"extensions":{"errorType":"NotFound","errorDetail":null,"origin":null,"debugInfo":null,"debugUri":null}}]}"#;
+    check_gql_response(build_request("", HASH), expected_response, &container).await;
+
+    let expected_response = r#"{"data":{"value":"value"}}"#;
+    check_gql_response(
+        build_request("{ value }", HASH),
+        expected_response,
+        &container,
+    )
+    .await;
+    check_gql_response(build_request("", HASH), expected_response, &container).await;
+}
+
+#[tokio::test]
+pub async fn test_persisted_query_hash_mismatch() {
+    let container = build_container().persisted_query_store(LruPersistedQueryStore::new(10));
+
+    let req = build_request("{ __typename }", HASH);
+    let response = execute(&container, req).await;
+    assert_eq!(
+        response.errors[0].message,
+        "provided sha does not match query"
+    );
+
+    let response = execute(&container, build_request("", HASH)).await;
+    assert_eq!(response.errors[0].message, "PersistedQueryNotFound");
+}
+
+#[tokio::test]
+pub async fn test_persisted_query_not_supported() {
+    let container = build_container();
+
+    let response = execute(&container, build_request("", HASH)).await;
+    assert_eq!(response.errors[0].message, "PersistedQueryNotSupported");
+}
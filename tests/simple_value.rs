@@ -90,12 +90,18 @@ pub async fn test_object() {
 
     let person_query = r#"{ person(id: 1) { name age description } }"#;
     let req = build_test_request(person_query, None, Default::default());
-    let expected = r#"{"data":{"person":{"age":20,"description":"description","name":"Tom"}}}"#;
+    let expected = r#"{"data":{"person":{"name":"Tom","age":20,"description":"description"}}}"#;
     check_gql_response(req, expected, &container).await;
 
     let partly_person_query = r#"{ person(id: 1) { name age } }"#;
     let req = build_test_request(partly_person_query, None, Default::default());
-    let expected = r#"{"data":{"person":{"age":20,"name":"Tom"}}}"#;
+    let expected = r#"{"data":{"person":{"name":"Tom","age":20}}}"#;
+    check_gql_response(req, expected, &container).await;
+
+    let ordered_person_query =
+        r#"{ person(id: 1) { description age } person(id: 1) { name age } }"#;
+    let req = build_test_request(ordered_person_query, None, Default::default());
+    let expected = r#"{"data":{"person":{"description":"description","age":20,"name":"Tom"}}}"#;
     check_gql_response(req, expected, &container).await;
 }
 
@@ -152,6 +158,6 @@ pub async fn test_list() {
     let query_doc = r#"{ persons {name age} }"#;
     let req = build_test_request(query_doc, None, Default::default());
     let expected_response =
-        r#"{"data":{"persons":[{"age":20,"name":"Tom"},{"age":10,"name":"Mary"}]}}"#;
+        r#"{"data":{"persons":[{"name":"Tom","age":20},{"name":"Mary","age":10}]}}"#;
     check_gql_response(req, expected_response, &container).await;
 }
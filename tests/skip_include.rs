@@ -0,0 +1,131 @@
+use rusty_gql::*;
+
+struct Person {
+    name: String,
+    age: i32,
+}
+
+#[GqlType]
+impl Person {
+    async fn name(&self) -> String {
+        self.name.clone()
+    }
+    async fn age(&self) -> i32 {
+        self.age
+    }
+}
+
+struct Query;
+
+#[GqlType]
+impl Query {
+    async fn value(&self) -> Option<i32> {
+        Some(10)
+    }
+    async fn persons(&self) -> Vec<Person> {
+        vec![Person {
+            name: "Tom".to_string(),
+            age: 20,
+        }]
+    }
+}
+
+fn build_container() -> Container<Query, EmptyMutation, EmptySubscription> {
+    let contents = schema_content("./tests/schemas/test_schema.graphql");
+    Container::new(
+        &vec![contents.as_str()],
+        Query,
+        EmptyMutation,
+        EmptySubscription,
+        Default::default(),
+    )
+    .unwrap()
+}
+
+fn build_variables(variables: &str) -> Variables {
+    serde_json::from_str::<Variables>(variables).unwrap()
+}
+
+#[tokio::test]
+pub async fn test_skip_and_include_on_field() {
+    let container = build_container();
+
+    let query_doc = r#"{ value @skip(if: true) persons { name age @include(if: false) } }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let expected_response = r#"{"data":{"persons":[{"name":"Tom"}]}}"#;
+    check_gql_response(req, expected_response, &container).await;
+
+    let query_doc = r#"query Test($skip: Boolean!, $include: Boolean!) {
+        value @skip(if: $skip)
+        persons { name @include(if: $include) age }
+    }"#;
+    let variables = build_variables(r#"{"skip": true, "include": false}"#);
+    let req = build_test_request(query_doc, None, variables);
+    let expected_response = r#"{"data":{"persons":[{"age":20}]}}"#;
+    check_gql_response(req, expected_response, &container).await;
+
+    let variables = build_variables(r#"{"skip": false, "include": true}"#);
+    let req = build_test_request(query_doc, None, variables);
+    let expected_response = r#"{"data":{"value":10,"persons":[{"name":"Tom","age":20}]}}"#;
+    check_gql_response(req, expected_response, &container).await;
+}
+
+#[tokio::test]
+pub async fn test_skip_and_include_combined() {
+    let container = build_container();
+
+    let query_doc = r#"query Test($skip: Boolean!, $include: Boolean!) {
+        value @skip(if: $skip) @include(if: $include)
+    }"#;
+    let cases = [
+        (
+            r#"{"skip": false, "include": true}"#,
+            r#"{"data":{"value":10}}"#,
+        ),
+        (r#"{"skip": true, "include": true}"#, r#"{"data":{}}"#),
+        (r#"{"skip": false, "include": false}"#, r#"{"data":{}}"#),
+        (r#"{"skip": true, "include": false}"#, r#"{"data":{}}"#),
+    ];
+    for (variables, expected_response) in cases {
+        let req = build_test_request(query_doc, None, build_variables(variables));
+        check_gql_response(req, expected_response, &container).await;
+    }
+}
+
+#[tokio::test]
+pub async fn test_skip_and_include_on_fragments() {
+    let container = build_container();
+
+    let query_doc = r#"query Test($withAge: Boolean!) {
+        persons {
+            name
+            ...PersonAge @include(if: $withAge)
+        }
+    }
+    fragment PersonAge on Person {
+        age
+    }"#;
+    let req = build_test_request(query_doc, None, build_variables(r#"{"withAge": false}"#));
+    let expected_response = r#"{"data":{"persons":[{"name":"Tom"}]}}"#;
+    check_gql_response(req, expected_response, &container).await;
+
+    let req = build_test_request(query_doc, None, build_variables(r#"{"withAge": true}"#));
+    let expected_response = r#"{"data":{"persons":[{"name":"Tom","age":20}]}}"#;
+    check_gql_response(req, expected_response, &container).await;
+
+    let query_doc = r#"query Test($withoutAge: Boolean!) {
+        persons {
+            name
+            ... on Person @skip(if: $withoutAge) {
+                age
+            }
+        }
+    }"#;
+    let req = build_test_request(query_doc, None, build_variables(r#"{"withoutAge": true}"#));
+    let expected_response = r#"{"data":{"persons":[{"name":"Tom"}]}}"#;
+    check_gql_response(req, expected_response, &container).await;
+
+    let req = build_test_request(query_doc, None, build_variables(r#"{"withoutAge": false}"#));
+    let expected_response = r#"{"data":{"persons":[{"name":"Tom","age":20}]}}"#;
+    check_gql_response(req, expected_response, &container).await;
+}
@@ -0,0 +1,110 @@
+use futures_util::{stream, Stream, StreamExt};
+use rusty_gql::*;
+
+#[tokio::test]
+pub async fn test_subscription() {
+    struct Person {
+        name: String,
+        description: Option<String>,
+        age: i32,
+    }
+
+    #[GqlType]
+    impl Person {
+        async fn name(&self) -> String {
+            self.name.clone()
+        }
+        async fn description(&self) -> Option<String> {
+            self.description.clone()
+        }
+        async fn age(&self) -> i32 {
+            self.age
+        }
+    }
+
+    struct Query;
+
+    #[GqlType]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct Subscription;
+
+    #[GqlType]
+    impl Subscription {
+        async fn values(&self, count: i32) -> impl Stream<Item = i32> {
+            stream::iter(0..count)
+        }
+
+        async fn persons(&self) -> impl Stream<Item = Person> {
+            stream::iter(vec![
+                Person {
+                    name: "Tom".to_string(),
+                    description: None,
+                    age: 20,
+                },
+                Person {
+                    name: "Mary".to_string(),
+                    description: Some("sample data".to_string()),
+                    age: 10,
+                },
+            ])
+        }
+    }
+
+    let contents = schema_content("./tests/schemas/subscription.graphql");
+
+    let container = Container::new(
+        &vec![contents.as_str()],
+        Query,
+        EmptyMutation,
+        Subscription,
+        Default::default(),
+    )
+    .unwrap();
+
+    let query_doc = r#"subscription { values(count: 3) }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let responses = execute_stream(&container, req)
+        .map(|res| serde_json::to_string(&res).unwrap())
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(
+        responses,
+        vec![
+            r#"{"data":{"values":0}}"#,
+            r#"{"data":{"values":1}}"#,
+            r#"{"data":{"values":2}}"#,
+        ]
+    );
+
+    let query_doc = r#"subscription { persons { name age } }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let responses = execute_stream(&container, req)
+        .map(|res| serde_json::to_string(&res).unwrap())
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(
+        responses,
+        vec![
+            r#"{"data":{"persons":{"name":"Tom","age":20}}}"#,
+            r#"{"data":{"persons":{"name":"Mary","age":10}}}"#,
+        ]
+    );
+
+    let query_doc = r#"{ value }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let responses = execute_stream(&container, req)
+        .map(|res| serde_json::to_string(&res).unwrap())
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(responses, vec![r#"{"data":{"value":10}}"#]);
+
+    let query_doc = r#"subscription { values(count: 3) }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let res = execute(&container, req).await;
+    assert!(res.is_error());
+}
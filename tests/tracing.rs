@@ -0,0 +1,195 @@
+#![cfg(feature = "tracing")]
+
+use std::{
+    fmt::Debug,
+    sync::{Arc, Mutex},
+};
+
+use rusty_gql::*;
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Subscriber,
+};
+use tracing_subscriber::{layer::Context as LayerContext, prelude::*, registry::LookupSpan, Layer};
+
+struct Person {
+    name: String,
+}
+
+#[GqlType]
+impl Person {
+    async fn name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+struct Query;
+
+#[GqlType]
+impl Query {
+    async fn persons(&self) -> Vec<Person> {
+        vec![Person {
+            name: "Alice".to_string(),
+        }]
+    }
+
+    async fn failure(&self) -> Result<Option<String>, String> {
+        Err("failure".to_string())
+    }
+
+    async fn missing(&self) -> Option<String> {
+        None
+    }
+}
+
+struct Fields(Vec<String>);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.push(format!("{}={:?}", field.name(), value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.push(format!("{}={}", field.name(), value));
+    }
+}
+
+/// Records every span and event as a line, e.g. `span resolve parent=execute field_name=persons`.
+#[derive(Clone, Default)]
+struct MemoryLayer(Arc<Mutex<Vec<String>>>);
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for MemoryLayer {
+    fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, ctx: LayerContext<'_, S>) {
+        let mut fields = Fields(Vec::new());
+        attrs.record(&mut fields);
+        let parent = ctx
+            .current_span()
+            .metadata()
+            .map(|metadata| metadata.name())
+            .unwrap_or_default();
+        self.0.lock().unwrap().push(format!(
+            "span {} parent={} {}",
+            attrs.metadata().name(),
+            parent,
+            fields.0.join(" ")
+        ));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: LayerContext<'_, S>) {
+        let mut fields = Fields(Vec::new());
+        values.record(&mut fields);
+        let name = ctx.span(id).map(|span| span.name()).unwrap_or_default();
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("record {} {}", name, fields.0.join(" ")));
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: LayerContext<'_, S>) {
+        let mut fields = Fields(Vec::new());
+        event.record(&mut fields);
+        let span = ctx
+            .event_span(event)
+            .map(|span| span.name())
+            .unwrap_or_default();
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("event {} {}", span, fields.0.join(" ")));
+    }
+}
+
+fn build_container() -> Container<Query, EmptyMutation, EmptySubscription> {
+    let contents = schema_content("./tests/schemas/tracing.graphql");
+    Container::new(
+        &vec![contents.as_str()],
+        Query,
+        EmptyMutation,
+        EmptySubscription,
+        Default::default(),
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+pub async fn test_tracing_spans() {
+    let layer = MemoryLayer::default();
+    let _guard = tracing_subscriber::registry()
+        .with(layer.clone())
+        .set_default();
+
+    let container = build_container();
+    let query_doc = r#"query GetPersons { persons { name } failure }"#;
+    let req = build_test_request(
+        query_doc,
+        Some("GetPersons".to_string()),
+        Default::default(),
+    );
+    execute(&container, req).await;
+
+    assert_eq!(
+        *layer.0.lock().unwrap(),
+        vec![
+            "span request parent= operation_name=GetPersons",
+            "span parse parent=request ",
+            "record request operation_type=Query",
+            "span validate parent=request ",
+            "span execute parent=request ",
+            "span resolve parent=execute parent_type=Query field_name=persons path=persons",
+            "span resolve parent=resolve parent_type=Person field_name=name path=persons.0.name",
+            "span resolve parent=execute parent_type=Query field_name=failure path=failure",
+            "event resolve message=failure",
+        ]
+    );
+}
+
+#[tokio::test]
+pub async fn test_tracing_request_error() {
+    let layer = MemoryLayer::default();
+    let _guard = tracing_subscriber::registry()
+        .with(layer.clone())
+        .set_default();
+
+    let container = build_container();
+    let req = build_test_request("{ unknown }", None, Default::default());
+    execute(&container, req).await;
+
+    let events = layer
+        .0
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|line| line.starts_with("event"))
+        .cloned()
+        .collect::<Vec<_>>();
+    assert_eq!(
+        events,
+        vec!["event request message=Unknown field \"unknown\" on type \"Query\""]
+    );
+}
+
+#[tokio::test]
+pub async fn test_tracing_null_error() {
+    let layer = MemoryLayer::default();
+    let _guard = tracing_subscriber::registry()
+        .with(layer.clone())
+        .set_default();
+
+    let container = build_container();
+    let req = build_test_request("{ missing }", None, Default::default());
+    execute(&container, req).await;
+
+    let events = layer
+        .0
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|line| line.starts_with("event"))
+        .cloned()
+        .collect::<Vec<_>>();
+    assert_eq!(
+        events,
+        vec!["event execute message=Cannot return null for non-nullable field Query.missing"]
+    );
+}
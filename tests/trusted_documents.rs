@@ -0,0 +1,149 @@
+use rusty_gql::*;
+
+struct Query;
+
+#[GqlType]
+impl Query {
+    async fn value(&self) -> String {
+        "value".to_string()
+    }
+}
+
+const MANIFEST: &str = r#"{
+    "value": "query GetValue { value }",
+    "typename": "query A { __typename } query B { value }"
+}"#;
+
+fn build_container() -> Container<Query, EmptyMutation, EmptySubscription> {
+    let contents = schema_content("./tests/schemas/trusted_documents.graphql");
+    Container::new(
+        &vec![contents.as_str()],
+        Query,
+        EmptyMutation,
+        EmptySubscription,
+        Default::default(),
+    )
+    .unwrap()
+}
+
+fn build_request(body: serde_json::Value) -> Request {
+    serde_json::from_value(body).unwrap()
+}
+
+#[tokio::test]
+pub async fn test_trusted_documents() {
+    let trusted_documents = TrustedDocuments::from_manifest(MANIFEST).unwrap();
+    let container = build_container()
+        .trusted_documents(trusted_documents)
+        .unwrap();
+
+    let expected_response = r#"{"data":{"value":"value"}}"#;
+    let req = build_request(serde_json::json!({"documentId": "value"}));
+    check_gql_response(req, expected_response, &container).await;
+
+    let req = build_request(serde_json::json!({"query": "query GetValue { value }"}));
+    check_gql_response(req, expected_response, &container).await;
+
+    let req = build_request(serde_json::json!({"documentId": "typename", "operationName": "B"}));
+    check_gql_response(req, expected_response, &container).await;
+
+    let req = build_request(serde_json::json!({"documentId": "unknown"}));
+    let response = execute(&container, req).await;
+    assert_eq!(
+        response.errors[0].message,
+        r#"Unknown document ID "unknown""#
+    );
+
+    let req = build_request(serde_json::json!({"query": "{ value }"}));
+    let response = execute(&container, req).await;
+    assert_eq!(
+        response.errors[0].message,
+        "Only trusted documents can be executed"
+    );
+}
+
+#[tokio::test]
+pub async fn test_trusted_documents_bypass() {
+    let trusted_documents = TrustedDocuments::from_manifest(MANIFEST)
+        .unwrap()
+        .bypass(|req| req.operation_name.as_deref() == Some("Internal"));
+    let container = build_container()
+        .trusted_documents(trusted_documents)
+        .unwrap();
+
+    let req = build_request(
+        serde_json::json!({"query": "query Internal { value }", "operationName": "Internal"}),
+    );
+    let expected_response = r#"{"data":{"value":"value"}}"#;
+    check_gql_response(req, expected_response, &container).await;
+
+    let req = build_request(
+        serde_json::json!({"query": "query External { value }", "operationName": "External"}),
+    );
+    let response = execute(&container, req).await;
+    assert!(response.is_error());
+}
+
+#[tokio::test]
+pub async fn test_invalid_trusted_documents() {
+    let trusted_documents =
+        TrustedDocuments::from_manifest(r#"{"invalid": "query Invalid { unknown }"}"#).unwrap();
+    let error = build_container()
+        .trusted_documents(trusted_documents)
+        .err()
+        .unwrap();
+    assert_eq!(
+        error.message,
+        r#"Trusted document "invalid" is invalid: Unknown field "unknown" on type "Query""#
+    );
+
+    let trusted_documents =
+        TrustedDocuments::from_manifest(r#"{"mixed": "query Named { value } { unknown }"}"#)
+            .unwrap();
+    let error = build_container()
+        .trusted_documents(trusted_documents)
+        .err()
+        .unwrap();
+    assert_eq!(
+        error.message,
+        r#"Trusted document "mixed" is invalid: an anonymous operation must be the only operation in the document"#
+    );
+
+    assert!(TrustedDocuments::from_manifest("[]").is_err());
+}
+
+#[tokio::test]
+pub async fn test_document_id_without_trusted_documents() {
+    let container = build_container();
+
+    let req = build_request(serde_json::json!({"documentId": "value"}));
+    let response = execute(&container, req).await;
+    assert_eq!(response.errors[0].message, "Document IDs are not supported");
+}
+
+#[tokio::test]
+pub async fn test_untrusted_persisted_query_is_not_stored() {
+    let trusted_documents = TrustedDocuments::from_manifest(MANIFEST).unwrap();
+    let container = build_container()
+        .trusted_documents(trusted_documents)
+        .unwrap()
+        .persisted_query_store(LruPersistedQueryStore::new(10));
+    // The SHA-256 hash of "{ value }".
+    let hash = "854174ebed716fe24fd6659c30290aecd9bc1d17dc4f47939a1848a1b8ed3c6b";
+
+    let req = build_request(serde_json::json!({
+        "query": "{ value }",
+        "extensions": {"persistedQuery": {"version": 1, "sha256Hash": hash}}
+    }));
+    let response = execute(&container, req).await;
+    assert_eq!(
+        response.errors[0].message,
+        "Only trusted documents can be executed"
+    );
+
+    let req = build_request(serde_json::json!({
+        "extensions": {"persistedQuery": {"version": 1, "sha256Hash": hash}}
+    }));
+    let response = execute(&container, req).await;
+    assert_eq!(response.errors[0].message, "PersistedQueryNotFound");
+}
@@ -118,6 +118,6 @@ pub async fn test_union() {
         }
     }}"#;
     let req = build_test_request(query_doc, None, Default::default());
-    let expected_response = r#"{"data":{"search_animal":{"age":20,"name":"Tom"}}}"#;
+    let expected_response = r#"{"data":{"search_animal":{"name":"Tom","age":20}}}"#;
     check_gql_response(req, expected_response, &container).await;
 }
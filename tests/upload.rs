@@ -0,0 +1,282 @@
+use std::io::Read;
+
+use futures_util::io::Cursor;
+use rusty_gql::*;
+
+struct Query;
+
+#[GqlType]
+impl Query {
+    async fn value(&self) -> Option<i32> {
+        None
+    }
+}
+
+struct Mutation;
+
+fn describe(upload: UploadValue) -> String {
+    let filename = upload.filename.clone();
+    let content_type = upload.content_type.clone().unwrap_or_default();
+    let mut content = String::new();
+    upload
+        .into_read()
+        .unwrap()
+        .read_to_string(&mut content)
+        .unwrap();
+    format!("{} {} {}", filename, content_type, content)
+}
+
+#[allow(non_snake_case)]
+#[GqlType]
+impl Mutation {
+    async fn singleUpload(&self, ctx: &Context<'_>, file: Upload) -> Result<String, GqlError> {
+        Ok(describe(file.value(ctx)?))
+    }
+
+    async fn multipleUpload(
+        &self,
+        ctx: &Context<'_>,
+        files: Vec<Upload>,
+    ) -> Result<Vec<String>, GqlError> {
+        files
+            .iter()
+            .map(|file| file.value(ctx).map(describe))
+            .collect()
+    }
+}
+
+fn build_container() -> Container<Query, Mutation, EmptySubscription> {
+    let contents = schema_content("./tests/schemas/upload.graphql");
+    Container::new(
+        &vec![contents.as_str()],
+        Query,
+        Mutation,
+        EmptySubscription,
+        Default::default(),
+    )
+    .unwrap()
+}
+
+const BOUNDARY: &str = "boundary";
+
+fn content_type() -> String {
+    format!("multipart/form-data; boundary={}", BOUNDARY)
+}
+
+/// Builds a multipart body from `(name, filename, content)`. A part without a filename is a form field.
+fn multipart_body(parts: &[(&str, Option<&str>, &str)]) -> Cursor<Vec<u8>> {
+    let mut body = String::new();
+    for (name, filename, content) in parts {
+        body.push_str(&format!("--{}\r\n", BOUNDARY));
+        match filename {
+            Some(filename) => body.push_str(&format!(
+                "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\nContent-Type: text/plain\r\n\r\n",
+                name, filename
+            )),
+            None => body.push_str(&format!(
+                "Content-Disposition: form-data; name=\"{}\"\r\n\r\n",
+                name
+            )),
+        }
+        body.push_str(content);
+        body.push_str("\r\n");
+    }
+    body.push_str(&format!("--{}--\r\n", BOUNDARY));
+    Cursor::new(body.into_bytes())
+}
+
+#[tokio::test]
+pub async fn test_single_upload() {
+    let container = build_container();
+    let body = multipart_body(&[
+        (
+            "operations",
+            None,
+            r#"{"query": "mutation ($file: Upload!) { singleUpload(file: $file) }", "variables": {"file": null}}"#,
+        ),
+        ("map", None, r#"{"0": ["variables.file"]}"#),
+        ("0", Some("a.txt"), "file a"),
+    ]);
+    let req = receive_multipart_request(body, &content_type(), Default::default())
+        .await
+        .unwrap();
+
+    let response = execute_batch(&container, req).await;
+    assert_eq!(
+        serde_json::to_string(&response).unwrap(),
+        r#"{"data":{"singleUpload":"a.txt text/plain file a"}}"#
+    );
+}
+
+#[tokio::test]
+pub async fn test_batch_upload() {
+    let container = build_container();
+    let body = multipart_body(&[
+        (
+            "operations",
+            None,
+            r#"[
+                {"query": "mutation ($files: [Upload!]!) { multipleUpload(files: $files) }", "variables": {"files": [null, null]}},
+                {"query": "mutation ($file: Upload!) { singleUpload(file: $file) }", "variables": {"file": null}}
+            ]"#,
+        ),
+        (
+            "map",
+            None,
+            r#"{"0": ["0.variables.files.0", "1.variables.file"], "1": ["0.variables.files.1"]}"#,
+        ),
+        ("0", Some("a.txt"), "file a"),
+        ("1", Some("b.txt"), "file b"),
+    ]);
+    let req = receive_multipart_request(body, &content_type(), Default::default())
+        .await
+        .unwrap();
+
+    let response = execute_batch(&container, req).await;
+    assert_eq!(
+        serde_json::to_string(&response).unwrap(),
+        r#"[{"data":{"multipleUpload":["a.txt text/plain file a","b.txt text/plain file b"]}},{"data":{"singleUpload":"a.txt text/plain file a"}}]"#
+    );
+}
+
+#[tokio::test]
+pub async fn test_forged_upload() {
+    let container = build_container();
+    let body = multipart_body(&[
+        (
+            "operations",
+            None,
+            r#"[
+                {"query": "mutation ($file: Upload!) { singleUpload(file: $file) }", "variables": {"file": null}},
+                {"query": "mutation ($file: Upload!) { singleUpload(file: $file) }", "variables": {"file": "0"}}
+            ]"#,
+        ),
+        ("map", None, r#"{"0": ["0.variables.file"]}"#),
+        ("0", Some("a.txt"), "file a"),
+    ]);
+    let req = receive_multipart_request(body, &content_type(), Default::default())
+        .await
+        .unwrap();
+    let response = execute_batch(&container, req).await;
+    let response = serde_json::to_value(&response).unwrap();
+    assert_eq!(
+        response[0]["data"]["singleUpload"],
+        "a.txt text/plain file a"
+    );
+    assert!(response[1]["errors"].is_array());
+
+    // The upload of another request of the batch is not accessible.
+    let body = multipart_body(&[
+        (
+            "operations",
+            None,
+            r#"[
+                {"query": "mutation ($file: Upload!) { singleUpload(file: $file) }", "variables": {"file": null}},
+                {"query": "mutation ($file: Upload!) { singleUpload(file: $file) }", "variables": {"file": null}}
+            ]"#,
+        ),
+        ("map", None, r#"{"0": ["0.variables.file"]}"#),
+        ("0", Some("a.txt"), "file a"),
+    ]);
+    let mut req = receive_multipart_request(body, &content_type(), Default::default())
+        .await
+        .unwrap();
+    if let BatchRequest::Batch(requests) = &mut req {
+        let upload = requests[0].variables.0["file"].clone();
+        requests[1].variables.0.insert("file".to_string(), upload);
+    }
+    let response = execute_batch(&container, req).await;
+    let response = serde_json::to_value(&response).unwrap();
+    assert_eq!(
+        response[0]["data"]["singleUpload"],
+        "a.txt text/plain file a"
+    );
+    assert!(response[1]["errors"][0]["message"]
+        .as_str()
+        .unwrap()
+        .contains("The uploaded file does not exist"));
+}
+
+#[tokio::test]
+pub async fn test_invalid_upload() {
+    let operations = r#"{"query": "mutation ($file: Upload!) { singleUpload(file: $file) }", "variables": {"file": null}}"#;
+
+    let body = multipart_body(&[
+        ("operations", None, operations),
+        ("map", None, r#"{"0": ["variables.file"]}"#),
+    ]);
+    let result = receive_multipart_request(body, &content_type(), Default::default()).await;
+    assert!(matches!(result, Err(HttpRequestError::InvalidRequest(_))));
+
+    let body = multipart_body(&[
+        ("operations", None, operations),
+        ("map", None, r#"{"0": ["variables.unknown"]}"#),
+        ("0", Some("a.txt"), "file a"),
+    ]);
+    let result = receive_multipart_request(body, &content_type(), Default::default()).await;
+    assert!(matches!(result, Err(HttpRequestError::InvalidRequest(_))));
+}
+
+#[tokio::test]
+pub async fn test_upload_limits() {
+    let operations = r#"{"query": "mutation ($files: [Upload!]!) { multipleUpload(files: $files) }", "variables": {"files": [null, null]}}"#;
+    let parts = [
+        ("operations", None, operations),
+        (
+            "map",
+            None,
+            r#"{"0": ["variables.files.0"], "1": ["variables.files.1"]}"#,
+        ),
+        ("0", Some("a.txt"), "file a"),
+        ("1", Some("b.txt"), "file b"),
+    ];
+
+    let options = MultipartOptions {
+        max_num_files: Some(1),
+        ..Default::default()
+    };
+    let result = receive_multipart_request(multipart_body(&parts), &content_type(), options).await;
+    assert!(matches!(result, Err(HttpRequestError::PayloadTooLarge(_))));
+
+    let options = MultipartOptions {
+        max_file_size: Some(5),
+        ..Default::default()
+    };
+    let result = receive_multipart_request(multipart_body(&parts), &content_type(), options).await;
+    assert!(matches!(result, Err(HttpRequestError::PayloadTooLarge(_))));
+
+    let options = MultipartOptions {
+        max_field_size: Some(10),
+        ..Default::default()
+    };
+    let result = receive_multipart_request(multipart_body(&parts), &content_type(), options).await;
+    assert!(matches!(
+        result,
+        Err(HttpRequestError::PayloadTooLarge(message)) if message.contains("operations")
+    ));
+
+    let options = MultipartOptions {
+        max_num_files: Some(2),
+        max_file_size: Some(6),
+        max_field_size: Some(1024),
+    };
+    let result = receive_multipart_request(multipart_body(&parts), &content_type(), options).await;
+    assert!(result.is_ok());
+
+    let map = (0..11)
+        .map(|i| format!(r#""{}": ["variables.files.{}"]"#, i, i))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let map = format!("{{{}}}", map);
+    let parts = [
+        ("operations", None, operations),
+        ("map", None, map.as_str()),
+    ];
+    let result = receive_multipart_request(
+        multipart_body(&parts),
+        &content_type(),
+        MultipartOptions::default(),
+    )
+    .await;
+    assert!(matches!(result, Err(HttpRequestError::PayloadTooLarge(_))));
+}
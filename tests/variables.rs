@@ -33,3 +33,155 @@ pub async fn test_variables() {
     let expected_response = r#"{"data":{"twice_value":40}}"#;
     check_gql_response(req, expected_response, &container).await;
 }
+
+struct CoercionQuery;
+
+#[GqlType]
+impl CoercionQuery {
+    async fn twice_value(&self, value: i32) -> i32 {
+        value * 2
+    }
+    async fn half_value(&self, value: f64) -> f64 {
+        value / 2.0
+    }
+    async fn small_value(&self, value: i8) -> i8 {
+        value
+    }
+    async fn person_id(&self, id: ID) -> ID {
+        id
+    }
+    async fn greet(&self, name: Option<String>) -> String {
+        format!("Hello, {}", name.unwrap_or_else(|| "stranger".to_string()))
+    }
+    async fn sum(&self, values: Vec<i32>) -> i32 {
+        values.iter().sum()
+    }
+}
+
+fn build_coercion_container() -> Container<CoercionQuery, EmptyMutation, EmptySubscription> {
+    let contents = schema_content("./tests/schemas/variables.graphql");
+    Container::new(
+        &vec![contents.as_str()],
+        CoercionQuery,
+        EmptyMutation,
+        EmptySubscription,
+        Default::default(),
+    )
+    .unwrap()
+}
+
+fn build_variables(variables: &str) -> Variables {
+    serde_json::from_str::<Variables>(variables).unwrap()
+}
+
+#[tokio::test]
+pub async fn test_variable_default_values() {
+    let container = build_coercion_container();
+
+    let query_doc = r#"query Test($value: Int = 3, $name: String = "Tom") {
+        twice_value(value: $value)
+        greet(name: $name)
+    }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let expected_response = r#"{"data":{"twice_value":6,"greet":"Hello, Tom"}}"#;
+    check_gql_response(req, expected_response, &container).await;
+
+    let req = build_test_request(
+        query_doc,
+        None,
+        build_variables(r#"{"value": 5, "name": null}"#),
+    );
+    let expected_response = r#"{"data":{"twice_value":10,"greet":"Hello, stranger"}}"#;
+    check_gql_response(req, expected_response, &container).await;
+
+    let query_doc = r#"query Test($name: String) { greet(name: $name) }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let expected_response = r#"{"data":{"greet":"Hello, stranger"}}"#;
+    check_gql_response(req, expected_response, &container).await;
+}
+
+#[tokio::test]
+pub async fn test_variable_coercion() {
+    let container = build_coercion_container();
+
+    let query_doc = r#"query Test($value: Float!, $id: ID!, $values: [Int!]!) {
+        half_value(value: $value)
+        person_id(id: $id)
+        sum(values: $values)
+    }"#;
+    let req = build_test_request(
+        query_doc,
+        None,
+        build_variables(r#"{"value": 3, "id": 10, "values": 4}"#),
+    );
+    let expected_response = r#"{"data":{"half_value":1.5,"person_id":"10","sum":4}}"#;
+    check_gql_response(req, expected_response, &container).await;
+}
+
+#[tokio::test]
+pub async fn test_invalid_variables() {
+    let container = build_coercion_container();
+
+    let query_doc = r#"query Test($value: Int!) { twice_value(value: $value) }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let expected_response = r#"{"data":null,"errors":[{"message":"Variable \"$value\" of required type \"Int!\" was not provided.","locations":[{"line":1,"column":12}],"path":[],"extensions":null}]}"#;
+    check_gql_response(req, expected_response, &container).await;
+
+    let req = build_test_request(query_doc, None, build_variables(r#"{"value": null}"#));
+    let res = execute(&container, req).await;
+    assert_eq!(
+        res.errors[0].message,
+        r#"Variable "$value" got invalid value; Expected non-nullable type "Int!" not to be null."#
+    );
+
+    let req = build_test_request(query_doc, None, build_variables(r#"{"value": "10"}"#));
+    let res = execute(&container, req).await;
+    assert_eq!(
+        res.errors[0].message,
+        r#"Variable "$value" got invalid value; Expected type "Int", found "10"."#
+    );
+
+    let req = build_test_request(query_doc, None, build_variables(r#"{"value": 1.5}"#));
+    let res = execute(&container, req).await;
+    assert_eq!(
+        res.errors[0].message,
+        r#"Variable "$value" got invalid value; Expected type "Int", found 1.5."#
+    );
+
+    let query_doc = r#"query Test($values: [Int!]!) { sum(values: $values) }"#;
+    let req = build_test_request(query_doc, None, build_variables(r#"{"values": [1, null]}"#));
+    let res = execute(&container, req).await;
+    assert_eq!(res.errors.len(), 1);
+    assert_eq!(res.errors[0].locations[0].column, 12);
+}
+
+#[tokio::test]
+pub async fn test_number_arguments() {
+    let container = build_coercion_container();
+
+    let query_doc = r#"{ half_value(value: 3) small_value(value: 100) }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let expected_response = r#"{"data":{"half_value":1.5,"small_value":100}}"#;
+    check_gql_response(req, expected_response, &container).await;
+
+    let query_doc = r#"{ small_value(value: 300) }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let expected_response = r#"{"data":null,"errors":[{"message":"Argument \"value\" got invalid value; 300 is out of range for i8","locations":[{"line":1,"column":3}],"path":["small_value"],"extensions":null}]}"#;
+    check_gql_response(req, expected_response, &container).await;
+
+    let query_doc = r#"{ twice_value(value: 3000000000) }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let res = execute(&container, req).await;
+    assert_eq!(
+        res.errors[0].message,
+        r#"Argument "value" got invalid value; Expected type "Int", found 3000000000."#
+    );
+
+    let query_doc = r#"{ twice_value(value: 1.5) }"#;
+    let req = build_test_request(query_doc, None, Default::default());
+    let res = execute(&container, req).await;
+    assert_eq!(
+        res.errors[0].message,
+        r#"Argument "value" got invalid value; Expected type "Int", found 1.5."#
+    );
+}
@@ -0,0 +1,270 @@
+use std::time::Duration;
+
+use futures_util::{stream, Stream, StreamExt};
+use rusty_gql::*;
+
+struct Query;
+
+#[GqlType]
+impl Query {
+    async fn value(&self) -> i32 {
+        10
+    }
+}
+
+struct Subscription;
+
+#[GqlType]
+impl Subscription {
+    async fn values(&self, count: i32) -> impl Stream<Item = i32> {
+        stream::iter(0..count)
+    }
+
+    async fn token(&self, ctx: &Context<'_>) -> impl Stream<Item = Option<String>> {
+        stream::iter(vec![ctx.data_opt::<String>().cloned()])
+    }
+
+    async fn failure(&self) -> Result<impl Stream<Item = Option<String>>, Error> {
+        Err::<stream::Empty<_>, _>(Error::new("Unavailable"))
+    }
+}
+
+fn build_container() -> Container<Query, EmptyMutation, Subscription> {
+    let contents = schema_content("./tests/schemas/subscription.graphql");
+    Container::new(
+        &vec![contents.as_str()],
+        Query,
+        EmptyMutation,
+        Subscription,
+        Default::default(),
+    )
+    .unwrap()
+}
+
+async fn serve_messages(
+    connection: WebSocketConnection<Query, EmptyMutation, Subscription>,
+    messages: Vec<&str>,
+    take: usize,
+) -> Vec<WsMessage> {
+    let messages: Vec<String> = messages.into_iter().map(|m| m.to_string()).collect();
+    let incoming = stream::iter(messages).chain(stream::pending());
+    connection.serve(incoming).take(take).collect().await
+}
+
+fn text(message: &str) -> WsMessage {
+    WsMessage::Text(message.to_string())
+}
+
+#[tokio::test]
+pub async fn test_graphql_transport_ws() {
+    let container = build_container();
+
+    let connection = WebSocketConnection::new(&container, WebSocketProtocol::GraphQLTransportWs);
+    let messages = serve_messages(
+        connection,
+        vec![
+            r#"{"type":"connection_init"}"#,
+            r#"{"type":"ping"}"#,
+            r#"{"type":"subscribe","id":"1","payload":{"query":"subscription { values(count: 2) }"}}"#,
+        ],
+        5,
+    )
+    .await;
+    assert_eq!(
+        messages,
+        vec![
+            text(r#"{"type":"connection_ack"}"#),
+            text(r#"{"type":"pong"}"#),
+            text(r#"{"type":"next","id":"1","payload":{"data":{"values":0}}}"#),
+            text(r#"{"type":"next","id":"1","payload":{"data":{"values":1}}}"#),
+            text(r#"{"type":"complete","id":"1"}"#),
+        ]
+    );
+
+    let connection = WebSocketConnection::new(&container, WebSocketProtocol::GraphQLTransportWs);
+    let messages = serve_messages(
+        connection,
+        vec![
+            r#"{"type":"connection_init"}"#,
+            r#"{"type":"subscribe","id":"1","payload":{"query":"subscription { unknown }"}}"#,
+        ],
+        2,
+    )
+    .await;
+    assert_eq!(messages[0], text(r#"{"type":"connection_ack"}"#));
+    assert!(
+        matches!(&messages[1], WsMessage::Text(message) if message.starts_with(r#"{"type":"error","id":"1""#))
+    );
+
+    // The errors of an executed subscription are sent in a `next` message.
+    let connection = WebSocketConnection::new(&container, WebSocketProtocol::GraphQLTransportWs);
+    let messages = serve_messages(
+        connection,
+        vec![
+            r#"{"type":"connection_init"}"#,
+            r#"{"type":"subscribe","id":"1","payload":{"query":"subscription { failure }"}}"#,
+        ],
+        3,
+    )
+    .await;
+    assert_eq!(messages[0], text(r#"{"type":"connection_ack"}"#));
+    assert!(
+        matches!(&messages[1], WsMessage::Text(message) if message.starts_with(r#"{"type":"next","id":"1","payload":{"data":null,"errors":[{"message":"Unavailable""#)),
+        "{:?}",
+        messages[1]
+    );
+    assert_eq!(messages[2], text(r#"{"type":"complete","id":"1"}"#));
+}
+
+#[tokio::test]
+pub async fn test_graphql_transport_ws_connection_init() {
+    let container = build_container();
+
+    let connection = WebSocketConnection::new(&container, WebSocketProtocol::GraphQLTransportWs)
+        .on_connection_init(|payload| async move {
+            let mut data = Data::default();
+            if let GqlValue::Object(obj) = payload {
+                if let Some(GqlValue::String(token)) = obj.get("token") {
+                    data.insert(token.clone());
+                }
+            }
+            Ok(data)
+        });
+    let messages = serve_messages(
+        connection,
+        vec![
+            r#"{"type":"connection_init","payload":{"token":"secret"}}"#,
+            r#"{"type":"subscribe","id":"1","payload":{"query":"subscription { token }"}}"#,
+        ],
+        3,
+    )
+    .await;
+    assert_eq!(
+        messages,
+        vec![
+            text(r#"{"type":"connection_ack"}"#),
+            text(r#"{"type":"next","id":"1","payload":{"data":{"token":"secret"}}}"#),
+            text(r#"{"type":"complete","id":"1"}"#),
+        ]
+    );
+
+    let connection = WebSocketConnection::new(&container, WebSocketProtocol::GraphQLTransportWs)
+        .on_connection_init(|_| async move { Err(GqlError::new("Forbidden", None)) });
+    let messages = serve_messages(connection, vec![r#"{"type":"connection_init"}"#], 1).await;
+    assert_eq!(
+        messages,
+        vec![WsMessage::Close(4403, "Forbidden".to_string())]
+    );
+
+    let connection = WebSocketConnection::new(&container, WebSocketProtocol::GraphQLTransportWs)
+        .connection_init_timeout(Duration::from_millis(10));
+    let messages = serve_messages(connection, vec![], 1).await;
+    assert_eq!(
+        messages,
+        vec![WsMessage::Close(
+            4408,
+            "Connection initialisation timeout".to_string()
+        )]
+    );
+
+    let connection = WebSocketConnection::new(&container, WebSocketProtocol::GraphQLTransportWs);
+    let messages = serve_messages(
+        connection,
+        vec![r#"{"type":"subscribe","id":"1","payload":{"query":"subscription { values(count: 2) }"}}"#],
+        1,
+    )
+    .await;
+    assert_eq!(
+        messages,
+        vec![WsMessage::Close(4401, "Unauthorized".to_string())]
+    );
+
+    let connection = WebSocketConnection::new(&container, WebSocketProtocol::GraphQLTransportWs);
+    let messages = serve_messages(
+        connection,
+        vec![
+            r#"{"type":"connection_init"}"#,
+            r#"{"type":"connection_init"}"#,
+        ],
+        2,
+    )
+    .await;
+    assert_eq!(
+        messages[1],
+        WsMessage::Close(4429, "Too many initialisation requests".to_string())
+    );
+}
+
+#[tokio::test]
+pub async fn test_subscriptions_transport_ws() {
+    let container = build_container();
+
+    let connection =
+        WebSocketConnection::new(&container, WebSocketProtocol::SubscriptionsTransportWs);
+    let messages = serve_messages(
+        connection,
+        vec![
+            r#"{"type":"connection_init","payload":{}}"#,
+            r#"{"type":"start","id":"1","payload":{"query":"subscription { values(count: 2) }"}}"#,
+        ],
+        4,
+    )
+    .await;
+    assert_eq!(
+        messages,
+        vec![
+            text(r#"{"type":"connection_ack"}"#),
+            text(r#"{"type":"data","id":"1","payload":{"data":{"values":0}}}"#),
+            text(r#"{"type":"data","id":"1","payload":{"data":{"values":1}}}"#),
+            text(r#"{"type":"complete","id":"1"}"#),
+        ]
+    );
+
+    let connection =
+        WebSocketConnection::new(&container, WebSocketProtocol::SubscriptionsTransportWs);
+    let messages = serve_messages(
+        connection,
+        vec![
+            r#"{"type":"connection_init"}"#,
+            r#"{"type":"start","id":"1","payload":{"query":"subscription { unknown }"}}"#,
+        ],
+        2,
+    )
+    .await;
+    assert_eq!(
+        messages[1],
+        text(
+            r#"{"type":"error","id":"1","payload":{"message":"Unknown field \"unknown\" on type \"Subscription\"","locations":[{"line":1,"column":16}],"path":[],"extensions":null}}"#
+        )
+    );
+
+    let connection =
+        WebSocketConnection::new(&container, WebSocketProtocol::SubscriptionsTransportWs);
+    let messages = serve_messages(
+        connection,
+        vec![
+            r#"{"type":"connection_init"}"#,
+            r#"{"type":"connection_terminate"}"#,
+        ],
+        2,
+    )
+    .await;
+    assert_eq!(messages, vec![text(r#"{"type":"connection_ack"}"#)]);
+}
+
+#[tokio::test]
+pub async fn test_subscriptions_transport_ws_connection_error() {
+    let container = build_container();
+
+    let connection =
+        WebSocketConnection::new(&container, WebSocketProtocol::SubscriptionsTransportWs)
+            .on_connection_init(|_| async move { Err(GqlError::new("Forbidden", None)) });
+    let messages = serve_messages(connection, vec![r#"{"type":"connection_init"}"#], 2).await;
+    assert_eq!(
+        messages,
+        vec![
+            text(r#"{"type":"connection_error","payload":{"message":"Forbidden"}}"#),
+            WsMessage::Close(4403, "Forbidden".to_string()),
+        ]
+    );
+}